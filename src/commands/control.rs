@@ -0,0 +1,256 @@
+//! Local control interface and shared command side effects.
+//!
+//! Local tools can connect to a Unix domain socket and send one JSON request
+//! per line, e.g. `{"command": "goto 2"}`. Each request is answered with the
+//! `CommandResult` as a JSON line. The socket is restricted to the owner
+//! (mode 0600) and does NOT go through the Telegram chat access check:
+//! anyone who can open the socket file can control the bot.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+use super::{BotCommand, CommandHandler, CommandResult};
+use crate::scheduler::SchedulerMessage;
+use crate::telegram::TelegramBot;
+
+/// A request received on the control socket.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ControlRequest {
+    /// Command text without the chat prefix, e.g. `"status"` or `"goto 2"`.
+    pub command: String,
+}
+
+/// Parses a JSON request line and executes it like a chat command, but
+/// without the command cooldown.
+pub async fn handle_request(handler: &CommandHandler, line: &str) -> CommandResult {
+    let request: ControlRequest = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return CommandResult::error(format!("Invalid request: {e}")),
+    };
+
+    match BotCommand::parse(&request.command, &[""]) {
+        Some(command) => handler.execute_unthrottled(command).await,
+        None => CommandResult::error(format!("Unknown command: '{}'", request.command)),
+    }
+}
+
+/// Applies the side effects of a command result (rate limit relaxation, name
+/// change, online status, update trigger).
+///
+/// A failed name change turns the result into an error.
+pub async fn apply_command_effects(
+    result: &mut CommandResult,
+    bot: &TelegramBot,
+    scheduler_tx: &mpsc::Sender<SchedulerMessage>,
+) {
+    if let Some(burst) = result.burst {
+        bot.allow_burst(
+            Duration::from_secs(burst.window_secs),
+            Duration::from_secs(burst.interval_secs),
+        )
+        .await;
+    }
+
+    if let Some(change) = &result.name_change
+        && let Err(e) = bot
+            .update_profile(Some(&change.first_name), change.last_name.as_deref(), None)
+            .await
+    {
+        result.success = false;
+        result.message = format!("✗ Failed to update name: {e}");
+    }
+
+    if let Some(presence) = result.presence
+        && let Err(e) = bot.set_offline(presence.is_offline()).await
+    {
+        result.success = false;
+        result.message = format!("✗ Failed to update online status: {e}");
+    }
+
+    if result.trigger_update {
+        let _ = scheduler_tx.send(SchedulerMessage::TriggerUpdate).await;
+    }
+}
+
+#[cfg(unix)]
+pub use socket::serve;
+
+#[cfg(unix)]
+mod socket {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::mpsc;
+    use tracing::{debug, info, warn};
+
+    use super::{apply_command_effects, handle_request};
+    use crate::commands::CommandHandler;
+    use crate::scheduler::SchedulerMessage;
+    use crate::telegram::TelegramBot;
+
+    /// Listens on a Unix domain socket for control requests.
+    ///
+    /// A stale socket file at `path` is replaced. Runs until accepting fails.
+    /// The socket is only reachable by the bot's user (mode 0600).
+    pub async fn serve(
+        path: &Path,
+        handler: Arc<CommandHandler>,
+        bot: Arc<TelegramBot>,
+        scheduler_tx: mpsc::Sender<SchedulerMessage>,
+    ) -> std::io::Result<()> {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+
+        let listener = bind_private(path)?;
+        info!("Control socket listening on {}", path.display());
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            debug!("Control socket client connected");
+
+            tokio::spawn(handle_connection(
+                stream,
+                Arc::clone(&handler),
+                Arc::clone(&bot),
+                scheduler_tx.clone(),
+            ));
+        }
+    }
+
+    /// Binds the socket inside a private (0700) directory, restricts it to
+    /// 0600 and only then moves it to `path`, so other users never get a
+    /// window to connect before the permissions are set.
+    pub(super) fn bind_private(path: &Path) -> std::io::Result<UnixListener> {
+        let file_name = path.file_name().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "no socket file name")
+        })?;
+        let mut dir_name = file_name.to_os_string();
+        dir_name.push(format!(".{}.tmp", std::process::id()));
+        let private_dir = path.with_file_name(dir_name);
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&private_dir)?;
+
+        let result = bind_in(&private_dir.join(file_name), path);
+        let _ = std::fs::remove_dir_all(&private_dir);
+        result
+    }
+
+    /// Binds at `temp`, restricts the socket and moves it to `path`.
+    fn bind_in(temp: &Path, path: &Path) -> std::io::Result<UnixListener> {
+        let listener = UnixListener::bind(temp)?;
+        std::fs::set_permissions(temp, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(temp, path)?;
+        Ok(listener)
+    }
+
+    /// Answers line-delimited requests until the client disconnects.
+    async fn handle_connection(
+        stream: UnixStream,
+        handler: Arc<CommandHandler>,
+        bot: Arc<TelegramBot>,
+        scheduler_tx: mpsc::Sender<SchedulerMessage>,
+    ) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut result = handle_request(&handler, &line).await;
+            apply_command_effects(&mut result, &bot, &scheduler_tx).await;
+
+            let mut response = serde_json::to_string(&result).unwrap_or_else(|e| {
+                warn!("Failed to encode control response: {}", e);
+                r#"{"success":false,"message":"Failed to encode result"}"#.to_owned()
+            });
+            response.push('\n');
+
+            if let Err(e) = writer.write_all(response.as_bytes()).await {
+                warn!("Failed to write control response: {}", e);
+                break;
+            }
+        }
+
+        debug!("Control socket client disconnected");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::config::DescriptionConfig;
+    use crate::scheduler::{NullStateStore, SchedulerState};
+
+    fn test_handler() -> CommandHandler {
+        CommandHandler::new(
+            vec!["/description_bot".to_owned()],
+            Arc::new(RwLock::new(SchedulerState::new())),
+            Arc::new(RwLock::new(DescriptionConfig::example())),
+            String::new(),
+            Arc::new(NullStateStore),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_status_request_roundtrip() {
+        let handler = test_handler();
+
+        let result = handle_request(&handler, r#"{"command": "status"}"#).await;
+        let json = serde_json::to_string(&result).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["success"], true);
+        assert_eq!(value["trigger_update"], false);
+        assert!(value["message"].as_str().unwrap().contains("Status:"));
+        assert_eq!(value["data"]["index"], 0);
+        assert_eq!(value["data"]["paused"], false);
+
+        // Commands without a payload omit the field
+        let result = handle_request(&handler, r#"{"command": "help"}"#).await;
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("\"data\""));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests() {
+        let handler = test_handler();
+
+        let result = handle_request(&handler, "status").await;
+        assert!(!result.success);
+        assert!(result.message.starts_with("Invalid request"));
+
+        let result = handle_request(&handler, r#"{"command": "dance"}"#).await;
+        assert!(!result.success);
+        assert!(result.message.starts_with("Unknown command"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("control_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let _listener = socket::bind_private(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let private_dir = path.with_extension(format!("sock.{}.tmp", std::process::id()));
+        assert!(!private_dir.exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
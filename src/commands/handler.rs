@@ -1,18 +1,69 @@
 //! Command handler implementation.
 
-use std::sync::Arc;
-
-use tokio::sync::RwLock;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLockReadGuard};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, warn};
 
-use super::types::{AddArgs, BotCommand, CommandResult, DurationArgs, EditArgs};
-use crate::config::{Description, DescriptionConfig, MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM};
-use crate::scheduler::SchedulerState;
+use super::info::{PremiumSource, RuntimeInfo};
+use super::paginate::paginate;
+use super::types::{
+    AddArgs, BotCommand, Burst, CommandResult, DurationArgs, EditArgs, NameChange, Presence,
+    RelocateTarget,
+};
+use crate::config::{
+    BotSettings, ChangelogEntry, Description, DescriptionConfig, Eligibility, Locale,
+    MAX_DURATION_SECS, MAX_NAME_LENGTH, OverlongPolicy, SettingsOverrides, SharedSettings,
+    TEMPLATES, TUNABLE_KEYS, ValidationError, append_entry, backup_path, changelog_path,
+    find_template, list_snapshots, load_snapshot, normalize_bio, read_entries, save_snapshot,
+    snapshots_dir, truncate_to_limit, tunable_key, utf16_len,
+};
+use crate::scheduler::{
+    NextExplanation, PauseMode, SchedulerState, StateStore, StatusSnapshot, render,
+};
+use crate::telegram::{RateLimiter, TelegramBot, TelegramError};
+use crate::util::truncate;
+
+/// Duration of a description imported from the live bio, in seconds.
+const IMPORT_DURATION_SECS: u64 = 3600;
+
+/// Default length of a `fast` window, in seconds.
+const FAST_WINDOW_SECS: u64 = 120;
+
+/// Longest `fast` window allowed, in seconds.
+const FAST_MAX_WINDOW_SECS: u64 = 600;
+
+/// Minimum seconds between profile updates during a `fast` window.
+const FAST_INTERVAL_SECS: u64 = 5;
+
+/// Changelog entries shown by `log` without a count.
+const DEFAULT_LOG_ENTRIES: usize = 10;
+
+/// Future returned by [`BioSource::current_bio`].
+pub type BioFuture<'a> = Pin<Box<dyn Future<Output = Result<String, TelegramError>> + Send + 'a>>;
+
+/// Provides the live bio for `import-current`.
+pub trait BioSource: Send + Sync {
+    /// Fetches the bio currently shown on the profile.
+    fn current_bio(&self) -> BioFuture<'_>;
+}
+
+impl BioSource for TelegramBot {
+    fn current_bio(&self) -> BioFuture<'_> {
+        Box::pin(self.get_current_bio())
+    }
+}
 
 /// Handles bot commands and manages application state.
 pub struct CommandHandler {
-    /// Command prefix (e.g., "`/description_bot`").
-    prefix: String,
+    /// Accepted command prefixes (e.g., "`/description_bot`", "`/db`").
+    prefixes: Vec<String>,
 
     /// Shared scheduler state.
     scheduler_state: Arc<RwLock<SchedulerState>>,
@@ -20,35 +71,215 @@ pub struct CommandHandler {
     /// Description configuration.
     config: Arc<RwLock<DescriptionConfig>>,
 
-    /// Path to the descriptions file (for saving changes).
-    config_path: String,
+    /// Path to the descriptions file (for saving changes), moved by
+    /// `relocate`.
+    config_path: std::sync::RwLock<String>,
+
+    /// Where state changes are persisted.
+    state_store: Arc<dyn StateStore>,
+
+    /// Bot settings (locale, limits, etc.), changed by `config set`.
+    settings: SharedSettings,
+
+    /// Whether a debounced config save is waiting to be written.
+    save_pending: Arc<AtomicBool>,
 
-    /// Path to the state file (for persisting state changes).
-    state_path: String,
+    /// Why the last debounced config save failed, until reported.
+    save_failure: Arc<std::sync::Mutex<Option<ValidationError>>>,
+
+    /// When each command (by name) last ran, for the cooldown.
+    last_executed: Mutex<HashMap<&'static str, Instant>>,
+
+    /// Where `import-current` reads the live bio from.
+    bio_source: Option<Arc<dyn BioSource>>,
+
+    /// Limiter adjusted by `rate-limit`.
+    rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Where `config set` saves changed settings (`None`: memory only).
+    settings_path: Option<PathBuf>,
+
+    /// When the handler was created, for the uptime in `info`.
+    started_at: Instant,
+
+    /// How the premium status was determined, shown by `info`.
+    premium_source: PremiumSource,
 }
 
 impl CommandHandler {
     /// Creates a new command handler.
     #[must_use]
     pub fn new(
-        prefix: String,
+        prefixes: Vec<String>,
         scheduler_state: Arc<RwLock<SchedulerState>>,
         config: Arc<RwLock<DescriptionConfig>>,
         config_path: String,
-        state_path: String,
+        state_store: Arc<dyn StateStore>,
     ) -> Self {
         Self {
-            prefix,
+            prefixes,
             scheduler_state,
             config,
-            config_path,
-            state_path,
+            config_path: std::sync::RwLock::new(config_path),
+            state_store,
+            settings: SharedSettings::default(),
+            save_pending: Arc::new(AtomicBool::new(false)),
+            save_failure: Arc::default(),
+            last_executed: Mutex::new(HashMap::new()),
+            bio_source: None,
+            rate_limiter: None,
+            settings_path: None,
+            started_at: Instant::now(),
+            premium_source: PremiumSource::default(),
+        }
+    }
+
+    /// Sets where `import-current` reads the live bio from.
+    #[must_use]
+    pub fn with_bio_source(mut self, source: Arc<dyn BioSource>) -> Self {
+        self.bio_source = Some(source);
+        self
+    }
+
+    /// Sets the rate limiter shown and adjusted by `rate-limit`.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the file `config set` saves changed settings to.
+    #[must_use]
+    pub fn with_settings_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.settings_path = Some(path.into());
+        self
+    }
+
+    /// Sets how the premium status was determined, for `info`.
+    #[must_use]
+    pub const fn with_premium_source(mut self, source: PremiumSource) -> Self {
+        self.premium_source = source;
+        self
+    }
+
+    /// Sets the bot settings used by command handlers.
+    #[must_use]
+    pub fn with_settings(self, settings: BotSettings) -> Self {
+        self.with_shared_settings(Arc::new(std::sync::RwLock::new(settings)))
+    }
+
+    /// Sets bot settings shared with other components.
+    #[must_use]
+    pub fn with_shared_settings(mut self, settings: SharedSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Returns the path of the descriptions file.
+    fn config_path(&self) -> String {
+        self.config_path
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the current bot settings.
+    fn settings(&self) -> RwLockReadGuard<'_, BotSettings> {
+        self.settings.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Validates description text, accounting for the overlong policy.
+    ///
+    /// With `Truncate`, the text that would actually be sent is validated.
+    fn validate_text(&self, text: &str, config: &DescriptionConfig) -> Result<(), String> {
+        match self.settings().on_overlong {
+            OverlongPolicy::Reject => validate_description_text(text, config),
+            OverlongPolicy::Truncate => {
+                validate_description_text(&truncate_to_limit(text, config.max_bio_length()), config)
+            }
+        }
+    }
+
+    /// Saves the config after a command changed it.
+    ///
+    /// With a debounce interval configured, the write is deferred so that a
+    /// burst of edits results in a single write. If that write fails, the
+    /// next edit is written immediately so its reply carries the error.
+    /// Call [`Self::flush_config`] to write pending changes immediately.
+    /// In read-only mode the change stays in memory only.
+    fn save_config(&self, config: &DescriptionConfig) -> Result<(), ValidationError> {
+        if self.settings().read_only {
+            warn!(
+                "Read-only mode, the change is not saved to {}",
+                self.config_path()
+            );
+            return Ok(());
+        }
+
+        let (debounce_ms, attempts) = {
+            let settings = self.settings();
+            (settings.config_save_debounce_ms, settings.save_attempts)
+        };
+        if debounce_ms == 0 || self.take_save_failure().is_some() {
+            return config.save_with_retry(self.config_path(), attempts);
         }
+
+        // A save is already scheduled and will pick up this change
+        if self.save_pending.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let config = Arc::clone(&self.config);
+        let config_path = self.config_path();
+        let save_pending = Arc::clone(&self.save_pending);
+        let save_failure = Arc::clone(&self.save_failure);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(debounce_ms)).await;
+
+            // Hold the lock while clearing the flag so no edit slips in between
+            let config = config.read().await;
+            if save_pending.swap(false, Ordering::SeqCst)
+                && let Err(e) = config.save_with_retry(&config_path, attempts)
+            {
+                warn!("Failed to save config: {}", e);
+                *save_failure.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Takes the error of a failed debounced save, if any.
+    fn take_save_failure(&self) -> Option<ValidationError> {
+        self.save_failure
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Writes any pending debounced config changes to disk, retrying a
+    /// debounced save that failed.
+    ///
+    /// Must be called before shutdown so recent edits are not lost.
+    pub async fn flush_config(&self) -> Result<(), ValidationError> {
+        let config = self.config.read().await;
+        let failed = self.take_save_failure().is_some();
+        if self.save_pending.swap(false, Ordering::SeqCst) || failed {
+            debug!("Flushing pending config changes");
+            let attempts = self.settings().save_attempts;
+            config.save_with_retry(self.config_path(), attempts)?;
+        }
+        Ok(())
+    }
+
+    /// Formats a duration using the configured locale.
+    fn format_duration(&self, secs: u64) -> String {
+        format_duration_localized(secs, self.settings().locale)
     }
 
     /// Saves the current scheduler state to disk.
-    fn save_state(&self, state: &SchedulerState) {
-        if let Err(e) = state.to_persistent().save(&self.state_path) {
+    fn save_state(&self, state: &mut SchedulerState) {
+        if let Err(e) = state.save(&*self.state_store, &self.settings()) {
             warn!("Failed to save state after command: {}", e);
         }
     }
@@ -57,7 +288,7 @@ impl CommandHandler {
     ///
     /// Returns `None` if the message is not a command.
     pub async fn try_handle(&self, message_text: &str) -> Option<CommandResult> {
-        let command = BotCommand::parse(message_text, &self.prefix)?;
+        let command = BotCommand::parse(message_text, &self.prefixes)?;
 
         debug!("Handling command: {}", command);
         let result = self.execute(command).await;
@@ -69,24 +300,168 @@ impl CommandHandler {
         Some(result)
     }
 
+    /// Checks the per-command cooldown.
+    ///
+    /// Returns the seconds left if the same command succeeded too recently.
+    async fn check_cooldown(&self, name: &'static str, now: Instant) -> Option<u64> {
+        let cooldown = Duration::from_secs(self.settings().command_cooldown_secs);
+        if cooldown.is_zero() {
+            return None;
+        }
+
+        let last_executed = self.last_executed.lock().await;
+        if let Some(&last) = last_executed.get(name) {
+            let elapsed = now.saturating_duration_since(last);
+            if elapsed < cooldown {
+                return Some(cooldown.saturating_sub(elapsed).as_secs().max(1));
+            }
+        }
+        None
+    }
+
     /// Executes a parsed command.
-    async fn execute(&self, command: BotCommand) -> CommandResult {
-        match command {
+    ///
+    /// A repeat of the same command within the cooldown of its last
+    /// successful run is rejected; failed runs don't start the cooldown.
+    pub async fn execute(&self, command: BotCommand) -> CommandResult {
+        let name = command.name();
+        if let Some(wait_secs) = self.check_cooldown(name, Instant::now()).await {
+            return CommandResult::error(format!(
+                "Slow down: '{name}' was just executed. Try again in {wait_secs}s."
+            ));
+        }
+
+        let result = self.execute_unthrottled(command).await;
+        if result.success {
+            self.last_executed.lock().await.insert(name, Instant::now());
+        }
+        result
+    }
+
+    /// Executes a parsed command without the cooldown, for the control
+    /// socket whose local callers may repeat a command on purpose.
+    pub async fn execute_unthrottled(&self, command: BotCommand) -> CommandResult {
+        let change = if command.edits_descriptions() {
+            Some((command.to_string(), self.changed_id(&command).await))
+        } else {
+            None
+        };
+
+        let result = match command {
             BotCommand::Skip => self.handle_skip().await,
             BotCommand::Status => self.handle_status().await,
-            BotCommand::List => self.handle_list().await,
+            BotCommand::List(page) => self.handle_list(page).await,
+            BotCommand::Ids => self.handle_ids().await,
             BotCommand::View(id) => self.handle_view(&id).await,
             BotCommand::Goto(target) => self.handle_goto(&target).await,
-            BotCommand::Pause => self.handle_pause().await,
+            BotCommand::Pause(mode) => self.handle_pause(mode).await,
             BotCommand::Resume => self.handle_resume().await,
             BotCommand::Reload => self.handle_reload().await,
             BotCommand::Help => self.handle_help(),
-            BotCommand::Set(text) => self.handle_set(&text).await,
+            BotCommand::Set {
+                text,
+                duration_secs,
+            } => self.handle_set(text, duration_secs).await,
+            BotCommand::Queue(item) => self.handle_queue(&item).await,
             BotCommand::Add(args) => self.handle_add(args).await,
+            BotCommand::BulkAdd(lines) => self.handle_bulk_add(&lines).await,
             BotCommand::Edit(args) => self.handle_edit(args).await,
+            BotCommand::ReplaceAll { from, to } => self.handle_replace_all(&from, &to).await,
             BotCommand::Duration(args) => self.handle_duration(args).await,
+            BotCommand::DurationAll(secs) => self.handle_duration_all(secs).await,
             BotCommand::Delete(id) => self.handle_delete(&id).await,
-            BotCommand::Info => self.handle_info(),
+            BotCommand::Info => self.handle_info().await,
+            BotCommand::Profile(name) => self.handle_profile(&name).await,
+            BotCommand::Only(tag) => self.handle_only(&tag).await,
+            BotCommand::All => self.handle_all().await,
+            BotCommand::Templates => self.handle_templates(),
+            BotCommand::TemplateAdd(name) => self.handle_template_add(&name).await,
+            BotCommand::Timeline => self.handle_timeline().await,
+            BotCommand::Lint => self.handle_lint().await,
+            BotCommand::Debug => self.handle_debug().await,
+            BotCommand::Why(target) => self.handle_why(&target).await,
+            BotCommand::Render(target) => self.handle_render(&target).await,
+            BotCommand::Snapshots => self.handle_snapshots(),
+            BotCommand::SnapshotSave(name) => self.handle_snapshot_save(&name).await,
+            BotCommand::SnapshotRestore(name) => self.handle_snapshot_restore(&name).await,
+            BotCommand::Presence(presence) => Self::handle_presence(presence),
+            BotCommand::Fast(window) => self.handle_fast(window),
+            BotCommand::RateLimit(secs) => self.handle_rate_limit(secs).await,
+            BotCommand::ImportCurrent(id) => self.handle_import_current(&id).await,
+            BotCommand::SetName { first, last } => Self::handle_set_name(first, last),
+            BotCommand::Stats => self.handle_stats().await,
+            BotCommand::StatsReset { confirmed } => self.handle_stats_reset(confirmed).await,
+            BotCommand::Config(key) => self.handle_config(key.as_deref()),
+            BotCommand::ConfigSet { key, value } => self.handle_config_set(&key, &value).await,
+            BotCommand::Extend(secs) => self.handle_extend(secs).await,
+            BotCommand::LastError => self.handle_last_error().await,
+            BotCommand::Save => self.handle_save().await,
+            BotCommand::Relocate {
+                what,
+                new_path,
+                remove_old,
+            } => self.handle_relocate(what, &new_path, remove_old).await,
+            BotCommand::Log(count) => self.handle_log(count.unwrap_or(DEFAULT_LOG_ENTRIES)),
+        };
+        let result = self.note_quiet_hours(result).await;
+
+        if result.success
+            && let Some((command, id)) = change
+        {
+            self.record_change(command, id);
+        }
+        result
+    }
+
+    /// Tells that an update asked for during quiet hours is held back until
+    /// they end, instead of the bio silently staying as it is.
+    async fn note_quiet_hours(&self, mut result: CommandResult) -> CommandResult {
+        if !(result.success && result.trigger_update) {
+            return result;
+        }
+        let now = self.scheduler_state.read().await.clock().now_utc();
+        let settings = self.settings();
+        if let Some(quiet) = settings.quiet_hours
+            && settings.is_quiet_at(now)
+        {
+            result.message = format!(
+                "{}\n🌙 Quiet hours: the bio is updated when they end at {}.",
+                result.message,
+                quiet.end.format("%H:%M")
+            );
+        }
+        result
+    }
+
+    /// Returns the ID of the description an editing command changes: the
+    /// one it names, resolved from a number if needed.
+    async fn changed_id(&self, command: &BotCommand) -> Option<String> {
+        let target = command.target()?;
+        if matches!(command, BotCommand::Add(_) | BotCommand::ImportCurrent(_)) {
+            return Some(target.to_owned());
+        }
+
+        let config = self.config.read().await;
+        let id = resolve_target(&config, target)
+            .map_or(target, |idx| config.active_descriptions()[idx].id.as_str());
+        Some(id.to_owned())
+    }
+
+    /// Appends a successful edit to the changelog next to the descriptions
+    /// file (not in read-only mode).
+    fn record_change(&self, command: String, id: Option<String>) {
+        if self.settings().read_only {
+            return;
+        }
+
+        let path = changelog_path(self.config_path());
+        let entry = ChangelogEntry {
+            at: chrono::Utc::now(),
+            id,
+            command,
+        };
+        if let Err(e) = append_entry(&path, &entry) {
+            warn!("Failed to write the changelog {}: {}", path.display(), e);
         }
     }
 
@@ -101,49 +476,197 @@ impl CommandHandler {
         // Advance to next and clear deadline to trigger immediate update
         state.advance(config.len());
         state.clear_deadline();
-        self.save_state(&state);
+        self.save_state(&mut state);
         CommandResult::success_with_update("✓ Skipping to next description...")
     }
 
+    async fn handle_extend(&self, secs: u64) -> CommandResult {
+        let mut state = self.scheduler_state.write().await;
+
+        if state.is_paused {
+            return CommandResult::error(
+                "Rotation is paused, so the description already stays. Nothing to extend.",
+            );
+        }
+        if !state.extend_deadline(secs) {
+            return CommandResult::error("No description is scheduled yet, nothing to extend.");
+        }
+
+        let remaining = state.time_remaining().unwrap_or_default().as_secs();
+        self.save_state(&mut state);
+        CommandResult::success(format!(
+            "✓ Keeping the current description {} longer ({} left)",
+            self.format_duration(secs),
+            self.format_duration(remaining)
+        ))
+        .with_data(serde_json::json!({ "extended_secs": secs, "remaining_secs": remaining }))
+    }
+
+    async fn handle_last_error(&self) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+        let Some(error) = state.last_error() else {
+            return CommandResult::success("✓ No recent errors.");
+        };
+
+        let ago = state.now_unix().saturating_sub(error.at_unix);
+        let failures = state.consecutive_failures();
+        let message = format!(
+            "Last bio update failed {} ago: {}\nFailed updates in a row: {failures}",
+            self.format_duration(ago),
+            error.message
+        );
+        let data = serde_json::json!({
+            "message": error.message,
+            "at_unix": error.at_unix,
+            "consecutive_failures": failures,
+        });
+        CommandResult::success(message).with_data(data)
+    }
+
+    async fn handle_save(&self) -> CommandResult {
+        if self.settings().read_only {
+            return CommandResult::error("Read-only mode, the state is not written to disk.");
+        }
+        let Some(path) = self.state_store.path() else {
+            return CommandResult::error(
+                "State persistence is off (PERSIST_STATE=false), nothing is written to disk.",
+            );
+        };
+        let path = std::path::absolute(&path).unwrap_or(path);
+
+        let now = {
+            let mut state = self.scheduler_state.write().await;
+            if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+                return CommandResult::error(format!(
+                    "Failed to save state to {}: {e}",
+                    path.display()
+                ));
+            }
+            state.now_unix()
+        };
+
+        // Report what a restart would find, not what is in memory
+        let Some(saved) = self.state_store.load() else {
+            return CommandResult::error(format!(
+                "State written to {} but it could not be read back.",
+                path.display()
+            ));
+        };
+
+        let deadline = match saved.expires_at_unix {
+            Some(at) if at > now => format!("in {}", self.format_duration(at - now)),
+            Some(_) => "passed".to_owned(),
+            None => "none".to_owned(),
+        };
+        let paused = if saved.is_paused {
+            format!("yes ({})", saved.pause_mode)
+        } else {
+            "no".to_owned()
+        };
+        let lines = [
+            format!("✓ State saved to {}", path.display()),
+            format!("Index: {}", saved.current_index + 1),
+            format!("Paused: {paused}"),
+            format!("Deadline: {deadline}"),
+            format!(
+                "Custom text: {}",
+                if saved.custom_description.is_some() {
+                    "pending"
+                } else {
+                    "none"
+                }
+            ),
+            format!("Queued texts: {}", saved.queue.len()),
+            format!("Statistics entries: {}", saved.stats.len()),
+        ];
+
+        let data = serde_json::json!({ "path": path, "state": saved });
+        CommandResult::success(lines.join("\n")).with_data(data)
+    }
+
     async fn handle_status(&self) -> CommandResult {
         let state = self.scheduler_state.read().await;
         let config = self.config.read().await;
+        let snapshot = StatusSnapshot::capture(&state, &config, &self.settings());
+        drop(state);
 
-        let current_desc = config.get(state.current_index).map_or_else(
+        let current_desc = config.get(snapshot.index).map_or_else(
             || "None".to_owned(),
             |d| format!("[{}] \"{}\"", d.id, truncate(&d.text, 30)),
         );
 
-        let status = if state.is_paused {
-            "⏸ Paused"
+        let status = if snapshot.paused_by_failures {
+            format!(
+                "⏸ Paused after {} failed updates (use 'resume')",
+                snapshot.failures
+            )
+        } else if snapshot.paused && snapshot.pause_mode == PauseMode::Auto {
+            "⏸ Automatic rotation paused ('set' still applies)".to_owned()
+        } else if snapshot.paused {
+            "⏸ Paused".to_owned()
+        } else if snapshot.quiet_hours
+            && let Some(quiet) = self.settings().quiet_hours
+        {
+            format!("🌙 Quiet hours until {}", quiet.end.format("%H:%M"))
         } else {
-            "▶ Running"
+            "▶ Running".to_owned()
         };
 
-        let time_info = match (state.time_remaining(), state.current_duration()) {
+        let time_info = match (snapshot.remaining_secs, snapshot.duration_secs) {
             (Some(remaining), Some(total)) => {
-                format!("{}s / {}s", remaining.as_secs(), total.as_secs())
+                format!(
+                    "{} / {}",
+                    self.format_duration(remaining),
+                    self.format_duration(total)
+                )
+            }
+            (Some(remaining), None) => {
+                format!("{} remaining", self.format_duration(remaining))
             }
-            (Some(remaining), None) => format!("{}s remaining", remaining.as_secs()),
             _ => "Pending update...".to_owned(),
         };
 
-        let account_type = if config.is_premium { "Premium" } else { "Free" };
+        let floor_info = if snapshot.duration_floored(&config) {
+            format!(
+                "\nMin update interval: {} (used instead of the configured duration)",
+                self.format_duration(self.settings().min_update_interval_secs)
+            )
+        } else {
+            String::new()
+        };
+
+        let account_type = if snapshot.premium { "Premium" } else { "Free" };
+
+        let profile_info = if config.profiles.is_empty() {
+            String::new()
+        } else {
+            format!("\nProfile: {}", snapshot.profile)
+        };
+
+        let rejected_info = if snapshot.rejected.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n⚠ Rejected by Telegram (too long): {}",
+                snapshot.rejected.join(", ")
+            )
+        };
 
         let message = format!(
             "Status: {status}\n\
              Current: {current_desc}\n\
              Index: {}/{}\n\
-             Time: {time_info}\n\
-             Account: {account_type}",
-            state.current_index + 1,
-            config.len(),
+             Time: {time_info}{floor_info}\n\
+             Account: {account_type}{profile_info}{rejected_info}",
+            snapshot.index + 1,
+            snapshot.count,
         );
 
-        CommandResult::success(message)
+        let data = serde_json::to_value(&snapshot).unwrap_or_default();
+        CommandResult::success(message).with_data(data)
     }
 
-    async fn handle_list(&self) -> CommandResult {
+    async fn handle_list(&self, page: Option<usize>) -> CommandResult {
         let config = self.config.read().await;
         let state = self.scheduler_state.read().await;
 
@@ -151,15 +674,33 @@ impl CommandHandler {
             return CommandResult::error("No descriptions configured.");
         }
 
-        let mut lines = vec!["Configured descriptions:".to_owned()];
+        let descriptions = config.active_descriptions();
+        let Some(page) = paginate(
+            descriptions,
+            page.unwrap_or(1),
+            self.settings().list_page_size,
+        ) else {
+            let pages = paginate(descriptions, 1, self.settings().list_page_size)
+                .map_or(1, |p| p.total_pages);
+            return CommandResult::error(format!("No such page. Pages: 1-{pages}."));
+        };
+
+        let mut lines = vec![if page.total_pages > 1 {
+            format!(
+                "Configured descriptions (page {}/{}):",
+                page.number, page.total_pages
+            )
+        } else {
+            "Configured descriptions:".to_owned()
+        }];
 
-        for (i, desc) in config.descriptions.iter().enumerate() {
+        for (i, desc) in (page.offset..).zip(page.items) {
             let marker = if i == state.current_index {
                 "→ "
             } else {
                 "  "
             };
-            let duration_str = format_duration(desc.duration_secs);
+            let duration_str = self.format_duration(desc.duration_secs);
             lines.push(format!(
                 "{marker}[{}] {} ({duration_str})",
                 desc.id,
@@ -167,39 +708,99 @@ impl CommandHandler {
             ));
         }
 
-        CommandResult::success(lines.join("\n"))
+        let data = serde_json::json!({
+            "current_index": state.current_index,
+            "descriptions": descriptions,
+            "page": page.number,
+            "pages": page.total_pages,
+        });
+
+        CommandResult::success(lines.join("\n")).with_data(data)
     }
 
-    async fn handle_view(&self, id: &str) -> CommandResult {
+    async fn handle_ids(&self) -> CommandResult {
         let config = self.config.read().await;
+        let state = self.scheduler_state.read().await;
 
-        let desc = config.descriptions.iter().find(|d| d.id == id).or_else(|| {
-            // Try as index
-            id.parse::<usize>()
-                .ok()
-                .filter(|&i| i > 0 && i <= config.len())
-                .and_then(|i| config.get(i - 1))
+        if config.is_empty() {
+            return CommandResult::error("No descriptions configured.");
+        }
+
+        // Long replies are split on spaces, so no pagination is needed
+        let ids: Vec<&str> = config
+            .active_descriptions()
+            .iter()
+            .map(|d| d.id.as_str())
+            .collect();
+        let message = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                if i == state.current_index {
+                    format!("*{id}")
+                } else {
+                    (*id).to_owned()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let data = serde_json::json!({
+            "current_index": state.current_index,
+            "ids": ids,
         });
+        CommandResult::success(message).with_data(data)
+    }
+
+    async fn handle_view(&self, id: &str) -> CommandResult {
+        let config = self.config.read().await;
+
+        let desc = resolve_target(&config, id).and_then(|i| config.get(i));
 
         match desc {
             Some(d) => {
                 let char_count = d.char_count();
-                let max_len = if config.is_premium {
-                    MAX_BIO_LENGTH_PREMIUM
+                let max_len = config.max_bio_length();
+
+                let schedule_info = d
+                    .schedule
+                    .map(|w| format!("\nSchedule: {w} ({})", self.settings().timezone))
+                    .unwrap_or_default();
+                let weekdays_info = if d.weekdays.is_empty() {
+                    String::new()
                 } else {
-                    MAX_BIO_LENGTH_FREE
+                    let days: Vec<String> = d.weekdays.iter().map(ToString::to_string).collect();
+                    format!("\nWeekdays: {}", days.join(", "))
+                };
+
+                let effective = self.settings().effective_duration_secs(d.duration_secs);
+                let effective_info = if effective == d.duration_secs {
+                    String::new()
+                } else {
+                    format!(
+                        " (effectively {}, minimum update interval)",
+                        self.format_duration(effective)
+                    )
                 };
 
                 let message = format!(
                     "Description [{}]:\n\
                      Text: \"{}\"\n\
-                     Duration: {}\n\
-                     Length: {}/{} chars",
+                     Duration: {}{}\n\
+                     Length: {}/{} chars{}{}{}",
                     d.id,
                     d.text,
-                    format_duration(d.duration_secs),
+                    self.format_duration(d.duration_secs),
+                    effective_info,
                     char_count,
-                    max_len
+                    max_len,
+                    schedule_info,
+                    weekdays_info,
+                    if d.sticky {
+                        "\nSticky: stays until skip or goto"
+                    } else {
+                        ""
+                    }
                 );
                 CommandResult::success(message)
             }
@@ -212,29 +813,15 @@ impl CommandHandler {
     async fn handle_goto(&self, target: &str) -> CommandResult {
         let config = self.config.read().await;
 
-        // Try to find by ID first
-        let index = config
-            .descriptions
-            .iter()
-            .position(|d| d.id == target)
-            .or_else(|| {
-                // Try to parse as index (1-based for user friendliness)
-                target
-                    .parse::<usize>()
-                    .ok()
-                    .filter(|&i| i > 0 && i <= config.len())
-                    .map(|i| i - 1)
-            });
-
-        match index {
+        match resolve_target(&config, target) {
             Some(idx) => {
                 drop(config); // Release read lock before acquiring write lock
                 let mut state = self.scheduler_state.write().await;
                 state.set_index(idx); // Sets index and clears deadline
-                self.save_state(&state);
+                self.save_state(&mut state);
 
                 let config = self.config.read().await;
-                let desc = &config.descriptions[idx];
+                let desc = &config.active_descriptions()[idx];
                 CommandResult::success_with_update(format!(
                     "✓ Jumping to [{}]: \"{}\"",
                     desc.id,
@@ -247,16 +834,19 @@ impl CommandHandler {
         }
     }
 
-    async fn handle_pause(&self) -> CommandResult {
+    async fn handle_pause(&self, mode: PauseMode) -> CommandResult {
         let mut state = self.scheduler_state.write().await;
 
-        if state.is_paused {
+        if state.is_paused && state.pause_mode == mode {
             return CommandResult::error("Already paused.");
         }
 
-        state.is_paused = true;
-        self.save_state(&state);
-        CommandResult::success("⏸ Description rotation paused.")
+        state.pause(mode);
+        self.save_state(&mut state);
+        CommandResult::success(match mode {
+            PauseMode::All => "⏸ Description rotation paused.",
+            PauseMode::Auto => "⏸ Automatic rotation paused; 'set' still changes the bio.",
+        })
     }
 
     async fn handle_resume(&self) -> CommandResult {
@@ -266,41 +856,73 @@ impl CommandHandler {
             return CommandResult::error("Already running.");
         }
 
-        state.is_paused = false;
-        self.save_state(&state);
+        state.resume();
+        self.save_state(&mut state);
         CommandResult::success("▶ Description rotation resumed.")
     }
 
     async fn handle_reload(&self) -> CommandResult {
-        match DescriptionConfig::load_from_file(&self.config_path) {
-            Ok(new_config) => {
-                if let Err(e) = new_config.validate() {
-                    return CommandResult::error(format!("Validation failed: {e}"));
-                }
-
-                let mut config = self.config.write().await;
-                let old_len = config.len();
-                *config = new_config;
-                let new_len = config.len();
-
-                // Reset index if out of bounds
-                let mut state = self.scheduler_state.write().await;
-                if state.current_index >= new_len {
-                    state.set_index(0); // Reset and clear deadline
-                }
-                self.save_state(&state);
+        // Don't lose edits that haven't been written yet
+        if let Err(e) = self.flush_config().await {
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save pending changes: {e}"));
+        }
 
-                CommandResult::success(format!(
+        match DescriptionConfig::load_from_file(self.config_path()) {
+            Ok(new_config) => match self.replace_config(new_config, false).await {
+                Ok((old_len, new_len)) => CommandResult::success(format!(
                     "✓ Reloaded configuration. {old_len} → {new_len} descriptions."
-                ))
-            }
+                )),
+                Err(e) => CommandResult::error(e),
+            },
             Err(e) => CommandResult::error(format!("Failed to reload: {e}")),
         }
     }
 
+    /// Validates `new_config` and makes it the active configuration,
+    /// resetting the index if it no longer points at a description.
+    ///
+    /// With `save`, the new configuration is also written to the
+    /// descriptions file. Returns the description counts before and after.
+    async fn replace_config(
+        &self,
+        mut new_config: DescriptionConfig,
+        save: bool,
+    ) -> Result<(usize, usize), String> {
+        // Limits detected at startup are not in the file
+        new_config.server_max_lengths = self.config.read().await.server_max_lengths;
+        if let Err(e) = new_config
+            .validate_with(self.settings().on_overlong)
+            .and_then(|()| new_config.validate_count(self.settings().max_descriptions))
+        {
+            return Err(format!("Validation failed: {e}"));
+        }
+
+        let mut state = self.scheduler_state.write().await;
+        let mut config = self.config.write().await;
+        let old_len = config.len();
+        *config = new_config;
+        let new_len = config.len();
+        if save && let Err(e) = self.save_config(&config) {
+            warn!("Failed to save config: {}", e);
+        }
+
+        // Reset index if out of bounds
+        if state.current_index >= new_len {
+            state.set_index(0); // Reset and clear deadline
+        }
+        state.clear_rejected(); // Give fixed descriptions another chance
+        self.save_state(&mut state);
+
+        Ok((old_len, new_len))
+    }
+
     fn handle_help(&self) -> CommandResult {
         let mut lines = vec![
-            format!("Description Bot Commands (prefix: {})", self.prefix),
+            format!(
+                "Description Bot Commands (prefix: {})",
+                self.prefixes.join(", ")
+            ),
             String::new(),
         ];
 
@@ -316,45 +938,122 @@ impl CommandHandler {
         CommandResult::success(lines.join("\n"))
     }
 
-    async fn handle_set(&self, text: &str) -> CommandResult {
+    async fn handle_set(&self, text: String, duration_secs: Option<u64>) -> CommandResult {
         // Validate text
-        {
+        let note = {
             let config = self.config.read().await;
-            if let Err(e) = validate_description_text(text, &config) {
+            if let Err(e) = self.validate_text(&text, &config) {
                 return CommandResult::error(e);
             }
-        }
+            self.near_limit_note(&text, &config)
+        };
+
+        let duration_secs = duration_secs.unwrap_or(self.settings().custom_duration_secs);
+        let message = format!(
+            "✓ Setting custom description for {}: \"{}\"{note}",
+            self.format_duration(duration_secs),
+            truncate(&text, 30)
+        );
 
         let mut state = self.scheduler_state.write().await;
-        state.custom_description = Some(text.to_owned());
+        state.set_custom(text, duration_secs);
         state.clear_deadline(); // Trigger immediate update
-        self.save_state(&state);
+        self.save_state(&mut state);
 
-        CommandResult::success_with_update(format!(
-            "✓ Setting custom description: \"{}\"",
-            truncate(text, 30)
-        ))
+        if state.holds_updates() {
+            return CommandResult::success(format!(
+                "{message}\n⏸ Rotation is paused, it is applied on 'resume'."
+            ));
+        }
+        CommandResult::success_with_update(message)
+    }
+
+    async fn handle_queue(&self, item: &str) -> CommandResult {
+        let mut state = self.scheduler_state.write().await;
+        let (text, note) = {
+            let config = self.config.read().await;
+            let text = config
+                .active_descriptions()
+                .iter()
+                .find(|d| d.id == item)
+                .map_or_else(|| item.to_owned(), |d| d.text.clone());
+            if let Err(e) = self.validate_text(&text, &config) {
+                return CommandResult::error(e);
+            }
+            let note = self.near_limit_note(&text, &config);
+            (text, note)
+        };
+
+        let duration_secs = self.settings().custom_duration_secs;
+        let message = format!(
+            "✓ Queued for {} at position {}: \"{}\"{note}",
+            self.format_duration(duration_secs),
+            state.queue.len() + 1,
+            truncate(&text, 30)
+        );
+        state.enqueue(text, duration_secs);
+        self.save_state(&mut state);
+
+        CommandResult::success(message)
+    }
+
+    /// Checks that `adding` more descriptions stay within `MAX_DESCRIPTIONS`.
+    fn check_capacity(&self, config: &DescriptionConfig, adding: usize) -> Result<(), String> {
+        match self.settings().max_descriptions {
+            Some(max) if config.len() + adding > max => Err(format!(
+                "Too many descriptions: the limit is {max}, {} configured. Delete some first.",
+                config.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Warns when a duration is shorter than the rate limiter allows.
+    fn duration_floor_note(&self, duration_secs: u64) -> String {
+        let effective = self.settings().effective_duration_secs(duration_secs);
+        if effective == duration_secs {
+            return String::new();
+        }
+        format!(
+            "\n⚠ Below the minimum update interval, effectively {}",
+            self.format_duration(effective)
+        )
+    }
+
+    /// Warns when a text uses more of the bio limit than `WARN_THRESHOLD_PCT`.
+    fn near_limit_note(&self, text: &str, config: &DescriptionConfig) -> String {
+        let threshold = usize::from(self.settings().warn_threshold_pct);
+        let max_len = config.max_bio_length();
+        let len = normalize_bio(text).chars().count();
+        if threshold == 0 || len * 100 <= max_len * threshold || len > max_len {
+            return String::new();
+        }
+        format!("\n⚠ Close to the limit: {len} of {max_len} characters")
     }
 
     async fn handle_add(&self, args: AddArgs) -> CommandResult {
         let mut config = self.config.write().await;
 
         // Check for duplicate ID
-        if config.descriptions.iter().any(|d| d.id == args.id) {
+        if config.active_descriptions().iter().any(|d| d.id == args.id) {
             return CommandResult::error(format!(
                 "Description with ID '{}' already exists. Use 'edit' to modify it.",
                 args.id
             ));
         }
 
+        if let Err(e) = self.check_capacity(&config, 1) {
+            return CommandResult::error(e);
+        }
+
         // Validate text
-        if let Err(e) = validate_description_text(&args.text, &config) {
+        if let Err(e) = self.validate_text(&args.text, &config) {
             return CommandResult::error(e);
         }
 
         // Validate duration
-        if args.duration_secs == 0 {
-            return CommandResult::error("Duration must be greater than 0 seconds.");
+        if let Some(problem) = duration_problem(args.duration_secs) {
+            return CommandResult::error(format!("Duration {problem}."));
         }
 
         // Validate ID (no spaces, not empty)
@@ -364,70 +1063,204 @@ impl CommandHandler {
 
         // Create and add the new description
         let desc = Description::new(args.id.clone(), args.text.clone(), args.duration_secs);
-        config.descriptions.push(desc);
+        config.active_descriptions_mut().push(desc);
 
         // Save to file
-        if let Err(e) = config.save_to_file(&self.config_path) {
+        if let Err(e) = self.save_config(&config) {
             warn!("Failed to save config: {}", e);
             return CommandResult::error(format!("Added but failed to save: {e}"));
         }
 
         CommandResult::success(format!(
-            "✓ Added description [{}]: \"{}\" ({})",
+            "✓ Added description [{}]: \"{}\" ({}){}{}",
             args.id,
             truncate(&args.text, 25),
-            format_duration(args.duration_secs)
+            self.format_duration(args.duration_secs),
+            self.duration_floor_note(args.duration_secs),
+            self.near_limit_note(&args.text, &config)
         ))
     }
 
-    async fn handle_edit(&self, args: EditArgs) -> CommandResult {
+    async fn handle_bulk_add(&self, lines: &str) -> CommandResult {
         let mut config = self.config.write().await;
+        let mut batch: Vec<Description> = Vec::new();
 
-        // Find by index first (immutable operation)
-        let index = config.descriptions.iter().position(|d| d.id == args.id);
+        // Validate every line before touching the config
+        for (number, line) in lines.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
 
-        let Some(idx) = index else {
-            return CommandResult::error(format!(
-                "Description not found: '{}'. Use 'list' to see available descriptions.",
-                args.id
-            ));
-        };
+            let line_error = |e: &str| {
+                CommandResult::error(format!(
+                    "Line {}: {e}. Nothing was added.\n  {}",
+                    number + 1,
+                    truncate(line, 40)
+                ))
+            };
 
-        // Validate new text
-        if let Err(e) = validate_description_text(&args.text, &config) {
-            return CommandResult::error(e);
-        }
+            let args = match AddArgs::parse_bulk_line(line) {
+                Ok(args) => args,
+                Err(e) => return line_error(&e),
+            };
 
-        // Now mutate
-        let old_text = config.descriptions[idx].text.clone();
-        config.descriptions[idx].text.clone_from(&args.text);
+            if args.id.contains(char::is_whitespace) {
+                return line_error("ID cannot contain spaces");
+            }
+            if config.active_descriptions().iter().any(|d| d.id == args.id) {
+                return line_error(&format!("ID '{}' already exists", args.id));
+            }
+            if batch.iter().any(|d| d.id == args.id) {
+                return line_error(&format!("ID '{}' appears twice in the batch", args.id));
+            }
+            if let Some(problem) = duration_problem(args.duration_secs) {
+                return line_error(&format!("duration {problem}"));
+            }
+            if let Err(e) = self.validate_text(&args.text, &config) {
+                return line_error(&e);
+            }
 
-        // Save to file
-        if let Err(e) = config.save_to_file(&self.config_path) {
-            config.descriptions[idx].text = old_text; // Rollback
-            warn!("Failed to save config: {}", e);
-            return CommandResult::error(format!("Failed to save: {e}"));
+            batch.push(Description::new(args.id, args.text, args.duration_secs));
         }
 
+        if batch.is_empty() {
+            return CommandResult::error("No descriptions given. Use one 'id|sec|text' per line.");
+        }
+        if let Err(e) = self.check_capacity(&config, batch.len()) {
+            return CommandResult::error(format!("{e} Nothing was added."));
+        }
+
+        let count = batch.len();
+        let below_floor = batch
+            .iter()
+            .filter(|d| self.settings().effective_duration_secs(d.duration_secs) > d.duration_secs)
+            .count();
+        let previous_len = config.len();
+        config.active_descriptions_mut().extend(batch);
+
+        // Save once for the whole batch
+        if let Err(e) = self.save_config(&config) {
+            config.active_descriptions_mut().truncate(previous_len); // Rollback
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+
+        let floor_info = if below_floor == 0 {
+            String::new()
+        } else {
+            format!(
+                "\n⚠ {below_floor} below the minimum update interval ({})",
+                self.format_duration(self.settings().min_update_interval_secs)
+            )
+        };
+        CommandResult::success(format!("✓ Added {count} descriptions{floor_info}"))
+    }
+
+    async fn handle_edit(&self, args: EditArgs) -> CommandResult {
+        let mut config = self.config.write().await;
+
+        // Find by ID or position first (immutable operation)
+        let Some(idx) = resolve_target(&config, &args.id) else {
+            return CommandResult::error(format!(
+                "Description not found: '{}'. Use 'list' to see available descriptions.",
+                args.id
+            ));
+        };
+        let id = config.active_descriptions()[idx].id.clone();
+
+        // Validate new text
+        if let Err(e) = self.validate_text(&args.text, &config) {
+            return CommandResult::error(e);
+        }
+
+        // Now mutate
+        let old_text = config.active_descriptions()[idx].text.clone();
+        config.active_descriptions_mut()[idx]
+            .text
+            .clone_from(&args.text);
+
+        // Save to file
+        if let Err(e) = self.save_config(&config) {
+            config.active_descriptions_mut()[idx].text = old_text; // Rollback
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+        let note = self.near_limit_note(&args.text, &config);
+        drop(config);
+
+        // The new text may be accepted by Telegram
+        self.scheduler_state.write().await.unmark_rejected(&id);
+
         CommandResult::success(format!(
-            "✓ Updated [{}]: \"{}\"",
-            args.id,
+            "✓ Updated [{id}]: \"{}\"{note}",
             truncate(&args.text, 30)
         ))
     }
 
+    async fn handle_replace_all(&self, from: &str, to: &str) -> CommandResult {
+        let mut config = self.config.write().await;
+
+        // Build and validate every new text before touching the config
+        let mut changes = Vec::new();
+        for (idx, desc) in config.active_descriptions().iter().enumerate() {
+            if !desc.text.contains(from) {
+                continue;
+            }
+            let text = desc.text.replace(from, to);
+            if let Err(e) = self.validate_text(&text, &config) {
+                return CommandResult::error(format!(
+                    "Nothing replaced: [{}] would become invalid: {e}",
+                    desc.id
+                ));
+            }
+            changes.push((idx, text));
+        }
+
+        if changes.is_empty() {
+            return CommandResult::success(format!("No description contains \"{from}\"."));
+        }
+
+        // Swap in the new texts, keeping the old ones for a rollback
+        let mut changed_ids = Vec::with_capacity(changes.len());
+        for (idx, text) in &mut changes {
+            let desc = &mut config.active_descriptions_mut()[*idx];
+            std::mem::swap(&mut desc.text, text);
+            changed_ids.push(desc.id.clone());
+        }
+
+        if let Err(e) = self.save_config(&config) {
+            for (idx, old_text) in changes {
+                config.active_descriptions_mut()[idx].text = old_text; // Rollback
+            }
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+        drop(config);
+
+        // The new texts may be accepted by Telegram
+        let mut state = self.scheduler_state.write().await;
+        for id in &changed_ids {
+            state.unmark_rejected(id);
+        }
+
+        CommandResult::success(format!(
+            "✓ Replaced in {} description(s): {}",
+            changed_ids.len(),
+            changed_ids.join(", ")
+        ))
+    }
+
     async fn handle_duration(&self, args: DurationArgs) -> CommandResult {
         let mut config = self.config.write().await;
 
         // Validate duration
-        if args.duration_secs == 0 {
-            return CommandResult::error("Duration must be greater than 0 seconds.");
+        if let Some(problem) = duration_problem(args.duration_secs) {
+            return CommandResult::error(format!("Duration {problem}."));
         }
 
-        // Find by index first
-        let index = config.descriptions.iter().position(|d| d.id == args.id);
-
-        let Some(idx) = index else {
+        // Find by ID or position first
+        let Some(idx) = resolve_target(&config, &args.id) else {
             return CommandResult::error(format!(
                 "Description not found: '{}'. Use 'list' to see available descriptions.",
                 args.id
@@ -435,37 +1268,96 @@ impl CommandHandler {
         };
 
         // Now mutate
-        let old_duration = config.descriptions[idx].duration_secs;
-        config.descriptions[idx].duration_secs = args.duration_secs;
+        let old_duration = config.active_descriptions()[idx].duration_secs;
+        config.active_descriptions_mut()[idx].duration_secs = args.duration_secs;
 
         // Save to file
-        if let Err(e) = config.save_to_file(&self.config_path) {
-            config.descriptions[idx].duration_secs = old_duration; // Rollback
+        if let Err(e) = self.save_config(&config) {
+            config.active_descriptions_mut()[idx].duration_secs = old_duration; // Rollback
             warn!("Failed to save config: {}", e);
             return CommandResult::error(format!("Failed to save: {e}"));
         }
 
         CommandResult::success(format!(
-            "✓ Updated [{}] duration: {} → {}",
-            args.id,
-            format_duration(old_duration),
-            format_duration(args.duration_secs)
+            "✓ Updated [{}] duration: {} → {}{}",
+            config.active_descriptions()[idx].id,
+            self.format_duration(old_duration),
+            self.format_duration(args.duration_secs),
+            self.duration_floor_note(args.duration_secs)
         ))
     }
 
-    async fn handle_delete(&self, id: &str) -> CommandResult {
+    async fn handle_duration_all(&self, duration_secs: u64) -> CommandResult {
         let mut config = self.config.write().await;
 
-        // Find the description index
-        let index = config.descriptions.iter().position(|d| d.id == id);
+        if let Some(problem) = duration_problem(duration_secs) {
+            return CommandResult::error(format!("Duration {problem}."));
+        }
+        if config.is_empty() {
+            return CommandResult::error("No descriptions configured.");
+        }
+
+        let old_durations: Vec<u64> = config
+            .active_descriptions()
+            .iter()
+            .map(|d| d.duration_secs)
+            .collect();
+        let changed = old_durations
+            .iter()
+            .filter(|&&d| d != duration_secs)
+            .count();
+        if changed == 0 {
+            return CommandResult::success(format!(
+                "All descriptions already last {}.",
+                self.format_duration(duration_secs)
+            ));
+        }
+
+        for desc in config.active_descriptions_mut() {
+            desc.duration_secs = duration_secs;
+        }
+
+        // Save once for all entries
+        if let Err(e) = self.save_config(&config) {
+            for (desc, old) in config
+                .active_descriptions_mut()
+                .iter_mut()
+                .zip(old_durations)
+            {
+                desc.duration_secs = old; // Rollback
+            }
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+
+        CommandResult::success(format!(
+            "✓ Set the duration of {changed} description(s) to {}{}",
+            self.format_duration(duration_secs),
+            self.duration_floor_note(duration_secs)
+        ))
+    }
+
+    async fn handle_delete(&self, id: &str) -> CommandResult {
+        let mut config = self.config.write().await;
 
-        match index {
+        match resolve_target(&config, id) {
             Some(idx) => {
-                let removed = config.descriptions.remove(idx);
+                let removed = config.active_descriptions_mut().remove(idx);
+
+                // A fallback no profile has any more would fail validation
+                let clears_fallback = config.fallback_id.as_deref() == Some(removed.id.as_str())
+                    && !config.has_id_in_any_profile(&removed.id);
+                if clears_fallback {
+                    config.fallback_id = None;
+                }
 
                 // Save to file
-                if let Err(e) = config.save_to_file(&self.config_path) {
-                    config.descriptions.insert(idx, removed); // Rollback
+                if let Err(e) = self.save_config(&config) {
+                    // Rollback
+                    if clears_fallback {
+                        config.fallback_id = Some(removed.id.clone());
+                    }
+                    config.active_descriptions_mut().insert(idx, removed);
                     warn!("Failed to save config: {}", e);
                     return CommandResult::error(format!("Failed to save: {e}"));
                 }
@@ -475,19 +1367,33 @@ impl CommandHandler {
                 let mut state = self.scheduler_state.write().await;
                 let config = self.config.read().await;
 
+                let mut deleted = format!(
+                    "✓ Deleted [{}]: \"{}\"",
+                    removed.id,
+                    truncate(&removed.text, 30)
+                );
+                if clears_fallback {
+                    deleted.push_str("\nIt was the fallback description; no fallback is set now.");
+                }
+
                 if config.is_empty() {
+                    // Nothing left to rotate; wait for 'add' and 'resume'
                     state.current_index = 0;
-                } else if state.current_index >= config.len() {
+                    state.pause(PauseMode::All);
+                    self.save_state(&mut state);
+                    return CommandResult::success(format!(
+                        "{deleted}\n⚠ That was the last description, rotation is paused. \
+                         Use 'add' and then 'resume'."
+                    ));
+                }
+
+                if state.current_index >= config.len() {
                     state.current_index = config.len() - 1;
                 } else if state.current_index > idx {
                     state.current_index -= 1;
                 }
 
-                CommandResult::success(format!(
-                    "✓ Deleted [{}]: \"{}\"",
-                    id,
-                    truncate(&removed.text, 30)
-                ))
+                CommandResult::success(deleted)
             }
             None => CommandResult::error(format!(
                 "Description not found: '{id}'. Use 'list' to see available descriptions."
@@ -495,165 +1401,2564 @@ impl CommandHandler {
         }
     }
 
-    #[allow(clippy::unused_self)]
-    fn handle_info(&self) -> CommandResult {
-        let version = env!("CARGO_PKG_VERSION");
-        let message = format!(
-            "Description User Bot v{version}\n\
-             A Telegram userbot for dynamic profile descriptions.\n\
-             Repository: https://github.com/user/description_user_bot"
-        );
-        CommandResult::success(message)
-    }
-}
+    async fn handle_profile(&self, name: &str) -> CommandResult {
+        let mut config = self.config.write().await;
 
-/// Validates description text for use as a Telegram bio.
-///
-/// Checks:
-/// - Not empty
-/// - Not too long (based on premium status)
-/// - Text only (no images, stickers, etc. - only printable characters)
-/// - No control characters except newlines
-fn validate_description_text(text: &str, config: &DescriptionConfig) -> Result<(), String> {
-    // Check empty
-    if text.is_empty() {
-        return Err("Description text cannot be empty.".to_owned());
-    }
+        if config.active_profile_name() == name {
+            return CommandResult::error(format!("Profile '{name}' is already active."));
+        }
 
-    // Check length
-    let max_len = if config.is_premium {
-        MAX_BIO_LENGTH_PREMIUM
-    } else {
-        MAX_BIO_LENGTH_FREE
-    };
+        let old_profile = config.active_profile.clone();
+        if config.switch_profile(name).is_err() {
+            return CommandResult::error(format!(
+                "Unknown profile: '{name}'. Available: {}",
+                config.profile_names().join(", ")
+            ));
+        }
 
-    let char_count = text.chars().count();
-    if char_count > max_len {
-        return Err(format!(
-            "Text too long: {char_count} chars (max: {max_len})"
-        ));
+        if config.is_empty() {
+            config.active_profile = old_profile; // Rollback
+            return CommandResult::error(format!("Profile '{name}' has no descriptions."));
+        }
+
+        // Save to file
+        if let Err(e) = self.save_config(&config) {
+            config.active_profile = old_profile; // Rollback
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+
+        let count = config.len();
+        drop(config);
+
+        // Start the new set from the beginning, untagged
+        let mut state = self.scheduler_state.write().await;
+        state.set_index(0);
+        state.clear_custom();
+        state.clear_rejected();
+        state.active_tag = None;
+        self.save_state(&mut state);
+
+        CommandResult::success_with_update(format!(
+            "✓ Switched to profile '{name}' ({count} descriptions)"
+        ))
     }
 
-    // Check for invalid characters (control chars except common whitespace)
-    for ch in text.chars() {
-        if ch.is_control() && ch != '\n' && ch != '\t' {
-            return Err(format!(
-                "Invalid character detected (code: U+{:04X}). Only text is allowed.",
-                ch as u32
+    async fn handle_only(&self, tag: &str) -> CommandResult {
+        let mut state = self.scheduler_state.write().await;
+        let config = self.config.read().await;
+
+        let count = config.count_tagged(tag);
+        if count == 0 {
+            return CommandResult::error(format!(
+                "No descriptions are tagged '{tag}'. The rotation is unchanged."
             ));
         }
+        let total = config.len();
+        drop(config);
+
+        state.active_tag = Some(tag.to_owned());
+        self.save_state(&mut state);
+
+        CommandResult::success_with_update(format!(
+            "✓ Rotating only through '{tag}': {count} of {total} descriptions"
+        ))
     }
 
-    // Check for object replacement character (often used for embedded objects)
-    if text.contains('\u{FFFC}') {
-        return Err(
-            "Embedded objects (images, files) are not allowed. Only text is supported.".to_owned(),
-        );
+    async fn handle_all(&self) -> CommandResult {
+        let mut state = self.scheduler_state.write().await;
+
+        let Some(tag) = state.active_tag.take() else {
+            return CommandResult::error("The rotation is not restricted to a tag.");
+        };
+        self.save_state(&mut state);
+        drop(state);
+
+        let count = self.config.read().await.len();
+        CommandResult::success(format!(
+            "✓ No longer restricted to '{tag}': rotating through all {count} descriptions"
+        ))
     }
 
-    // Check for zero-width characters that might hide content
-    let suspicious_chars = [
-        '\u{200B}', // Zero-width space
-        '\u{200C}', // Zero-width non-joiner
-        '\u{200D}', // Zero-width joiner
-        '\u{2060}', // Word joiner
-        '\u{FEFF}', // BOM / Zero-width no-break space
-    ];
+    fn handle_templates(&self) -> CommandResult {
+        let mut lines = vec!["Built-in templates:".to_owned()];
 
-    for &ch in &suspicious_chars {
-        if text.contains(ch) {
-            return Err(format!(
-                "Invisible/zero-width characters detected (U+{:04X}). Please use only visible text.",
-                ch as u32
+        for template in TEMPLATES {
+            lines.push(format!(
+                "  {} - {} ({})",
+                template.name,
+                template.text,
+                self.format_duration(template.duration_secs)
             ));
         }
-    }
 
-    Ok(())
-}
+        lines.push(String::new());
+        lines.push("Use 'template add <name>' to add one.".to_owned());
 
-/// Truncates a string to a maximum length, adding "..." if truncated.
-fn truncate(s: &str, max_len: usize) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    if chars.len() <= max_len {
-        s.to_owned()
-    } else {
-        format!("{}...", chars[..max_len].iter().collect::<String>())
+        CommandResult::success(lines.join("\n"))
     }
-}
 
-/// Formats a duration in seconds to a human-readable string.
-fn format_duration(secs: u64) -> String {
-    if secs < 60 {
-        format!("{secs}s")
-    } else if secs < 3600 {
-        format!("{}m", secs / 60)
-    } else {
-        let hours = secs / 3600;
-        let mins = (secs % 3600) / 60;
-        if mins == 0 {
-            format!("{hours}h")
-        } else {
-            format!("{hours}h {mins}m")
+    async fn handle_template_add(&self, name: &str) -> CommandResult {
+        let Some(template) = find_template(name) else {
+            return CommandResult::error(format!(
+                "Unknown template: '{name}'. Use 'templates' to see available templates."
+            ));
+        };
+
+        let mut config = self.config.write().await;
+
+        if let Err(e) = self.check_capacity(&config, 1) {
+            return CommandResult::error(e);
+        }
+        if let Err(e) = self.validate_text(template.text, &config) {
+            return CommandResult::error(e);
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let id = config.unique_id(template.name);
+        let desc = Description::new(id.clone(), template.text.to_owned(), template.duration_secs);
+        config.active_descriptions_mut().push(desc);
 
-    #[test]
-    fn test_truncate() {
-        assert_eq!(truncate("Hello", 10), "Hello");
-        assert_eq!(truncate("Hello, World!", 5), "Hello...");
-        assert_eq!(truncate("Hi", 2), "Hi");
-    }
+        // Save to file
+        if let Err(e) = self.save_config(&config) {
+            config.active_descriptions_mut().pop(); // Rollback
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
 
-    #[test]
-    fn test_format_duration() {
-        assert_eq!(format_duration(30), "30s");
-        assert_eq!(format_duration(60), "1m");
-        assert_eq!(format_duration(90), "1m");
-        assert_eq!(format_duration(3600), "1h");
-        assert_eq!(format_duration(3660), "1h 1m");
-        assert_eq!(format_duration(7200), "2h");
+        CommandResult::success(format!(
+            "✓ Added template as [{id}]: \"{}\" ({})",
+            template.text,
+            self.format_duration(template.duration_secs)
+        ))
     }
 
-    #[test]
-    fn test_validate_description_text_valid() {
-        let config = DescriptionConfig::default();
-        assert!(validate_description_text("Hello World!", &config).is_ok());
-        assert!(validate_description_text("Привет мир! 👋", &config).is_ok());
+    async fn handle_import_current(&self, id: &str) -> CommandResult {
+        let Some(source) = &self.bio_source else {
+            return CommandResult::error("Importing the bio needs a Telegram connection.");
+        };
+
+        let bio = match source.current_bio().await {
+            Ok(bio) => bio,
+            Err(e) => return CommandResult::error(format!("Failed to fetch the current bio: {e}")),
+        };
+        let bio = bio.trim();
+        if bio.is_empty() {
+            return CommandResult::error("The profile has no bio to import. Use 'add' instead.");
+        }
+
+        let mut config = self.config.write().await;
+
+        if config.active_descriptions().iter().any(|d| d.id == id) {
+            return CommandResult::error(format!(
+                "Description with ID '{id}' already exists. Use 'edit' to modify it."
+            ));
+        }
+        if let Err(e) = self.check_capacity(&config, 1) {
+            return CommandResult::error(e);
+        }
+        if let Err(e) = self.validate_text(bio, &config) {
+            return CommandResult::error(e);
+        }
+
+        let desc = Description::new(id.to_owned(), bio.to_owned(), IMPORT_DURATION_SECS);
+        config.active_descriptions_mut().push(desc);
+
+        if let Err(e) = self.save_config(&config) {
+            config.active_descriptions_mut().pop(); // Rollback
+            warn!("Failed to save config: {}", e);
+            return CommandResult::error(format!("Failed to save: {e}"));
+        }
+
+        CommandResult::success(format!(
+            "✓ Imported the current bio as [{id}]: \"{}\" ({})",
+            truncate(bio, 30),
+            self.format_duration(IMPORT_DURATION_SECS)
+        ))
     }
 
-    #[test]
-    fn test_validate_description_text_empty() {
-        let config = DescriptionConfig::default();
-        assert!(validate_description_text("", &config).is_err());
+    async fn handle_why(&self, target: &str) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+        let config = self.config.read().await;
+
+        let Some(desc) = resolve_target(&config, target).and_then(|i| config.get(i)) else {
+            return CommandResult::error(format!(
+                "Description not found: '{target}'. Use 'list' to see available descriptions."
+            ));
+        };
+
+        if state.is_rejected(&desc.id) {
+            return CommandResult::success(format!(
+                "[{}] is skipped: rejected by Telegram (too long). Edit it to retry.",
+                desc.id
+            ));
+        }
+
+        let tz = self.settings().timezone;
+        let active_tag = state.active_tag.as_deref();
+        let message = match config.eligibility(&desc.id, active_tag, state.clock().now_utc(), &tz) {
+            Eligibility::Eligible => format!("[{}] is eligible right now.", desc.id),
+            Eligibility::OutsideWindow(window) => {
+                format!(
+                    "[{}] is skipped: only shown during {window} ({tz}).",
+                    desc.id
+                )
+            }
+            reason => format!("[{}] is skipped: {reason}.", desc.id),
+        };
+        CommandResult::success(message)
     }
 
-    #[test]
-    fn test_validate_description_text_too_long() {
-        let config = DescriptionConfig::default();
-        let long_text = "a".repeat(71);
-        assert!(validate_description_text(&long_text, &config).is_err());
+    async fn handle_render(&self, target: &str) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+        let config = self.config.read().await;
+
+        let Some(desc) = resolve_target(&config, target).and_then(|i| config.get(i)) else {
+            return CommandResult::error(format!(
+                "Description not found: '{target}'. Use 'list' to see available descriptions."
+            ));
+        };
+
+        let on_overlong = self.settings().on_overlong;
+        let Some(rendered) = render(&config, &desc.id, on_overlong, state.clock().now_unix())
+        else {
+            return CommandResult::error(format!("Cannot render [{}]", desc.id));
+        };
+
+        let mut lines = vec![
+            format!("Bio for [{}]:", rendered.id),
+            rendered.text.clone(),
+            format!(
+                "Length: {}/{} UTF-16 units",
+                rendered.utf16_len, rendered.max_len
+            ),
+        ];
+        if let Some(len) = rendered.truncated_from {
+            lines.push(format!("✂ Truncated from {len} units to fit the limit"));
+        }
+        if rendered.is_overlong() {
+            lines.push("⚠ Too long: Telegram will reject it (ON_OVERLONG=reject)".to_owned());
+        }
+        if rendered.decorated {
+            lines.push("The emoji is picked from the pool at random on each update".to_owned());
+        }
+
+        let data = serde_json::to_value(&rendered).unwrap_or_default();
+        CommandResult::success(lines.join("\n")).with_data(data)
     }
 
-    #[test]
-    fn test_validate_description_text_premium_allows_longer() {
-        let config = DescriptionConfig {
-            is_premium: true,
-            ..Default::default()
+    fn handle_presence(presence: Presence) -> CommandResult {
+        let message = if presence.is_offline() {
+            "✓ The account will appear offline"
+        } else {
+            "✓ The account may appear online again"
         };
-        let text = "a".repeat(100);
-        assert!(validate_description_text(&text, &config).is_ok());
+        CommandResult::success(message).with_presence(presence)
     }
 
-    #[test]
-    fn test_validate_description_text_zero_width() {
-        let config = DescriptionConfig::default();
-        let text_with_zwsp = "Hello\u{200B}World";
+    fn handle_fast(&self, window: Option<u64>) -> CommandResult {
+        let window_secs = window.unwrap_or(FAST_WINDOW_SECS);
+        if window_secs > FAST_MAX_WINDOW_SECS {
+            return CommandResult::error(format!(
+                "Fast mode lasts at most {}.",
+                self.format_duration(FAST_MAX_WINDOW_SECS)
+            ));
+        }
+
+        CommandResult::success(format!(
+            "⚡ For the next {}, profile updates are allowed every {} (normally {}).\n\
+             ⚠ Frequent changes risk flood waits from Telegram.",
+            self.format_duration(window_secs),
+            self.format_duration(FAST_INTERVAL_SECS),
+            self.format_duration(self.settings().min_update_interval_secs)
+        ))
+        .with_burst(Burst {
+            window_secs,
+            interval_secs: FAST_INTERVAL_SECS,
+        })
+    }
+
+    async fn handle_rate_limit(&self, secs: Option<u64>) -> CommandResult {
+        let Some(limiter) = &self.rate_limiter else {
+            return CommandResult::error("The rate limit needs a Telegram connection.");
+        };
+
+        let Some(secs) = secs else {
+            let interval = limiter.min_interval().await.as_secs();
+            let wait = limiter.time_until_allowed().await.as_secs();
+            let next = if wait == 0 {
+                "now".to_owned()
+            } else {
+                format!("in {}", self.format_duration(wait))
+            };
+            return CommandResult::success(format!(
+                "⏱ Minimum interval between updates: {}\nNext update allowed: {next}",
+                self.format_duration(interval)
+            ))
+            .with_data(serde_json::json!({
+                "min_interval_secs": interval,
+                "wait_secs": wait,
+            }));
+        };
+
+        limiter.set_min_interval(Duration::from_secs(secs)).await;
+        let mut state = self.scheduler_state.write().await;
+        state.rate_limit_secs = Some(secs);
+        self.save_state(&mut state);
+
+        let floor = self.settings().min_update_interval_secs;
+        let warning = if secs < floor {
+            format!(
+                "\n⚠ Shorter than the configured {}; this risks flood waits.",
+                self.format_duration(floor)
+            )
+        } else {
+            String::new()
+        };
+        CommandResult::success(format!(
+            "✓ Minimum interval between updates set to {}{warning}",
+            self.format_duration(secs)
+        ))
+    }
+
+    async fn handle_lint(&self) -> CommandResult {
+        let warnings = self
+            .config
+            .read()
+            .await
+            .lint(self.settings().min_update_interval_secs);
+        let data = serde_json::to_value(&warnings).unwrap_or_default();
+
+        if warnings.is_empty() {
+            return CommandResult::success("✓ No issues found.").with_data(data);
+        }
+
+        let mut lines = vec![format!("⚠ {} possible issue(s):", warnings.len())];
+        lines.extend(warnings.iter().map(|w| format!("• {w}")));
+        CommandResult::success(lines.join("\n")).with_data(data)
+    }
+
+    async fn handle_debug(&self) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+        let config = self.config.read().await;
+        let now = state
+            .clock()
+            .now_utc()
+            .with_timezone(&self.settings().timezone)
+            .naive_local();
+        let explanation = NextExplanation::capture(&state, &config, now);
+        let data = serde_json::to_value(&explanation).unwrap_or_default();
+
+        let next = match (&explanation.next_id, explanation.next_index) {
+            (Some(id), Some(index)) => format!("[{id}] (index {index})"),
+            _ => "nothing".to_owned(),
+        };
+        let lines = [
+            format!("current_index: {}", explanation.current_index),
+            format!("is_paused: {}", explanation.is_paused),
+            format!("is_held: {}", explanation.is_held),
+            format!("is_expired: {}", explanation.is_expired),
+            format!("has_deadline: {}", explanation.has_deadline),
+            format!("has_custom: {}", explanation.has_custom),
+            format!("is_due: {}", explanation.is_due),
+            format!(
+                "should_advance: {}",
+                explanation
+                    .should_advance
+                    .map_or_else(|| "-".to_owned(), |a| a.to_string())
+            ),
+            format!("next: {next}"),
+            format!(
+                "→ {}",
+                if explanation.would_update() {
+                    "the next tick updates the bio"
+                } else {
+                    "the next tick keeps the bio"
+                }
+            ),
+        ];
+        CommandResult::success(lines.join("\n")).with_data(data)
+    }
+
+    async fn handle_timeline(&self) -> CommandResult {
+        let clock = Arc::clone(self.scheduler_state.read().await.clock());
+        let config = self.config.read().await;
+        let tz = self.settings().timezone;
+
+        if config
+            .active_descriptions()
+            .iter()
+            .all(|d| d.schedule.is_none())
+        {
+            return CommandResult::success(
+                "No schedule windows configured, regular rotation all day.",
+            );
+        }
+
+        let now = clock.now_utc();
+        let segments = config.timeline(now, now + chrono::Duration::hours(24), &tz);
+
+        let mut lines = vec![format!("Timeline for the next 24h ({tz}):")];
+        for (i, segment) in segments.iter().enumerate() {
+            let start = if i == 0 {
+                "now  ".to_owned()
+            } else {
+                segment.start.format("%H:%M").to_string()
+            };
+            let active = segment
+                .description_id
+                .as_ref()
+                .map_or_else(|| "rotation".to_owned(), |id| format!("[{id}]"));
+            lines.push(format!("  {start} → {active}"));
+        }
+
+        CommandResult::success(lines.join("\n"))
+    }
+
+    fn handle_set_name(first: String, last: Option<String>) -> CommandResult {
+        if let Err(e) = validate_name(&first, last.as_deref()) {
+            return CommandResult::error(e);
+        }
+
+        let display = match &last {
+            Some(last) => format!("{first} {last}"),
+            None => first.clone(),
+        };
+
+        CommandResult::success(format!("✓ Name set to: {display}")).with_name_change(NameChange {
+            first_name: first,
+            last_name: last,
+        })
+    }
+
+    async fn handle_stats(&self) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+
+        if state.stats.is_empty() {
+            return CommandResult::success("No statistics recorded yet.");
+        }
+
+        let now = state.now_unix();
+        let mut lines = vec!["Display statistics:".to_owned()];
+        let mut entries = Vec::new();
+
+        for (id, stats) in state.stats.iter() {
+            let total_secs = state.stats.total_secs_at(id, now);
+            lines.push(format!(
+                "  [{id}] shown {}×, {} total, last {} ago",
+                stats.shown_count,
+                self.format_duration(total_secs),
+                self.format_duration(now.saturating_sub(stats.last_shown_unix))
+            ));
+            entries.push(serde_json::json!({
+                "id": id,
+                "shown_count": stats.shown_count,
+                "total_secs": total_secs,
+                "last_shown_unix": stats.last_shown_unix,
+            }));
+        }
+
+        CommandResult::success(lines.join("\n")).with_data(serde_json::json!({ "stats": entries }))
+    }
+
+    async fn handle_stats_reset(&self, confirmed: bool) -> CommandResult {
+        let mut state = self.scheduler_state.write().await;
+        let count = state.stats.len();
+
+        if self.settings().confirm_destructive && !confirmed {
+            return CommandResult::error(format!(
+                "This clears statistics for {count} descriptions. \
+                 Send 'stats reset confirm' to proceed."
+            ));
+        }
+
+        state.reset_stats();
+        self.save_state(&mut state);
+
+        CommandResult::success(format!("✓ Cleared statistics for {count} descriptions"))
+    }
+
+    fn handle_config(&self, key: Option<&str>) -> CommandResult {
+        let settings = self.settings();
+        let keys = match key.map(tunable_key).transpose() {
+            Ok(Some(key)) => vec![key],
+            Ok(None) => TUNABLE_KEYS.to_vec(),
+            Err(e) => return CommandResult::error(e.to_string()),
+        };
+
+        let mut lines = Vec::new();
+        let mut data = serde_json::Map::new();
+        for key in keys {
+            let value = settings.get_setting(key).unwrap_or_default();
+            lines.push(format!("{key} = {value}"));
+            data.insert(key.to_owned(), value.into());
+        }
+
+        CommandResult::success(lines.join("\n")).with_data(data.into())
+    }
+
+    async fn handle_config_set(&self, key: &str, value: &str) -> CommandResult {
+        let key = match tunable_key(key) {
+            Ok(key) => key,
+            Err(e) => return CommandResult::error(e.to_string()),
+        };
+
+        let (old, new, read_only, interval) = {
+            let mut settings = self
+                .settings
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+            let old = settings.get_setting(key).unwrap_or_default();
+            if let Err(e) = settings.set_setting(key, value) {
+                return CommandResult::error(e.to_string());
+            }
+            let new = settings.get_setting(key).unwrap_or_default();
+            let interval = Duration::from_secs(settings.min_update_interval_secs);
+            (old, new, settings.read_only, interval)
+        };
+
+        // An interval chosen with 'rate-limit' still wins over the setting
+        if key == "min-interval"
+            && let Some(limiter) = &self.rate_limiter
+            && self.scheduler_state.read().await.rate_limit_secs.is_none()
+        {
+            limiter.set_min_interval(interval).await;
+        }
+
+        let note = match &self.settings_path {
+            Some(_) if read_only => {
+                "\n⚠ Read-only mode, the change lasts until restart.".to_owned()
+            }
+            Some(path) => match save_setting(path, key, &new) {
+                Ok(()) => String::new(),
+                Err(e) => {
+                    warn!("Failed to save settings to {}: {}", path.display(), e);
+                    format!("\n⚠ Could not save the change, it lasts until restart: {e}")
+                }
+            },
+            None => String::new(),
+        };
+
+        CommandResult::success(format!("✓ {key} set to {new} (was {old}){note}"))
+    }
+
+    fn handle_log(&self, count: usize) -> CommandResult {
+        let path = changelog_path(self.config_path());
+        let entries = match read_entries(&path, count) {
+            Ok(entries) => entries,
+            Err(e) => return CommandResult::error(format!("Cannot read the changelog: {e}")),
+        };
+        if entries.is_empty() {
+            return CommandResult::success("No changes recorded yet.");
+        }
+
+        let timezone = self.settings().timezone;
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|entry| {
+                let at = entry.at.with_timezone(&timezone).format("%Y-%m-%d %H:%M");
+                match &entry.id {
+                    Some(id) => format!("{at} [{id}] {}", truncate(&entry.command, 60)),
+                    None => format!("{at} {}", truncate(&entry.command, 60)),
+                }
+            })
+            .collect();
+        CommandResult::success(format!(
+            "Last {} changes (newest last):\n{}",
+            entries.len(),
+            lines.join("\n")
+        ))
+        .with_data(serde_json::json!(entries))
+    }
+
+    fn handle_snapshots(&self) -> CommandResult {
+        let dir = snapshots_dir(self.config_path());
+        match list_snapshots(&dir) {
+            Ok(names) if names.is_empty() => {
+                CommandResult::success("No snapshots yet. Use 'snapshot save <name>'.")
+            }
+            Ok(names) => CommandResult::success(format!(
+                "Snapshots in {}:\n{}",
+                dir.display(),
+                names.join("\n")
+            ))
+            .with_data(serde_json::json!(names)),
+            Err(e) => CommandResult::error(format!("Cannot list snapshots: {e}")),
+        }
+    }
+
+    async fn handle_snapshot_save(&self, name: &str) -> CommandResult {
+        if self.settings().read_only {
+            return CommandResult::error("Read-only mode, snapshots cannot be saved.");
+        }
+
+        let config = self.config.read().await;
+        let dir = snapshots_dir(self.config_path());
+        match save_snapshot(&config, &dir, name) {
+            Ok(path) => CommandResult::success(format!(
+                "✓ Saved {} descriptions as snapshot '{name}' ({})",
+                config.len(),
+                path.display()
+            )),
+            Err(e) => CommandResult::error(format!("Failed to save snapshot: {e}")),
+        }
+    }
+
+    async fn handle_snapshot_restore(&self, name: &str) -> CommandResult {
+        let dir = snapshots_dir(self.config_path());
+        let new_config = match load_snapshot(&dir, name) {
+            Ok(config) => config,
+            Err(e) => return CommandResult::error(format!("Failed to restore snapshot: {e}")),
+        };
+
+        match self.replace_config(new_config, true).await {
+            Ok((old_len, new_len)) => CommandResult::success_with_update(format!(
+                "✓ Restored snapshot '{name}'. {old_len} → {new_len} descriptions."
+            )),
+            Err(e) => CommandResult::error(e),
+        }
+    }
+
+    async fn handle_relocate(
+        &self,
+        what: RelocateTarget,
+        new_path: &Path,
+        remove_old: bool,
+    ) -> CommandResult {
+        if self.settings().read_only {
+            return CommandResult::error("Read-only mode, files cannot be moved.");
+        }
+        let new_path = &self.resolve_file_path(new_path);
+        if let Err(e) = check_writable(new_path) {
+            return CommandResult::error(format!("Cannot use {}: {e}", new_path.display()));
+        }
+
+        let moved = match what {
+            RelocateTarget::Config => self.relocate_config(new_path).await,
+            RelocateTarget::State => self.relocate_state(new_path).await,
+        };
+        let old_path = match moved {
+            Ok(old_path) => old_path,
+            Err(e) => {
+                let _ = std::fs::remove_file(new_path);
+                return CommandResult::error(format!("Failed to move the {what} file: {e}"));
+            }
+        };
+
+        let mut notes = Vec::new();
+        if what == RelocateTarget::Config
+            && let Err(e) = copy_config_companions(&old_path, new_path)
+        {
+            warn!("Failed to copy the changelog or snapshots: {}", e);
+            notes.push(format!("⚠ Could not copy the changelog or snapshots: {e}"));
+        }
+        if !remove_old {
+            notes.push("The old file was left in place.".to_owned());
+        } else if let Err(e) = std::fs::remove_file(&old_path) {
+            warn!("Failed to remove {}: {}", old_path.display(), e);
+            notes.push(format!("⚠ Could not remove the old file: {e}"));
+        } else if what == RelocateTarget::Config {
+            let _ = std::fs::remove_file(backup_path(&old_path));
+            let _ = std::fs::remove_file(changelog_path(&old_path));
+        }
+        info!("Moved the {} file to {}", what, new_path.display());
+
+        let setting = match what {
+            RelocateTarget::Config => "DESCRIPTIONS_PATH (or --config)",
+            RelocateTarget::State => "STATE_PATH",
+        };
+        notes.push(format!(
+            "Set {setting} to the new path, the old one is used again after a restart."
+        ));
+        CommandResult::success(format!(
+            "✓ {what} file moved from {} to {}\n{}",
+            old_path.display(),
+            new_path.display(),
+            notes.join("\n")
+        ))
+        .with_data(serde_json::json!({
+            "what": what,
+            "from": old_path,
+            "to": new_path,
+            "removed_old": remove_old,
+        }))
+    }
+
+    /// Resolves a relative path given in a command against the config
+    /// directory, like the paths given at startup.
+    fn resolve_file_path(&self, path: &Path) -> PathBuf {
+        self.settings_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map_or_else(|| path.to_path_buf(), |dir| dir.join(path))
+    }
+
+    /// Writes the descriptions to `new_path` and saves there from now on.
+    ///
+    /// Returns the previous path.
+    async fn relocate_config(&self, new_path: &Path) -> Result<PathBuf, String> {
+        // The write lock keeps a debounced save from writing the old file
+        let config = self.config.write().await;
+        config.save_to_file(new_path).map_err(|e| e.to_string())?;
+        self.save_pending.store(false, Ordering::SeqCst);
+
+        let mut path = self
+            .config_path
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let old_path = std::mem::replace(&mut *path, new_path.to_string_lossy().into_owned());
+        Ok(PathBuf::from(old_path))
+    }
+
+    /// Writes the state to `new_path` and saves there from now on.
+    ///
+    /// Returns the previous path.
+    async fn relocate_state(&self, new_path: &Path) -> Result<PathBuf, String> {
+        let Some(old_path) = self.state_store.path() else {
+            return Err("the state is not saved to a file (PERSIST_STATE=false)".to_owned());
+        };
+
+        let mut state = self.scheduler_state.write().await;
+        self.state_store
+            .relocate(new_path.to_path_buf())
+            .map_err(|e| e.to_string())?;
+        if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+            let _ = self.state_store.relocate(old_path);
+            return Err(e.to_string());
+        }
+        Ok(old_path)
+    }
+
+    async fn handle_info(&self) -> CommandResult {
+        let state = self.scheduler_state.read().await;
+        let config = self.config.read().await;
+        let info = RuntimeInfo::capture(
+            &state,
+            &config,
+            self.started_at.elapsed(),
+            self.premium_source,
+        );
+        let message = info.format(self.settings().locale);
+        CommandResult::success(message).with_data(serde_json::json!(info))
+    }
+}
+
+/// Records a changed setting in the settings file.
+fn save_setting(path: &Path, key: &str, value: &str) -> std::io::Result<()> {
+    let mut overrides = SettingsOverrides::load(path)?;
+    overrides.insert(key, value);
+    overrides.save(path)
+}
+
+/// Copies the changelog and snapshots of the descriptions file at
+/// `old_path` next to `new_path`; existing files there are kept.
+fn copy_config_companions(old_path: &Path, new_path: &Path) -> std::io::Result<()> {
+    let (old_log, new_log) = (changelog_path(old_path), changelog_path(new_path));
+    if old_log.exists() && !new_log.exists() {
+        std::fs::copy(&old_log, &new_log)?;
+    }
+
+    let (old_dir, new_dir) = (snapshots_dir(old_path), snapshots_dir(new_path));
+    if old_dir == new_dir || !old_dir.is_dir() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(&new_dir)?;
+    for entry in std::fs::read_dir(&old_dir)? {
+        let entry = entry?;
+        let target = new_dir.join(entry.file_name());
+        if entry.file_type()?.is_file() && !target.exists() {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a file can be created at `path` without replacing one.
+fn check_writable(path: &Path) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    std::fs::remove_file(path)
+}
+
+/// Resolves a command target to a description index in the active profile.
+///
+/// The target is matched as an ID first, then as a 1-based position as
+/// shown by `list` (`2` is the second description).
+fn resolve_target(config: &DescriptionConfig, target: &str) -> Option<usize> {
+    config
+        .active_descriptions()
+        .iter()
+        .position(|d| d.id == target)
+        .or_else(|| {
+            target
+                .parse::<usize>()
+                .ok()
+                .filter(|&i| i > 0 && i <= config.len())
+                .map(|i| i - 1)
+        })
+}
+
+/// Describes what is wrong with a description duration, if anything.
+fn duration_problem(secs: u64) -> Option<&'static str> {
+    if secs == 0 {
+        Some("must be greater than 0 seconds")
+    } else if secs > MAX_DURATION_SECS {
+        Some("must be at most one year")
+    } else {
+        None
+    }
+}
+
+/// Validates a display name (first name required, both within 64 UTF-16 units).
+fn validate_name(first: &str, last: Option<&str>) -> Result<(), String> {
+    if first.trim().is_empty() {
+        return Err("First name cannot be empty.".to_owned());
+    }
+
+    for (label, value) in [("First name", Some(first)), ("Last name", last)] {
+        let Some(value) = value else { continue };
+
+        let len = utf16_len(value);
+        if len > MAX_NAME_LENGTH {
+            return Err(format!(
+                "{label} too long: {len} chars (max: {MAX_NAME_LENGTH})"
+            ));
+        }
+        if value.chars().any(char::is_control) {
+            return Err(format!("{label} contains invalid characters."));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates description text for use as a Telegram bio.
+///
+/// Runs [`Description::validate_against`] on the text, so commands accept
+/// exactly what a configuration file may contain. Durations are checked
+/// by the commands that take one.
+fn validate_description_text(text: &str, config: &DescriptionConfig) -> Result<(), String> {
+    let candidate = Description::new(String::new(), text.to_owned(), 1);
+    candidate.validate_against(config).map_err(|e| match e {
+        ValidationError::Empty { .. } => "Description text cannot be empty.".to_owned(),
+        ValidationError::TooLong {
+            length, max_length, ..
+        } => format!("Text too long: {length} chars (max: {max_length})"),
+        ValidationError::InvalidCharacter { code, .. } => {
+            format!("Invalid character detected (code: U+{code:04X}). Only text is allowed.")
+        }
+        ValidationError::EmbeddedObject { .. } => {
+            "Embedded objects (images, files) are not allowed. Only text is supported.".to_owned()
+        }
+        ValidationError::InvisibleCharacter { code, .. } => format!(
+            "Invisible/zero-width characters detected (U+{code:04X}). Please use only visible text."
+        ),
+        other => other.to_string(),
+    })
+}
+
+/// Formats a duration in seconds to a human-readable string
+/// using the unit suffixes of the given locale.
+pub(super) fn format_duration_localized(secs: u64, locale: Locale) -> String {
+    let (s, m, h) = locale.duration_units();
+    if secs < 60 {
+        format!("{secs}{s}")
+    } else if secs < 3600 {
+        format!("{}{m}", secs / 60)
+    } else {
+        let hours = secs / 3600;
+        let mins = (secs % 3600) / 60;
+        if mins == 0 {
+            format!("{hours}{h}")
+        } else {
+            format!("{hours}{h} {mins}{m}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{TimeWindow, backup_path};
+    use crate::scheduler::{PersistentState, ReadOnlyStateStore, decide_next, state_store};
+
+    #[test]
+    fn test_format_duration() {
+        let en = Locale::En;
+        assert_eq!(format_duration_localized(30, en), "30s");
+        assert_eq!(format_duration_localized(60, en), "1m");
+        assert_eq!(format_duration_localized(90, en), "1m");
+        assert_eq!(format_duration_localized(3600, en), "1h");
+        assert_eq!(format_duration_localized(3660, en), "1h 1m");
+        assert_eq!(format_duration_localized(7200, en), "2h");
+    }
+
+    #[test]
+    fn test_format_duration_russian() {
+        let ru = Locale::Ru;
+        assert_eq!(format_duration_localized(30, ru), "30с");
+        assert_eq!(format_duration_localized(90, ru), "1м");
+        assert_eq!(format_duration_localized(3600, ru), "1ч");
+        assert_eq!(format_duration_localized(3660, ru), "1ч 1м");
+    }
+
+    #[test]
+    fn test_validate_description_text_valid() {
+        let config = DescriptionConfig::default();
+        assert!(validate_description_text("Hello World!", &config).is_ok());
+        assert!(validate_description_text("Привет мир! 👋", &config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_description_text_empty() {
+        let config = DescriptionConfig::default();
+        assert!(validate_description_text("", &config).is_err());
+    }
+
+    #[test]
+    fn test_validate_description_text_too_long() {
+        let config = DescriptionConfig::default();
+        let long_text = "a".repeat(71);
+        assert!(validate_description_text(&long_text, &config).is_err());
+    }
+
+    #[test]
+    fn test_validate_description_text_premium_allows_longer() {
+        let config = DescriptionConfig {
+            is_premium: true,
+            ..Default::default()
+        };
+        let text = "a".repeat(100);
+        assert!(validate_description_text(&text, &config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_description_text_zero_width() {
+        let config = DescriptionConfig::default();
+        let text_with_zwsp = "Hello\u{200B}World";
         assert!(validate_description_text(text_with_zwsp, &config).is_err());
     }
+
+    #[test]
+    fn test_validate_name_length() {
+        assert!(validate_name(&"a".repeat(64), Some(&"b".repeat(64))).is_ok());
+        assert!(validate_name(&"a".repeat(65), None).is_err());
+        assert!(validate_name("Alex", Some(&"b".repeat(65))).is_err());
+        // Emoji count as two UTF-16 units
+        assert!(validate_name(&"👋".repeat(33), None).is_err());
+        assert!(validate_name("  ", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_name_without_last_keeps_it_unchanged() {
+        let handler = test_handler(DescriptionConfig::example(), "set_name");
+
+        let result = handler
+            .execute(BotCommand::SetName {
+                first: "Alex".to_owned(),
+                last: None,
+            })
+            .await;
+        assert!(result.success);
+        assert_eq!(
+            result.name_change,
+            Some(NameChange {
+                first_name: "Alex".to_owned(),
+                last_name: None,
+            })
+        );
+
+        let result = handler
+            .execute(BotCommand::SetName {
+                first: "a".repeat(65),
+                last: None,
+            })
+            .await;
+        assert!(!result.success);
+        assert!(result.name_change.is_none());
+    }
+
+    /// Returns a unique path in the temp directory for test files.
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!(
+                "description_bot_test_{}_{name}",
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn test_handler(config: DescriptionConfig, name: &str) -> CommandHandler {
+        CommandHandler::new(
+            vec!["/description_bot".to_owned()],
+            Arc::new(RwLock::new(SchedulerState::new())),
+            Arc::new(RwLock::new(config)),
+            temp_path(&format!("{name}_config.json")),
+            state_store(temp_path(&format!("{name}_state.json")), true),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_profile_switch_resets_index_and_saves() {
+        let mut config = DescriptionConfig::example();
+        config.profiles.insert(
+            "work".to_owned(),
+            vec![Description::new(
+                "desk".to_owned(),
+                "At my desk".to_owned(),
+                60,
+            )],
+        );
+        let handler = test_handler(config, "profile_switch");
+        handler.scheduler_state.write().await.current_index = 2;
+
+        let result = handler
+            .execute(BotCommand::Profile("work".to_owned()))
+            .await;
+        assert!(result.success);
+        assert!(result.trigger_update);
+        assert_eq!(handler.scheduler_state.read().await.current_index, 0);
+
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(handler.config_path()).unwrap();
+        assert_eq!(saved.active_profile.as_deref(), Some("work"));
+        assert_eq!(saved.get(0).unwrap().id, "desk");
+
+        let result = handler
+            .execute(BotCommand::Profile("missing".to_owned()))
+            .await;
+        assert!(!result.success);
+
+        let _ = std::fs::remove_file(handler.config_path());
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_only_restricts_rotation_until_all() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].tags = vec!["home".to_owned()];
+        config.descriptions[2].tags = vec!["Home".to_owned()];
+        let handler = test_handler(config, "only_tag");
+        let next_id = |state: &SchedulerState, config: &DescriptionConfig| {
+            let now = chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap();
+            decide_next(state, config, now).unwrap().description_id
+        };
+
+        let result = handler.execute(BotCommand::Only("nope".to_owned())).await;
+        assert!(!result.success);
+        assert_eq!(handler.scheduler_state.read().await.active_tag, None);
+
+        let result = handler.execute(BotCommand::Only("home".to_owned())).await;
+        assert!(result.success);
+        assert!(result.message.contains("2 of 3"), "{}", result.message);
+        let saved = PersistentState::load(handler.state_store.path().unwrap());
+        assert_eq!(saved.active_tag.as_deref(), Some("home"));
+
+        // "working" is untagged and skipped
+        {
+            let mut state = handler.scheduler_state.write().await;
+            state.set_deadline(0);
+            let config = handler.config.read().await;
+            assert_eq!(next_id(&state, &config), "evening");
+        }
+
+        let result = handler.execute(BotCommand::All).await;
+        assert!(result.success);
+        {
+            let state = handler.scheduler_state.read().await;
+            let config = handler.config.read().await;
+            assert_eq!(next_id(&state, &config), "working");
+        }
+        assert!(!handler.execute(BotCommand::All).await.success);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_mode_rotates_without_writing_state() {
+        let state_path = temp_path("ephemeral_state.json");
+        let _ = std::fs::remove_file(&state_path);
+        let handler = CommandHandler::new(
+            vec!["/description_bot".to_owned()],
+            Arc::new(RwLock::new(SchedulerState::new())),
+            Arc::new(RwLock::new(DescriptionConfig::example())),
+            temp_path("ephemeral_config.json"),
+            state_store(&state_path, false),
+        );
+
+        let result = handler.execute(BotCommand::Skip).await;
+        assert!(result.success);
+        let result = handler.execute(BotCommand::Pause(PauseMode::All)).await;
+        assert!(result.success);
+
+        let state = handler.scheduler_state.read().await;
+        assert_eq!(state.current_index, 1);
+        assert!(state.is_paused);
+        assert!(!std::path::Path::new(&state_path).exists());
+        assert!(handler.state_store.path().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_writes_no_files() {
+        let config_path = temp_path("read_only_config.json");
+        let state_path = temp_path("read_only_state.json");
+        let _ = std::fs::remove_file(&config_path);
+        let _ = std::fs::remove_file(&state_path);
+        let settings = BotSettings {
+            read_only: true,
+            ..BotSettings::default()
+        };
+        let handler = CommandHandler::new(
+            vec!["/description_bot".to_owned()],
+            Arc::new(RwLock::new(SchedulerState::new())),
+            Arc::new(RwLock::new(DescriptionConfig::example())),
+            config_path.clone(),
+            Arc::new(ReadOnlyStateStore::new(&state_path)),
+        )
+        .with_settings(settings);
+
+        let add = BotCommand::Add(AddArgs {
+            id: "extra".to_owned(),
+            duration_secs: 60,
+            text: "Extra".to_owned(),
+        });
+        assert!(handler.execute(add).await.success);
+        assert!(handler.execute(BotCommand::Skip).await.success);
+        assert!(
+            handler
+                .execute(BotCommand::Pause(PauseMode::All))
+                .await
+                .success
+        );
+        handler.flush_config().await.unwrap();
+
+        // The changes apply in memory only
+        assert_eq!(handler.config.read().await.len(), 4);
+        assert!(handler.scheduler_state.read().await.is_paused);
+        assert!(!std::path::Path::new(&config_path).exists());
+        assert!(!std::path::Path::new(&state_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_deleting_last_description_pauses_rotation() {
+        let config = DescriptionConfig {
+            descriptions: vec![Description::new("only".to_owned(), "Only".to_owned(), 60)],
+            ..DescriptionConfig::default()
+        };
+        let handler = test_handler(config, "delete_last");
+
+        let result = handler.execute(BotCommand::Delete("only".to_owned())).await;
+        assert!(result.success, "{}", result.message);
+        assert!(result.message.contains("paused"), "{}", result.message);
+        assert!(handler.config.read().await.is_empty());
+
+        let state = handler.scheduler_state.read().await;
+        assert!(state.is_paused);
+        assert_eq!(state.current_index, 0);
+        let saved = PersistentState::load(handler.state_store.path().unwrap());
+        assert!(saved.is_paused);
+
+        handler.flush_config().await.unwrap();
+        let _ = std::fs::remove_file(handler.config_path());
+        let _ = std::fs::remove_file(backup_path(handler.config_path()));
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_debug_reports_decision_inputs() {
+        let handler = test_handler(DescriptionConfig::example(), "debug");
+        handler
+            .scheduler_state
+            .write()
+            .await
+            .set_custom("Lunch".to_owned(), 900);
+
+        let result = handler.execute(BotCommand::Debug).await;
+        assert!(result.success);
+        assert!(
+            result.message.contains("has_custom: true"),
+            "{}",
+            result.message
+        );
+        assert!(result.message.contains("next: [custom] (index 0)"));
+        let data = result.data.unwrap();
+        assert_eq!(data["next_id"], "custom");
+        assert_eq!(data["should_advance"], false);
+    }
+
+    #[tokio::test]
+    async fn test_lint_lists_warnings() {
+        let config = DescriptionConfig {
+            descriptions: vec![
+                Description::new("a".to_owned(), "Same".to_owned(), 60),
+                Description::new("b".to_owned(), "Same".to_owned(), 120),
+            ],
+            ..DescriptionConfig::default()
+        };
+        let handler = test_handler(config, "lint");
+
+        let result = handler.execute(BotCommand::Lint).await;
+        assert!(result.success);
+        assert!(
+            result.message.contains("[b] has the same text as [a]"),
+            "{}",
+            result.message
+        );
+        assert_eq!(result.data.unwrap()[0]["kind"], "duplicate_text");
+
+        let handler = test_handler(DescriptionConfig::example(), "lint_clean");
+        handler
+            .config
+            .write()
+            .await
+            .active_descriptions_mut()
+            .truncate(1);
+        let result = handler.execute(BotCommand::Lint).await;
+        assert!(result.message.contains("No issues"), "{}", result.message);
+    }
+
+    #[tokio::test]
+    async fn test_render_marks_truncation() {
+        let mut config = DescriptionConfig::example();
+        config.active_descriptions_mut().push(
+            Description::builder()
+                .id("long")
+                .text("b".repeat(75))
+                .duration_secs(60)
+                .build()
+                .unwrap(),
+        );
+        let handler = test_handler(config, "render").with_settings(BotSettings {
+            on_overlong: OverlongPolicy::Truncate,
+            ..BotSettings::default()
+        });
+
+        let result = handler.execute(BotCommand::Render("long".to_owned())).await;
+        assert!(result.success, "{}", result.message);
+        assert!(
+            result.message.contains("Length: 70/70"),
+            "{}",
+            result.message
+        );
+        assert!(result.message.contains("Truncated from 75"));
+
+        let result = handler.execute(BotCommand::Render("1".to_owned())).await;
+        assert!(result.message.contains("Bio for [morning]"));
+        assert!(!result.message.contains("Truncated"));
+
+        let result = handler
+            .execute(BotCommand::Render("missing".to_owned()))
+            .await;
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn test_edit_appends_changelog_line() {
+        let handler = test_handler(DescriptionConfig::example(), "changelog");
+        let path = changelog_path(handler.config_path());
+        let _ = std::fs::remove_file(&path);
+
+        let result = handler
+            .execute(BotCommand::Edit(EditArgs {
+                id: "2".to_owned(),
+                text: "🍔 Lunch\nbreak".to_owned(),
+            }))
+            .await;
+        assert!(result.success, "{}", result.message);
+        // Failed and read-only commands are not recorded
+        handler
+            .execute(BotCommand::Delete("missing".to_owned()))
+            .await;
+        handler.execute(BotCommand::Status).await;
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let fields: Vec<&str> = lines[0].split('\t').collect();
+        assert!(chrono::DateTime::parse_from_rfc3339(fields[0]).is_ok());
+        assert_eq!(fields[1..], ["working", "edit 2 🍔 Lunch\\nbreak"]);
+
+        let result = handler.execute(BotCommand::Log(None)).await;
+        assert!(
+            result.message.contains("[working] edit 2 🍔 Lunch"),
+            "{}",
+            result.message
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_save_list_restore() {
+        let handler = test_handler(DescriptionConfig::example(), "snapshot");
+        let (work, weekend) = (
+            format!("work_{}", std::process::id()),
+            format!("weekend_{}", std::process::id()),
+        );
+
+        let result = handler
+            .execute(BotCommand::SnapshotSave(work.clone()))
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        // A smaller setup, while the rotation is at the last description
+        handler
+            .config
+            .write()
+            .await
+            .active_descriptions_mut()
+            .truncate(1);
+        handler
+            .execute(BotCommand::SnapshotSave(weekend.clone()))
+            .await;
+        handler
+            .execute(BotCommand::SnapshotRestore(work.clone()))
+            .await;
+        handler.execute(BotCommand::Goto("3".to_owned())).await;
+        assert_eq!(handler.scheduler_state.read().await.current_index, 2);
+
+        let result = handler.execute(BotCommand::Snapshots).await;
+        assert!(result.message.contains(&work) && result.message.contains(&weekend));
+
+        let result = handler
+            .execute(BotCommand::SnapshotRestore(weekend.clone()))
+            .await;
+        assert!(result.success, "{}", result.message);
+        assert!(result.message.contains("3 → 1"), "{}", result.message);
+        assert_eq!(handler.config.read().await.len(), 1);
+        assert_eq!(handler.scheduler_state.read().await.current_index, 0);
+
+        // The restored descriptions were written to the descriptions file
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(handler.config_path()).unwrap();
+        assert_eq!(saved.len(), 1);
+
+        let result = handler
+            .execute(BotCommand::SnapshotRestore("missing".to_owned()))
+            .await;
+        assert!(!result.success);
+
+        let dir = snapshots_dir(handler.config_path());
+        for name in [work, weekend] {
+            std::fs::remove_file(dir.join(format!("{name}.json"))).unwrap();
+        }
+        let _ = std::fs::remove_file(handler.config_path());
+        let _ = std::fs::remove_file(backup_path(handler.config_path()));
+    }
+
+    #[tokio::test]
+    async fn test_why_reports_rejection_and_not_found() {
+        let handler = test_handler(DescriptionConfig::example(), "why");
+
+        let result = handler.execute(BotCommand::Why("missing".to_owned())).await;
+        assert!(!result.success);
+
+        let result = handler.execute(BotCommand::Why("1".to_owned())).await;
+        assert!(result.success);
+        assert!(result.message.contains("eligible"), "{}", result.message);
+
+        let id = handler.config.read().await.get(0).unwrap().id.clone();
+        handler.scheduler_state.write().await.mark_rejected(&id);
+        let result = handler.execute(BotCommand::Why(id)).await;
+        assert!(result.message.contains("rejected by Telegram"));
+
+        handler.scheduler_state.write().await.active_tag = Some("nope".to_owned());
+        let result = handler.execute(BotCommand::Why("2".to_owned())).await;
+        assert!(result.message.contains("tag 'nope'"), "{}", result.message);
+    }
+
+    #[tokio::test]
+    async fn test_duration_all_updates_every_description() {
+        let settings = BotSettings {
+            min_update_interval_secs: 60,
+            ..BotSettings::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "duration_all").with_settings(settings);
+
+        let result = handler.execute(BotCommand::DurationAll(1800)).await;
+        assert!(result.success, "{}", result.message);
+        assert!(
+            result.message.contains("3 description(s)"),
+            "{}",
+            result.message
+        );
+        assert!(!result.message.contains('⚠'));
+        assert!(
+            handler
+                .config
+                .read()
+                .await
+                .descriptions
+                .iter()
+                .all(|d| d.duration_secs == 1800)
+        );
+
+        // Below the floor: applied, with a warning
+        let result = handler.execute(BotCommand::DurationAll(30)).await;
+        assert!(result.success);
+        assert!(
+            result
+                .message
+                .contains("⚠ Below the minimum update interval")
+        );
+        assert_eq!(
+            handler.config.read().await.descriptions[2].duration_secs,
+            30
+        );
+
+        let result = handler.execute(BotCommand::DurationAll(30)).await;
+        assert!(result.message.starts_with("All descriptions already"));
+
+        handler.flush_config().await.unwrap();
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_duration_below_update_interval_warns() {
+        let settings = BotSettings {
+            min_update_interval_secs: 60,
+            ..BotSettings::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "duration_floor").with_settings(settings);
+
+        let result = handler
+            .execute(BotCommand::Duration(DurationArgs {
+                id: "1".to_owned(),
+                duration_secs: 10,
+            }))
+            .await;
+        assert!(result.success);
+        assert!(
+            result
+                .message
+                .contains("⚠ Below the minimum update interval")
+        );
+        assert_eq!(handler.settings().effective_duration_secs(10), 60);
+
+        let result = handler.execute(BotCommand::View("1".to_owned())).await;
+        assert!(
+            result.message.contains("effectively 1m"),
+            "{}",
+            result.message
+        );
+
+        let result = handler.execute(BotCommand::Status).await;
+        assert_eq!(result.data.unwrap()["effective_duration_secs"], 60);
+
+        // At or above the floor nothing is reported
+        let result = handler
+            .execute(BotCommand::Duration(DurationArgs {
+                id: "1".to_owned(),
+                duration_secs: 60,
+            }))
+            .await;
+        assert!(!result.message.contains('⚠'));
+
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_presence_command_maps_to_status_change() {
+        let handler = test_handler(DescriptionConfig::example(), "presence");
+
+        let result = handler
+            .execute(BotCommand::Presence(Presence::Offline))
+            .await;
+        assert!(result.success);
+        assert_eq!(result.presence, Some(Presence::Offline));
+        assert!(!result.trigger_update);
+
+        let result = handler
+            .execute(BotCommand::Presence(Presence::Online))
+            .await;
+        assert_eq!(result.presence, Some(Presence::Online));
+    }
+
+    #[tokio::test]
+    async fn test_fast_command_relaxes_rate_limit() {
+        let handler = test_handler(DescriptionConfig::example(), "fast");
+
+        let result = handler.execute(BotCommand::Fast(None)).await;
+        assert!(result.success);
+        assert!(result.message.contains("flood wait"));
+        assert_eq!(
+            result.burst,
+            Some(Burst {
+                window_secs: FAST_WINDOW_SECS,
+                interval_secs: FAST_INTERVAL_SECS,
+            })
+        );
+
+        let result = handler
+            .execute(BotCommand::Fast(Some(FAST_MAX_WINDOW_SECS + 1)))
+            .await;
+        assert!(!result.success);
+        assert!(result.burst.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_reads_and_persists_interval() {
+        let limiter = Arc::new(RateLimiter::from_secs(60));
+        let handler = test_handler(DescriptionConfig::example(), "rate_limit")
+            .with_rate_limiter(Arc::clone(&limiter));
+
+        let result = handler.execute(BotCommand::RateLimit(None)).await;
+        assert!(result.success);
+        assert_eq!(result.data.as_ref().unwrap()["min_interval_secs"], 60);
+        assert_eq!(result.data.as_ref().unwrap()["wait_secs"], 0);
+
+        let result = handler.execute(BotCommand::RateLimit(Some(120))).await;
+        assert!(result.success);
+        assert!(!result.message.contains("flood wait"), "{}", result.message);
+        assert_eq!(limiter.min_interval().await.as_secs(), 120);
+
+        let saved = PersistentState::load(handler.state_store.path().unwrap());
+        assert_eq!(saved.rate_limit_secs, Some(120));
+
+        // Below the configured floor is allowed, with a warning
+        let result = handler.execute(BotCommand::RateLimit(Some(2))).await;
+        assert!(result.success);
+        assert!(result.message.contains("flood wait"), "{}", result.message);
+
+        // Without a Telegram connection there is no limiter to show
+        let result = test_handler(DescriptionConfig::example(), "rate_limit_none")
+            .execute(BotCommand::RateLimit(None))
+            .await;
+        assert!(!result.success);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_config_get_and_set_min_interval() {
+        let limiter = Arc::new(RateLimiter::from_secs(5));
+        let settings_path = temp_path("config_settings.json");
+        let handler = test_handler(DescriptionConfig::example(), "config_interval")
+            .with_rate_limiter(Arc::clone(&limiter))
+            .with_settings_path(&settings_path);
+
+        let config_get = |key: &str| BotCommand::Config(Some(key.to_owned()));
+        let config_set = |key: &str, value: &str| BotCommand::ConfigSet {
+            key: key.to_owned(),
+            value: value.to_owned(),
+        };
+
+        let result = handler.execute(config_get("min-interval")).await;
+        assert_eq!(result.message, "min-interval = 5");
+
+        let result = handler.execute(config_set("min-interval", "30")).await;
+        assert!(result.success, "{}", result.message);
+        assert_eq!(handler.settings().min_update_interval_secs, 30);
+        assert_eq!(limiter.min_interval().await.as_secs(), 30);
+        let result = handler.execute(config_get("min_interval")).await;
+        assert_eq!(result.data.unwrap()["min-interval"], "30");
+
+        // Saved for the next start
+        let mut restarted = BotSettings::default();
+        assert!(
+            SettingsOverrides::load(&settings_path)
+                .unwrap()
+                .apply(&mut restarted)
+                .is_empty()
+        );
+        assert_eq!(restarted.min_update_interval_secs, 30);
+
+        // Out of range values leave the setting alone
+        let result = handler.execute(config_set("min-interval", "0")).await;
+        assert!(!result.success);
+        assert!(result.message.contains("1 to 3600"), "{}", result.message);
+        assert_eq!(handler.settings().min_update_interval_secs, 30);
+
+        let _ = std::fs::remove_file(&settings_path);
+    }
+
+    #[tokio::test]
+    async fn test_config_set_overlong_policy_and_unknown_key() {
+        let handler = test_handler(DescriptionConfig::example(), "config_overlong");
+        let long = "x".repeat(100);
+
+        let set = |text: &str| BotCommand::Set {
+            text: text.to_owned(),
+            duration_secs: None,
+        };
+        assert!(!handler.execute(set(&long)).await.success);
+
+        let result = handler
+            .execute(BotCommand::ConfigSet {
+                key: "on-overlong".to_owned(),
+                value: "truncate".to_owned(),
+            })
+            .await;
+        assert_eq!(result.message, "✓ on-overlong set to truncate (was reject)");
+        // Takes effect right away
+        assert!(handler.execute(set(&long)).await.success);
+
+        let result = handler
+            .execute(BotCommand::ConfigSet {
+                key: "rotation-mode".to_owned(),
+                value: "random".to_owned(),
+            })
+            .await;
+        assert!(!result.success);
+        assert!(result.message.contains("Unknown setting"));
+
+        let result = handler.execute(BotCommand::Config(None)).await;
+        assert_eq!(result.message.lines().count(), TUNABLE_KEYS.len());
+        assert!(result.message.contains("on-overlong = truncate"));
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_last_error_reports_and_clears() {
+        let handler = test_handler(DescriptionConfig::example(), "last_error");
+        let result = handler.execute(BotCommand::LastError).await;
+        assert!(result.success);
+        assert_eq!(result.message, "✓ No recent errors.");
+
+        handler
+            .scheduler_state
+            .write()
+            .await
+            .record_error("Flood wait required: 420 seconds".to_owned());
+        let result = handler.execute(BotCommand::LastError).await;
+        assert!(result.message.contains("Flood wait required: 420 seconds"));
+        assert_eq!(
+            result.data.unwrap()["message"],
+            "Flood wait required: 420 seconds"
+        );
+
+        handler.scheduler_state.write().await.record_success();
+        let result = handler.execute(BotCommand::LastError).await;
+        assert_eq!(result.message, "✓ No recent errors.");
+    }
+
+    #[tokio::test]
+    async fn test_save_writes_state_to_disk() {
+        let handler = test_handler(DescriptionConfig::example(), "save");
+        {
+            let mut state = handler.scheduler_state.write().await;
+            state.current_index = 2;
+            state.pause(PauseMode::Auto);
+            state.set_deadline(600);
+        }
+
+        let result = handler.execute(BotCommand::Save).await;
+        assert!(result.success, "{}", result.message);
+        let path = handler.state_store.path().unwrap();
+        assert!(
+            result.message.contains(&path.display().to_string()),
+            "{}",
+            result.message
+        );
+        assert!(result.message.contains("Paused: yes (auto)"));
+
+        let on_disk = PersistentState::try_load(&path).unwrap();
+        let in_memory = handler.scheduler_state.read().await.to_persistent();
+        assert_eq!(
+            serde_json::to_value(&on_disk).unwrap(),
+            serde_json::to_value(&in_memory).unwrap()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extend_pushes_deadline_out() {
+        let handler = test_handler(DescriptionConfig::example(), "extend");
+
+        // Nothing shown yet: no deadline to extend
+        let result = handler.execute(BotCommand::Extend(600)).await;
+        assert!(!result.success);
+        assert!(!handler.scheduler_state.read().await.has_deadline());
+
+        handler.scheduler_state.write().await.set_deadline(600);
+        let result = handler.execute(BotCommand::Extend(3600)).await;
+        assert!(result.success, "{}", result.message);
+        let state = handler.scheduler_state.read().await;
+        let remaining = state.time_remaining().unwrap().as_secs();
+        assert!((4195..=4200).contains(&remaining), "{remaining}");
+        assert_eq!(state.current_index, 0);
+        drop(state);
+        let saved = PersistentState::load(handler.state_store.path().unwrap());
+        assert_eq!(
+            saved.expires_at_unix,
+            handler
+                .scheduler_state
+                .read()
+                .await
+                .to_persistent()
+                .expires_at_unix
+        );
+
+        handler.scheduler_state.write().await.is_paused = true;
+        assert!(!handler.execute(BotCommand::Extend(60)).await.success);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_relocate_config_saves_to_new_path() {
+        let handler = test_handler(DescriptionConfig::example(), "relocate_config");
+        let old_path = PathBuf::from(handler.config_path());
+        let new_path = PathBuf::from(temp_path("relocated_config.json"));
+        let _ = std::fs::remove_file(&new_path);
+        handler.config.read().await.save_to_file(&old_path).unwrap();
+
+        let result = handler
+            .execute(BotCommand::Relocate {
+                what: RelocateTarget::Config,
+                new_path: new_path.clone(),
+                remove_old: true,
+            })
+            .await;
+        assert!(result.success, "{}", result.message);
+        assert!(!old_path.exists());
+
+        let result = handler
+            .execute(BotCommand::Duration(DurationArgs {
+                id: "morning".to_owned(),
+                duration_secs: 120,
+            }))
+            .await;
+        assert!(result.success, "{}", result.message);
+        handler.flush_config().await.unwrap();
+        assert!(!old_path.exists());
+        let saved = DescriptionConfig::load_from_file(&new_path).unwrap();
+        let morning = saved.descriptions.iter().find(|d| d.id == "morning");
+        assert_eq!(morning.unwrap().duration_secs, 120);
+
+        // An existing file is never replaced
+        let result = handler
+            .execute(BotCommand::Relocate {
+                what: RelocateTarget::Config,
+                new_path: new_path.clone(),
+                remove_old: false,
+            })
+            .await;
+        assert!(!result.success);
+
+        let _ = std::fs::remove_file(&new_path);
+        let _ = std::fs::remove_file(backup_path(&new_path));
+    }
+
+    #[tokio::test]
+    async fn test_relocate_state_keeps_old_file_by_default() {
+        let handler = test_handler(DescriptionConfig::example(), "relocate_state");
+        let old_path = handler.state_store.path().unwrap();
+        let new_path = PathBuf::from(temp_path("relocated_state.json"));
+        let _ = std::fs::remove_file(&new_path);
+        {
+            let mut state = handler.scheduler_state.write().await;
+            state.current_index = 2;
+            state
+                .save(&*handler.state_store, &BotSettings::default())
+                .unwrap();
+        }
+
+        let result = handler
+            .execute(BotCommand::Relocate {
+                what: RelocateTarget::State,
+                new_path: new_path.clone(),
+                remove_old: false,
+            })
+            .await;
+        assert!(result.success, "{}", result.message);
+        assert!(old_path.exists());
+        assert_eq!(handler.state_store.path(), Some(new_path.clone()));
+        assert_eq!(PersistentState::load(&new_path).current_index, 2);
+
+        let result = handler.execute(BotCommand::Pause(PauseMode::All)).await;
+        assert!(result.success, "{}", result.message);
+        assert!(PersistentState::load(&new_path).is_paused);
+        assert!(!PersistentState::load(&old_path).is_paused);
+
+        let _ = std::fs::remove_file(&old_path);
+        let _ = std::fs::remove_file(&new_path);
+    }
+
+    #[tokio::test]
+    async fn test_relocate_resolves_against_config_dir() {
+        let dir = PathBuf::from(temp_path("relocate_dir"));
+        std::fs::create_dir_all(dir.join("old")).unwrap();
+        let old_path = dir.join("old").join("descriptions.json");
+        let handler = CommandHandler::new(
+            vec!["/description_bot".to_owned()],
+            Arc::new(RwLock::new(SchedulerState::new())),
+            Arc::new(RwLock::new(DescriptionConfig::example())),
+            old_path.to_string_lossy().into_owned(),
+            state_store(temp_path("relocate_dir_state.json"), true),
+        )
+        .with_settings_path(dir.join("settings.json"));
+        handler.config.read().await.save_to_file(&old_path).unwrap();
+        std::fs::write(changelog_path(&old_path), "log\n").unwrap();
+        save_snapshot(
+            &DescriptionConfig::example(),
+            &snapshots_dir(&old_path),
+            "work",
+        )
+        .unwrap();
+
+        let result = handler
+            .execute(BotCommand::Relocate {
+                what: RelocateTarget::Config,
+                new_path: PathBuf::from("bios.json"),
+                remove_old: false,
+            })
+            .await;
+        assert!(result.success, "{}", result.message);
+        assert!(result.message.contains("DESCRIPTIONS_PATH"));
+        let new_path = dir.join("bios.json");
+        assert_eq!(handler.config_path(), new_path.to_string_lossy());
+        assert!(old_path.exists());
+        assert!(changelog_path(&new_path).exists());
+        assert!(snapshots_dir(&new_path).join("work.json").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(temp_path("relocate_dir_state.json"));
+    }
+
+    #[tokio::test]
+    async fn test_set_uses_configured_custom_duration() {
+        let settings = BotSettings {
+            custom_duration_secs: 900,
+            ..BotSettings::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "custom_duration").with_settings(settings);
+        let config = DescriptionConfig::example();
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        let set = |text: &str, duration_secs| BotCommand::Set {
+            text: text.to_owned(),
+            duration_secs,
+        };
+        handler.execute(set("Lunch", None)).await;
+        let state = handler.scheduler_state.read().await.clone();
+        assert_eq!(
+            decide_next(&state, &config, now).unwrap().duration_secs,
+            900
+        );
+
+        // A per-command duration wins
+        handler.execute(set("Meeting", Some(1800))).await;
+        let state = handler.scheduler_state.read().await.clone();
+        assert_eq!(
+            decide_next(&state, &config, now).unwrap().duration_secs,
+            1800
+        );
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_queue_text_and_id() {
+        let handler = test_handler(DescriptionConfig::example(), "queue");
+
+        let result = handler
+            .execute(BotCommand::Queue("Back at 3".to_owned()))
+            .await;
+        assert!(result.success);
+        assert!(!result.trigger_update);
+        let result = handler
+            .execute(BotCommand::Queue("working".to_owned()))
+            .await;
+        assert!(result.message.contains("position 2"));
+
+        let state = handler.scheduler_state.read().await;
+        let texts: Vec<&str> = state.queue.iter().map(|q| q.text.as_str()).collect();
+        assert_eq!(texts, vec!["Back at 3", "💻 Currently working..."]);
+        assert!(
+            state
+                .queue
+                .iter()
+                .all(|q| q.duration_secs == handler.settings().custom_duration_secs)
+        );
+        drop(state);
+
+        let result = handler
+            .execute(BotCommand::Queue("a\u{200B}b".to_owned()))
+            .await;
+        assert!(!result.success);
+        assert_eq!(handler.scheduler_state.read().await.queue.len(), 2);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    /// A bio source returning a fixed bio.
+    struct FixedBio(&'static str);
+
+    impl BioSource for FixedBio {
+        fn current_bio(&self) -> BioFuture<'_> {
+            Box::pin(async move { Ok(self.0.to_owned()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_current_adds_live_bio() {
+        let handler = test_handler(DescriptionConfig::example(), "import_current")
+            .with_bio_source(Arc::new(FixedBio("Coffee first ☕")));
+
+        let result = handler
+            .execute(BotCommand::ImportCurrent("mine".to_owned()))
+            .await;
+        assert!(result.success, "{}", result.message);
+        {
+            let config = handler.config.read().await;
+            let added = config.get(3).unwrap();
+            assert_eq!(
+                (added.id.as_str(), added.text.as_str()),
+                ("mine", "Coffee first ☕")
+            );
+            assert_eq!(added.duration_secs, IMPORT_DURATION_SECS);
+        }
+
+        // The ID is taken now
+        let result = handler
+            .execute(BotCommand::ImportCurrent("mine".to_owned()))
+            .await;
+        assert!(!result.success);
+
+        let handler = test_handler(DescriptionConfig::example(), "import_empty")
+            .with_bio_source(Arc::new(FixedBio("  ")));
+        let result = handler
+            .execute(BotCommand::ImportCurrent("mine".to_owned()))
+            .await;
+        assert!(result.message.contains("no bio to import"));
+
+        let _ = std::fs::remove_file(temp_path("import_current_config.json"));
+    }
+
+    #[tokio::test]
+    async fn test_add_rejected_over_max_descriptions() {
+        let settings = BotSettings {
+            max_descriptions: Some(4),
+            ..BotSettings::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "max_descriptions").with_settings(settings);
+
+        let add = |id: &str| {
+            BotCommand::Add(AddArgs {
+                id: id.to_owned(),
+                duration_secs: 60,
+                text: "Extra".to_owned(),
+            })
+        };
+        assert!(handler.execute(add("fourth")).await.success);
+
+        let result = handler.execute(add("fifth")).await;
+        assert!(!result.success);
+        assert!(
+            result
+                .message
+                .starts_with("Too many descriptions: the limit is 4")
+        );
+
+        let result = handler
+            .execute(BotCommand::BulkAdd("a|60|A\nb|60|B".to_owned()))
+            .await;
+        assert!(!result.success);
+        assert_eq!(handler.config.read().await.len(), 4);
+
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_near_limit_text_succeeds_with_warning() {
+        let handler = test_handler(DescriptionConfig::example(), "near_limit");
+        assert_eq!(handler.settings().warn_threshold_pct, 90);
+
+        // 67 of 70 characters is about 95% of the free limit
+        let long = "x".repeat(67);
+        let short = "x".repeat(35);
+        let add = |id: &str, text: &str| {
+            BotCommand::Add(AddArgs {
+                id: id.to_owned(),
+                duration_secs: 60,
+                text: text.to_owned(),
+            })
+        };
+
+        let result = handler.execute(add("long", &long)).await;
+        assert!(result.success, "{}", result.message);
+        assert!(result.message.contains("⚠ Close to the limit: 67 of 70"));
+
+        let result = handler.execute(add("short", &short)).await;
+        assert!(result.success);
+        assert!(!result.message.contains('⚠'));
+
+        let edit = BotCommand::Edit(EditArgs {
+            id: "short".to_owned(),
+            text: long.clone(),
+        });
+        assert!(
+            handler
+                .execute(edit)
+                .await
+                .message
+                .contains("Close to the limit")
+        );
+
+        let set = |text: &str| BotCommand::Set {
+            text: text.to_owned(),
+            duration_secs: None,
+        };
+        assert!(
+            handler
+                .execute(set(&long))
+                .await
+                .message
+                .contains("Close to the limit")
+        );
+        assert!(!handler.execute(set(&short)).await.message.contains('⚠'));
+
+        let _ = std::fs::remove_file(handler.config_path());
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_template_add_appends_valid_description() {
+        let handler = test_handler(DescriptionConfig::example(), "template_add");
+
+        // "morning" already exists in the example config
+        let result = handler
+            .execute(BotCommand::TemplateAdd("morning".to_owned()))
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        let config = handler.config.read().await;
+        assert_eq!(config.len(), 4);
+        let added = config.get(3).unwrap();
+        assert_eq!(added.id, "morning_2");
+        assert_eq!(added.text, find_template("morning").unwrap().text);
+        assert!(config.validate().is_ok());
+
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_appends_all_lines() {
+        let handler = test_handler(DescriptionConfig::example(), "bulk_add");
+
+        let result = handler
+            .execute(BotCommand::BulkAdd(
+                "tea|600|Tea time 🍵\n\nlunch | 3600 | Lunch | back soon".to_owned(),
+            ))
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(handler.config_path()).unwrap();
+        assert_eq!(saved.len(), 5);
+        assert_eq!(saved.get(3).unwrap().id, "tea");
+        assert_eq!(saved.get(4).unwrap().text, "Lunch | back soon");
+        assert_eq!(saved.get(4).unwrap().duration_secs, 3600);
+
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_rapid_edits_are_saved_once() {
+        let settings = BotSettings {
+            config_save_debounce_ms: 50,
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "debounce").with_settings(settings);
+        let path = PathBuf::from(handler.config_path());
+
+        for secs in 1..=5 {
+            let result = handler
+                .execute(BotCommand::Duration(DurationArgs {
+                    id: "morning".to_owned(),
+                    duration_secs: secs * 60,
+                }))
+                .await;
+            assert!(result.success, "{}", result.message);
+        }
+        assert!(!path.exists(), "saved before the debounce interval");
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let saved = DescriptionConfig::load_from_file(&path).unwrap();
+        assert_eq!(saved.get(0).unwrap().duration_secs, 300);
+
+        // No further writes are scheduled
+        std::fs::remove_file(&path).unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(!path.exists());
+
+        // Flushing writes pending changes immediately
+        handler
+            .execute(BotCommand::Edit(EditArgs {
+                id: "morning".to_owned(),
+                text: "Flushed".to_owned(),
+            }))
+            .await;
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(&path).unwrap();
+        assert_eq!(saved.get(0).unwrap().text, "Flushed");
+
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_debounced_save_is_reported() {
+        let settings = BotSettings {
+            config_save_debounce_ms: 20,
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "debounce_fail").with_settings(settings);
+        let dir = PathBuf::from(temp_path("debounce_fail_dir"));
+        *handler.config_path.write().unwrap() =
+            dir.join("descriptions.json").to_string_lossy().into_owned();
+        let edit = |secs| {
+            BotCommand::Duration(DurationArgs {
+                id: "morning".to_owned(),
+                duration_secs: secs,
+            })
+        };
+
+        // The deferred write fails, the next edit reports it
+        assert!(handler.execute(edit(60)).await.success);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let result = handler.execute(edit(120)).await;
+        assert!(!result.success, "{}", result.message);
+
+        // Once the file can be written, flushing saves every change
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(handler.execute(edit(180)).await.success);
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(dir.join("descriptions.json")).unwrap();
+        assert_eq!(saved.get(0).unwrap().duration_secs, 180);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_during_quiet_hours_is_deferred() {
+        let now = chrono::Utc::now().time();
+        let settings = BotSettings {
+            quiet_hours: Some(TimeWindow::new(
+                now - chrono::Duration::hours(1),
+                now + chrono::Duration::hours(1),
+            )),
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "quiet_skip").with_settings(settings);
+
+        let result = handler.execute(BotCommand::Skip).await;
+        assert!(result.success && result.trigger_update);
+        assert!(result.message.contains("Quiet hours"), "{}", result.message);
+
+        let result = handler.execute(BotCommand::Status).await;
+        assert!(!result.message.contains("the bio is updated when"));
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_status_data() {
+        let handler = test_handler(DescriptionConfig::example(), "status_data");
+
+        let data = handler.execute(BotCommand::Status).await.data.unwrap();
+        assert_eq!(data["index"], 0);
+        assert_eq!(data["id"], "morning");
+        assert_eq!(data["paused"], false);
+        assert!(data["remaining_secs"].is_null()); // No deadline yet
+
+        {
+            let mut state = handler.scheduler_state.write().await;
+            state.current_index = 1;
+            state.is_paused = true;
+            state.set_deadline(600);
+        }
+
+        let data = handler.execute(BotCommand::Status).await.data.unwrap();
+        assert_eq!(data["index"], 1);
+        assert_eq!(data["paused"], true);
+        assert!(data["remaining_secs"].as_u64().unwrap() <= 600);
+    }
+
+    #[tokio::test]
+    async fn test_ids_lists_every_id_once_in_order() {
+        let handler = test_handler(DescriptionConfig::example(), "ids");
+        handler.scheduler_state.write().await.current_index = 1;
+
+        let result = handler.execute(BotCommand::Ids).await;
+        assert!(result.success);
+        assert_eq!(result.message, "morning *working evening");
+
+        let ids: Vec<&str> = result
+            .message
+            .split(' ')
+            .map(|id| id.trim_start_matches('*'))
+            .collect();
+        assert_eq!(ids, ["morning", "working", "evening"]);
+        assert_eq!(result.data.unwrap()["ids"][2], "evening");
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_mark_current_only_on_its_page() {
+        let settings = BotSettings {
+            list_page_size: 2,
+            ..BotSettings::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "list_pages").with_settings(settings);
+        handler.scheduler_state.write().await.current_index = 2;
+
+        let result = handler.execute(BotCommand::List(None)).await;
+        assert!(
+            result
+                .message
+                .starts_with("Configured descriptions (page 1/2):")
+        );
+        assert!(!result.message.contains('→'));
+
+        let result = handler.execute(BotCommand::List(Some(2))).await;
+        assert!(result.message.contains("→ [evening]"), "{}", result.message);
+        assert!(!result.message.contains("[morning]"));
+
+        let result = handler.execute(BotCommand::List(Some(3))).await;
+        assert!(!result.success);
+        assert_eq!(result.message, "No such page. Pages: 1-2.");
+    }
+
+    #[tokio::test]
+    async fn test_list_and_stats_data() {
+        let handler = test_handler(DescriptionConfig::example(), "list_data");
+        handler
+            .scheduler_state
+            .write()
+            .await
+            .record_shown(Some("working"));
+
+        let data = handler.execute(BotCommand::List(None)).await.data.unwrap();
+        assert_eq!(data["descriptions"].as_array().unwrap().len(), 3);
+        assert_eq!(data["descriptions"][1]["id"], "working");
+        assert_eq!(data["descriptions"][1]["duration_secs"], 7200);
+
+        let data = handler.execute(BotCommand::Stats).await.data.unwrap();
+        assert_eq!(data["stats"][0]["id"], "working");
+        assert_eq!(data["stats"][0]["shown_count"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_reset_zeroes_counters() {
+        let handler = test_handler(DescriptionConfig::example(), "stats_reset");
+        {
+            let mut state = handler.scheduler_state.write().await;
+            state.record_shown(Some("morning"));
+            state.record_shown(Some("working"));
+            state.record_shown(Some("morning"));
+        }
+
+        let result = handler.execute(BotCommand::Stats).await;
+        assert!(
+            result.message.contains("[morning] shown 2×"),
+            "{}",
+            result.message
+        );
+
+        let result = handler
+            .execute(BotCommand::StatsReset { confirmed: false })
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        let state = handler.scheduler_state.read().await;
+        assert!(
+            state
+                .stats
+                .iter()
+                .all(|(_, s)| s.shown_count == 0 && s.total_secs == 0)
+        );
+        assert!(state.stats.get("working").is_none());
+
+        let saved = PersistentState::load(handler.state_store.path().unwrap());
+        assert_eq!(saved.stats, state.stats);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_stats_reset_requires_confirmation() {
+        let settings = BotSettings {
+            confirm_destructive: true,
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "stats_confirm").with_settings(settings);
+        handler
+            .scheduler_state
+            .write()
+            .await
+            .record_shown(Some("morning"));
+
+        let result = handler
+            .execute(BotCommand::StatsReset { confirmed: false })
+            .await;
+        assert!(!result.success);
+        assert!(!handler.scheduler_state.read().await.stats.is_empty());
+
+        let result = handler
+            .execute(BotCommand::StatsReset { confirmed: true })
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_command_cooldown() {
+        let settings = BotSettings {
+            command_cooldown_secs: 5,
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "cooldown").with_settings(settings);
+        let start = Instant::now();
+
+        assert_eq!(handler.check_cooldown("skip", start).await, None);
+        handler.last_executed.lock().await.insert("skip", start);
+
+        // Same command inside the window is rejected, others are not
+        let inside = start + Duration::from_secs(2);
+        assert_eq!(handler.check_cooldown("skip", inside).await, Some(3));
+        assert_eq!(handler.check_cooldown("status", inside).await, None);
+
+        // After the window it passes again
+        let after = start + Duration::from_secs(5);
+        assert_eq!(handler.check_cooldown("skip", after).await, None);
+
+        // Through execute: the second identical command is rejected
+        let result = handler.execute(BotCommand::List(None)).await;
+        assert!(result.success);
+        let result = handler.execute(BotCommand::List(None)).await;
+        assert!(!result.success);
+        assert!(
+            result.message.starts_with("Slow down"),
+            "{}",
+            result.message
+        );
+
+        // A failed run doesn't start the cooldown
+        for _ in 0..2 {
+            let result = handler
+                .execute(BotCommand::View("missing".to_owned()))
+                .await;
+            assert!(!result.message.starts_with("Slow down"));
+        }
+
+        // The control socket is not throttled
+        let result = handler.execute_unthrottled(BotCommand::List(None)).await;
+        assert!(result.success, "{}", result.message);
+    }
+
+    #[test]
+    fn test_resolve_target_id_then_position() {
+        let config = DescriptionConfig::example();
+        assert_eq!(resolve_target(&config, "working"), Some(1));
+        assert_eq!(resolve_target(&config, "2"), Some(1));
+        assert_eq!(resolve_target(&config, "0"), None);
+        assert_eq!(resolve_target(&config, "4"), None);
+        assert_eq!(resolve_target(&config, "missing"), None);
+
+        // An ID that looks like a number wins over the position
+        let mut config = DescriptionConfig::example();
+        config.descriptions[2].id = "1".to_owned();
+        assert_eq!(resolve_target(&config, "1"), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_replace_all_changes_every_match() {
+        let handler = test_handler(DescriptionConfig::example(), "replace_all");
+
+        let result = handler
+            .execute(BotCommand::ReplaceAll {
+                from: "ing".to_owned(),
+                to: "ING".to_owned(),
+            })
+            .await;
+        assert!(result.success, "{}", result.message);
+        assert!(
+            result.message.contains("3 description(s)"),
+            "{}",
+            result.message
+        );
+
+        let config = handler.config.read().await;
+        assert_eq!(config.descriptions[1].text, "💻 Currently workING...");
+        assert_eq!(config.descriptions[2].text, "🌙 RelaxING in the evenING");
+        drop(config);
+
+        handler.flush_config().await.unwrap();
+        let saved = DescriptionConfig::load_from_file(handler.config_path()).unwrap();
+        assert_eq!(saved.descriptions[1].text, "💻 Currently workING...");
+
+        let result = handler
+            .execute(BotCommand::ReplaceAll {
+                from: "missing".to_owned(),
+                to: "x".to_owned(),
+            })
+            .await;
+        assert!(result.success);
+        assert!(result.message.starts_with("No description"));
+
+        let _ = std::fs::remove_file(handler.config_path());
+    }
+
+    #[tokio::test]
+    async fn test_replace_all_rolls_back_when_one_becomes_too_long() {
+        let handler = test_handler(DescriptionConfig::example(), "replace_all_rollback");
+        let before = handler.config.read().await.descriptions.clone();
+
+        // Only the evening text contains "evening"; "ing" hits every one
+        let result = handler
+            .execute(BotCommand::ReplaceAll {
+                from: "evening".to_owned(),
+                to: "x".repeat(100),
+            })
+            .await;
+        assert!(!result.success);
+        assert!(result.message.contains("[evening]"), "{}", result.message);
+
+        let result = handler
+            .execute(BotCommand::ReplaceAll {
+                from: "ing".to_owned(),
+                to: "y".repeat(30),
+            })
+            .await;
+        assert!(!result.success);
+        assert_eq!(handler.config.read().await.descriptions, before);
+    }
+
+    #[tokio::test]
+    async fn test_edit_and_delete_by_position() {
+        let by_position = test_handler(DescriptionConfig::example(), "target_position");
+        let by_id = test_handler(DescriptionConfig::example(), "target_id");
+
+        for (handler, target) in [(&by_position, "2"), (&by_id, "working")] {
+            let result = handler
+                .execute(BotCommand::Edit(EditArgs {
+                    id: target.to_owned(),
+                    text: "Edited".to_owned(),
+                }))
+                .await;
+            assert!(result.success, "{}", result.message);
+            assert!(result.message.contains("[working]"), "{}", result.message);
+        }
+        assert_eq!(
+            by_position.config.read().await.active_descriptions(),
+            by_id.config.read().await.active_descriptions()
+        );
+
+        for (handler, target) in [(&by_position, "2"), (&by_id, "working")] {
+            let result = handler.execute(BotCommand::Delete(target.to_owned())).await;
+            assert!(result.success, "{}", result.message);
+            assert!(result.message.contains("[working]"), "{}", result.message);
+        }
+        assert_eq!(
+            by_position.config.read().await.active_descriptions(),
+            by_id.config.read().await.active_descriptions()
+        );
+        assert_eq!(by_position.config.read().await.len(), 2);
+
+        for handler in [&by_position, &by_id] {
+            let _ = std::fs::remove_file(handler.config_path());
+            let _ = std::fs::remove_file(backup_path(handler.config_path()));
+            let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_invalid_line_adds_nothing() {
+        let handler = test_handler(DescriptionConfig::example(), "bulk_add_invalid");
+
+        let result = handler
+            .execute(BotCommand::BulkAdd(
+                "tea|600|Tea time\nlunch|0|Lunch\nnap|900|Nap".to_owned(),
+            ))
+            .await;
+        assert!(!result.success);
+        assert!(result.message.starts_with("Line 2:"), "{}", result.message);
+
+        assert_eq!(handler.config.read().await.len(), 3);
+        assert!(!std::path::Path::new(&handler.config_path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_rejects_duplicate_ids() {
+        let handler = test_handler(DescriptionConfig::example(), "bulk_add_dup");
+
+        // Duplicate within the batch
+        let result = handler
+            .execute(BotCommand::BulkAdd(
+                "tea|600|Tea\ntea|900|More tea".to_owned(),
+            ))
+            .await;
+        assert!(!result.success);
+        assert!(
+            result.message.contains("appears twice"),
+            "{}",
+            result.message
+        );
+
+        // Duplicate of an existing description
+        let result = handler
+            .execute(BotCommand::BulkAdd(
+                "tea|600|Tea\nmorning|900|Hi".to_owned(),
+            ))
+            .await;
+        assert!(!result.success);
+        assert!(
+            result.message.contains("already exists"),
+            "{}",
+            result.message
+        );
+
+        assert_eq!(handler.config.read().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_overlong_text_accepted_when_truncating() {
+        let long_text = "a".repeat(100);
+
+        let handler = test_handler(DescriptionConfig::example(), "overlong_reject");
+        let result = handler
+            .execute(BotCommand::Set {
+                text: long_text.clone(),
+                duration_secs: None,
+            })
+            .await;
+        assert!(!result.success);
+
+        let settings = BotSettings {
+            on_overlong: OverlongPolicy::Truncate,
+            ..Default::default()
+        };
+        let handler =
+            test_handler(DescriptionConfig::example(), "overlong_truncate").with_settings(settings);
+        let result = handler
+            .execute(BotCommand::Set {
+                text: long_text,
+                duration_secs: None,
+            })
+            .await;
+        assert!(result.success, "{}", result.message);
+
+        let _ = std::fs::remove_file(handler.state_store.path().unwrap());
+    }
 }
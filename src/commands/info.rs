@@ -0,0 +1,189 @@
+//! Build and runtime details shown by the `info` command.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::handler::format_duration_localized;
+use crate::config::{DescriptionConfig, Locale};
+use crate::scheduler::{PauseMode, SchedulerState};
+
+/// Project home shown by `info`.
+const REPOSITORY: &str = "https://github.com/user/description_user_bot";
+
+/// How the premium status behind the length limit was determined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PremiumSource {
+    /// Taken from the descriptions file (`auto_detect_premium` off).
+    #[default]
+    Configured,
+    /// Detected from the account at startup.
+    Detected,
+    /// Detection failed and the status was guessed from the lengths.
+    Assumed,
+}
+
+impl PremiumSource {
+    /// Returns how the status came about, for display.
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Configured => "from the descriptions file",
+            Self::Detected => "auto-detected",
+            Self::Assumed => "assumed, detection failed",
+        }
+    }
+}
+
+/// Details for bug reports, built from the shared state.
+///
+/// Holds no secrets: no API credentials, session or account details.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuntimeInfo {
+    /// Crate version.
+    pub version: &'static str,
+
+    /// Architecture and OS the binary runs on (e.g. `x86_64-linux`).
+    pub target: String,
+
+    /// Seconds since the bot started.
+    pub uptime_secs: u64,
+
+    /// Successful bio updates since the bot started.
+    pub session_updates: u64,
+
+    /// Rotation mode: running or paused, with the tag and profile in use.
+    pub rotation: String,
+
+    /// Whether the premium length limit applies.
+    pub is_premium: bool,
+
+    /// How the premium status was determined.
+    pub premium_source: PremiumSource,
+
+    /// Number of descriptions in the active profile.
+    pub descriptions: usize,
+}
+
+impl RuntimeInfo {
+    /// Collects the details from the scheduler state and configuration.
+    #[must_use]
+    pub fn capture(
+        state: &SchedulerState,
+        config: &DescriptionConfig,
+        uptime: Duration,
+        premium_source: PremiumSource,
+    ) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            uptime_secs: uptime.as_secs(),
+            session_updates: state.session_updates(),
+            rotation: rotation_mode(state, config),
+            is_premium: config.is_premium,
+            premium_source,
+            descriptions: config.len(),
+        }
+    }
+
+    /// Formats the details for chat, with durations in `locale`.
+    #[must_use]
+    pub fn format(&self, locale: Locale) -> String {
+        format!(
+            "Description User Bot v{} ({})\n\
+             Uptime: {}\n\
+             Bio updates this session: {}\n\
+             Rotation: {}\n\
+             Premium: {} ({})\n\
+             Descriptions: {}\n\
+             Repository: {REPOSITORY}",
+            self.version,
+            self.target,
+            format_duration_localized(self.uptime_secs, locale),
+            self.session_updates,
+            self.rotation,
+            if self.is_premium { "yes" } else { "no" },
+            self.premium_source.label(),
+            self.descriptions,
+        )
+    }
+}
+
+/// Describes whether the rotation runs, and the tag and profile it uses.
+fn rotation_mode(state: &SchedulerState, config: &DescriptionConfig) -> String {
+    let mut mode = if state.is_paused_by_failures() {
+        "paused after failures"
+    } else if state.is_paused {
+        match state.pause_mode {
+            PauseMode::All => "paused",
+            PauseMode::Auto => "paused (auto)",
+        }
+    } else {
+        "running"
+    }
+    .to_owned();
+
+    if let Some(tag) = &state.active_tag {
+        let _ = write!(mode, ", tag '{tag}'");
+    }
+    if let Some(profile) = &config.active_profile {
+        let _ = write!(mode, ", profile '{profile}'");
+    }
+    mode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_runtime_info() {
+        let info = RuntimeInfo {
+            version: "1.2.3",
+            target: "x86_64-linux".to_owned(),
+            uptime_secs: 3 * 3600 + 120,
+            session_updates: 14,
+            rotation: "running, tag 'work'".to_owned(),
+            is_premium: false,
+            premium_source: PremiumSource::Detected,
+            descriptions: 5,
+        };
+
+        assert_eq!(
+            info.format(Locale::En),
+            "Description User Bot v1.2.3 (x86_64-linux)\n\
+             Uptime: 3h 2m\n\
+             Bio updates this session: 14\n\
+             Rotation: running, tag 'work'\n\
+             Premium: no (auto-detected)\n\
+             Descriptions: 5\n\
+             Repository: https://github.com/user/description_user_bot"
+        );
+    }
+
+    #[test]
+    fn test_capture_from_state() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.record_success();
+        state.record_success();
+        state.pause(PauseMode::Auto);
+        state.active_tag = Some("home".to_owned());
+
+        let info = RuntimeInfo::capture(
+            &state,
+            &config,
+            Duration::from_secs(90),
+            PremiumSource::Assumed,
+        );
+        assert_eq!(info.session_updates, 2);
+        assert_eq!(info.rotation, "paused (auto), tag 'home'");
+        assert_eq!(info.uptime_secs, 90);
+        assert_eq!(info.descriptions, 3);
+        assert!(
+            info.format(Locale::En)
+                .contains("(assumed, detection failed)")
+        );
+    }
+}
@@ -3,8 +3,20 @@
 //! Processes user commands sent to the bot via Telegram messages.
 //! Commands use the `/description_bot` prefix.
 
+mod control;
 mod handler;
+mod info;
+mod paginate;
+mod split;
+mod tracker;
 mod types;
 
-pub use handler::CommandHandler;
-pub use types::{BotCommand, CommandResult};
+#[cfg(unix)]
+pub use control::serve as serve_control_socket;
+pub use control::{ControlRequest, apply_command_effects, handle_request};
+pub use handler::{BioFuture, BioSource, CommandHandler};
+pub use info::{PremiumSource, RuntimeInfo};
+pub use paginate::{Page, paginate};
+pub use split::split_message;
+pub use tracker::MessageTracker;
+pub use types::{BotCommand, Burst, CommandResult, NameChange, Presence, RelocateTarget};
@@ -0,0 +1,89 @@
+//! Pagination of long listings.
+
+/// One page of a listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page<'a, T> {
+    /// Items on this page.
+    pub items: &'a [T],
+
+    /// Index of the first item on this page in the whole listing.
+    pub offset: usize,
+
+    /// 1-based page number.
+    pub number: usize,
+
+    /// Total number of pages (at least 1).
+    pub total_pages: usize,
+}
+
+/// Returns the 1-based `page` of `items` with `size` items per page.
+///
+/// A `size` of 0 puts everything on one page. Returns `None` if the page
+/// does not exist.
+#[must_use]
+pub fn paginate<T>(items: &[T], page: usize, size: usize) -> Option<Page<'_, T>> {
+    let size = if size == 0 { items.len().max(1) } else { size };
+    let total_pages = items.len().div_ceil(size).max(1);
+
+    if page == 0 || page > total_pages {
+        return None;
+    }
+
+    let offset = (page - 1) * size;
+    let end = (offset + size).min(items.len());
+    Some(Page {
+        items: &items[offset..end],
+        offset,
+        number: page,
+        total_pages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_bounds() {
+        let items: Vec<u32> = (0..10).collect();
+
+        let first = paginate(&items, 1, 4).unwrap();
+        assert_eq!(first.items, &[0, 1, 2, 3]);
+        assert_eq!((first.offset, first.total_pages), (0, 3));
+
+        let second = paginate(&items, 2, 4).unwrap();
+        assert_eq!(second.items, &[4, 5, 6, 7]);
+        assert_eq!(second.offset, 4);
+    }
+
+    #[test]
+    fn test_last_partial_page() {
+        let items: Vec<u32> = (0..10).collect();
+        let last = paginate(&items, 3, 4).unwrap();
+        assert_eq!(last.items, &[8, 9]);
+        assert_eq!(last.number, last.total_pages);
+
+        // Exact multiple: no empty trailing page
+        assert_eq!(paginate(&items, 2, 5).unwrap().total_pages, 2);
+    }
+
+    #[test]
+    fn test_out_of_range_pages() {
+        let items: Vec<u32> = (0..10).collect();
+        assert!(paginate(&items, 0, 4).is_none());
+        assert!(paginate(&items, 4, 4).is_none());
+
+        // Empty listing still has one (empty) page
+        let empty: [u32; 0] = [];
+        assert_eq!(paginate(&empty, 1, 4).unwrap().total_pages, 1);
+        assert!(paginate(&empty, 2, 4).is_none());
+    }
+
+    #[test]
+    fn test_zero_size_is_one_page() {
+        let items: Vec<u32> = (0..10).collect();
+        let page = paginate(&items, 1, 0).unwrap();
+        assert_eq!(page.items.len(), 10);
+        assert_eq!(page.total_pages, 1);
+    }
+}
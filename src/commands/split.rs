@@ -0,0 +1,107 @@
+//! Splitting of long replies into Telegram-sized messages.
+
+use crate::config::utf16_len;
+
+/// Splits text into chunks of at most `max_len` UTF-16 code units.
+///
+/// Splits on line boundaries first, then on spaces for lines that do not
+/// fit, and only cuts inside a word that is longer than a whole chunk.
+/// Characters are never broken. Line breaks and spaces at a split point
+/// are dropped.
+#[must_use]
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    let max_len = max_len.max(2); // Room for any single character
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in pieces(text, max_len) {
+        // Blank lines at a split point are dropped too
+        if current.is_empty() && piece.trim().is_empty() {
+            continue;
+        }
+
+        let fits = utf16_len(format!("{current}{piece}").trim_end()) <= max_len;
+        if !fits {
+            flush(&mut chunks, &mut current);
+        }
+        current.push_str(piece);
+    }
+    flush(&mut chunks, &mut current);
+
+    chunks
+}
+
+/// Breaks text into the smallest pieces that fit: lines, else words, else
+/// characters. Each piece keeps its trailing separator.
+fn pieces(text: &str, max_len: usize) -> Vec<&str> {
+    let mut result = Vec::new();
+    for line in text.split_inclusive('\n') {
+        if utf16_len(line.trim_end()) <= max_len {
+            result.push(line);
+            continue;
+        }
+        for word in line.split_inclusive(' ') {
+            if utf16_len(word.trim_end()) <= max_len {
+                result.push(word);
+                continue;
+            }
+            result.extend(word.char_indices().map(|(i, c)| &word[i..i + c.len_utf8()]));
+        }
+    }
+    result
+}
+
+/// Moves the current chunk into the result without trailing whitespace.
+fn flush(chunks: &mut Vec<String>, current: &mut String) {
+    let chunk = current.trim_end();
+    if !chunk.is_empty() {
+        chunks.push(chunk.to_owned());
+    }
+    current.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_one_chunk() {
+        assert_eq!(split_message("Status: ok", 4096), vec!["Status: ok"]);
+        assert!(split_message("", 4096).is_empty());
+    }
+
+    #[test]
+    fn test_splits_on_lines_into_minimum_chunks() {
+        // A list of 30 lines of 8 chars: three lines fit in 30 units
+        let lines: Vec<String> = (0..30).map(|i| format!("  [id{i:02}]")).collect();
+        let text = lines.join("\n");
+        let chunks = split_message(&text, 30);
+
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks.iter().all(|c| utf16_len(c) <= 30));
+        assert_eq!(chunks[0], "  [id00]\n  [id01]\n  [id02]");
+        assert_eq!(chunks.join("\n"), text);
+    }
+
+    #[test]
+    fn test_chunks_do_not_start_with_blank_lines() {
+        let chunks = split_message("Commands:\n\n  skip\n  status", 10);
+        assert_eq!(chunks, vec!["Commands:", "  skip", "  status"]);
+    }
+
+    #[test]
+    fn test_long_line_splits_on_words() {
+        let chunks = split_message("alpha beta gamma delta", 11);
+        assert_eq!(chunks, vec!["alpha beta", "gamma delta"]);
+    }
+
+    #[test]
+    fn test_never_breaks_characters() {
+        // Each emoji is 2 UTF-16 units
+        let chunks = split_message(&"😀".repeat(5), 4);
+        assert_eq!(chunks, vec!["😀😀", "😀😀", "😀"]);
+
+        let chunks = split_message("привет мир", 6);
+        assert_eq!(chunks, vec!["привет", "мир"]);
+    }
+}
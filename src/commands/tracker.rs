@@ -0,0 +1,147 @@
+//! Tracking of polled Saved Messages for command execution.
+//!
+//! New messages are handled once. An edited message (e.g. a fixed typo) is
+//! handled again, unless a command from the same message already ran
+//! within the de-duplication window.
+
+use std::collections::BTreeMap;
+
+use crate::telegram::SavedMessage;
+
+/// How many recent message IDs are remembered for edit detection.
+const MAX_TRACKED: usize = 50;
+
+/// What is known about a message that was already seen.
+#[derive(Debug, Clone, Copy)]
+struct Seen {
+    /// Edit date the message had when it was last looked at.
+    edit_date: Option<i32>,
+    /// Unix time a command from this message last ran.
+    executed_at: Option<u64>,
+}
+
+/// Decides which polled messages should be executed as commands.
+#[derive(Debug, Clone)]
+pub struct MessageTracker {
+    /// Highest message ID seen so far.
+    last_id: i32,
+    /// Recently seen messages by ID.
+    seen: BTreeMap<i32, Seen>,
+    /// Seconds during which a message's command is not executed again.
+    dedup_window_secs: u64,
+}
+
+impl MessageTracker {
+    /// Creates a tracker that ignores messages up to `last_id`.
+    #[must_use]
+    pub const fn new(last_id: i32, dedup_window_secs: u64) -> Self {
+        Self {
+            last_id,
+            seen: BTreeMap::new(),
+            dedup_window_secs,
+        }
+    }
+
+    /// Checks if a polled message should be handled as a command.
+    ///
+    /// Older messages seen for the first time only become the baseline for
+    /// later edits.
+    pub fn should_handle(&mut self, message: &SavedMessage, now: u64) -> bool {
+        if message.id > self.last_id {
+            self.last_id = message.id;
+            self.remember(message.id, message.edit_date);
+            return true;
+        }
+
+        let Some(seen) = self.seen.get_mut(&message.id) else {
+            self.remember(message.id, message.edit_date);
+            return false;
+        };
+
+        if seen.edit_date == message.edit_date {
+            return false;
+        }
+        seen.edit_date = message.edit_date;
+
+        // The same command shortly before: don't run it twice
+        seen.executed_at
+            .is_none_or(|at| now.saturating_sub(at) >= self.dedup_window_secs)
+    }
+
+    /// Records that a command from the message was executed at `now`.
+    pub fn mark_executed(&mut self, id: i32, now: u64) {
+        if let Some(seen) = self.seen.get_mut(&id) {
+            seen.executed_at = Some(now);
+        }
+    }
+
+    fn remember(&mut self, id: i32, edit_date: Option<i32>) {
+        self.seen.insert(
+            id,
+            Seen {
+                edit_date,
+                executed_at: None,
+            },
+        );
+
+        // Forget the oldest IDs
+        while self.seen.len() > MAX_TRACKED {
+            self.seen.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: i32, edit_date: Option<i32>) -> SavedMessage {
+        SavedMessage {
+            id,
+            text: "/description_bot skip".to_owned(),
+            edit_date,
+        }
+    }
+
+    #[test]
+    fn test_new_messages_handled_once() {
+        let mut tracker = MessageTracker::new(10, 30);
+
+        assert!(!tracker.should_handle(&message(9, None), 1_000)); // Before start
+        assert!(tracker.should_handle(&message(11, None), 1_000));
+        assert!(!tracker.should_handle(&message(11, None), 1_001));
+    }
+
+    #[test]
+    fn test_edit_of_unexecuted_message_is_handled() {
+        let mut tracker = MessageTracker::new(10, 30);
+
+        // A typo: not a command, nothing executed
+        assert!(tracker.should_handle(&message(11, None), 1_000));
+        assert!(tracker.should_handle(&message(11, Some(1_005)), 1_005));
+        assert!(!tracker.should_handle(&message(11, Some(1_005)), 1_006));
+    }
+
+    #[test]
+    fn test_edit_deduplicated_within_window() {
+        let mut tracker = MessageTracker::new(10, 30);
+
+        assert!(tracker.should_handle(&message(11, None), 1_000));
+        tracker.mark_executed(11, 1_000);
+
+        // Edited right after running: skipped
+        assert!(!tracker.should_handle(&message(11, Some(1_010)), 1_010));
+
+        // Edited again after the window: runs again
+        assert!(tracker.should_handle(&message(11, Some(1_040)), 1_040));
+    }
+
+    #[test]
+    fn test_edit_of_old_message_after_start() {
+        let mut tracker = MessageTracker::new(10, 30);
+
+        // Baseline on first sight, then the edit counts
+        assert!(!tracker.should_handle(&message(10, None), 1_000));
+        assert!(tracker.should_handle(&message(10, Some(1_100)), 1_100));
+    }
+}
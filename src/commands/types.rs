@@ -1,6 +1,11 @@
 //! Command types and definitions.
 
 use std::fmt;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::scheduler::PauseMode;
 
 /// Arguments for adding a new description.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,6 +15,39 @@ pub struct AddArgs {
     pub text: String,
 }
 
+impl AddArgs {
+    /// Parses one `bulk-add` line: `<id>|<duration_secs>|<text>`.
+    ///
+    /// The text may itself contain `|`.
+    pub fn parse_bulk_line(line: &str) -> Result<Self, String> {
+        let mut parts = line.splitn(3, '|');
+        let (Some(id), Some(duration), Some(text)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err("expected 'id|duration|text'".to_owned());
+        };
+
+        let id = id.trim();
+        let text = text.trim();
+        if id.is_empty() {
+            return Err("ID cannot be empty".to_owned());
+        }
+        if text.is_empty() {
+            return Err("text cannot be empty".to_owned());
+        }
+
+        let duration_secs = duration
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid duration '{}'", duration.trim()))?;
+
+        Ok(Self {
+            id: id.to_owned(),
+            duration_secs,
+            text: text.to_owned(),
+        })
+    }
+}
+
 /// Arguments for editing an existing description.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EditArgs {
@@ -24,6 +62,89 @@ pub struct DurationArgs {
     pub duration_secs: u64,
 }
 
+/// A display name change to apply via the Telegram API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NameChange {
+    pub first_name: String,
+    /// `None` leaves the last name unchanged.
+    pub last_name: Option<String>,
+}
+
+/// A temporary relaxation of the profile update rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Burst {
+    /// How long the relaxed limit lasts, in seconds.
+    pub window_secs: u64,
+    /// Minimum seconds between updates while it lasts.
+    pub interval_secs: u64,
+}
+
+/// Online status shown for the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Presence {
+    /// Let Telegram show the account as online while the bot is active.
+    Online,
+    /// Keep the account appearing offline.
+    Offline,
+}
+
+impl Presence {
+    /// Parses `online`/`on` or `offline`/`off`.
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "online" | "on" => Some(Self::Online),
+            "offline" | "off" => Some(Self::Offline),
+            _ => None,
+        }
+    }
+
+    /// Checks if this is the offline status.
+    #[must_use]
+    pub const fn is_offline(self) -> bool {
+        matches!(self, Self::Offline)
+    }
+}
+
+impl fmt::Display for Presence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Online => write!(f, "online"),
+            Self::Offline => write!(f, "offline"),
+        }
+    }
+}
+
+/// File moved by `relocate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelocateTarget {
+    /// The descriptions file.
+    Config,
+    /// The scheduler state file.
+    State,
+}
+
+impl RelocateTarget {
+    /// Parses `config`/`descriptions` or `state`.
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "config" | "descriptions" => Some(Self::Config),
+            "state" => Some(Self::State),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RelocateTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Config => write!(f, "config"),
+            Self::State => write!(f, "state"),
+        }
+    }
+}
+
 /// Available bot commands.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BotCommand {
@@ -33,8 +154,11 @@ pub enum BotCommand {
     /// Show the current status (current description, time remaining, etc.).
     Status,
 
-    /// List all configured descriptions.
-    List,
+    /// List configured descriptions, optionally a given page (1-based).
+    List(Option<usize>),
+
+    /// List only the description IDs, for scripts.
+    Ids,
 
     /// Show detailed view of a specific description.
     View(String),
@@ -42,8 +166,8 @@ pub enum BotCommand {
     /// Jump to a specific description by ID or index.
     Goto(String),
 
-    /// Pause the description rotation.
-    Pause,
+    /// Pause the description rotation (`auto`: still apply `set` texts).
+    Pause(PauseMode),
 
     /// Resume the description rotation.
     Resume,
@@ -55,22 +179,125 @@ pub enum BotCommand {
     Help,
 
     /// Set a custom description temporarily.
-    Set(String),
+    Set {
+        text: String,
+        /// Seconds to keep it (`None`: `CUSTOM_DURATION`).
+        duration_secs: Option<u64>,
+    },
+
+    /// Queue a text, or a description's text by ID, to show once after the
+    /// current description.
+    Queue(String),
 
     /// Add a new description.
     Add(AddArgs),
 
+    /// Add several descriptions at once, one `id|duration|text` per line.
+    BulkAdd(String),
+
     /// Edit an existing description's text.
     Edit(EditArgs),
 
+    /// Replace a literal substring in the text of every description.
+    ReplaceAll { from: String, to: String },
+
     /// Change description duration.
     Duration(DurationArgs),
 
+    /// Set the same duration (seconds) on every description.
+    DurationAll(u64),
+
     /// Delete a description.
     Delete(String),
 
     /// Show information about the bot.
     Info,
+
+    /// Switch the active description profile.
+    Profile(String),
+
+    /// Rotate only through descriptions carrying a tag.
+    Only(String),
+
+    /// Rotate through every description again.
+    All,
+
+    /// List the built-in bio templates.
+    Templates,
+
+    /// Add a built-in template as a new description.
+    TemplateAdd(String),
+
+    /// Show which description is scheduled over the next 24 hours.
+    Timeline,
+
+    /// Point out likely mistakes in the descriptions.
+    Lint,
+
+    /// Show the inputs and outcome of the next rotation decision.
+    Debug,
+
+    /// Explain why a description is or is not shown right now.
+    Why(String),
+
+    /// Show the exact bio a description would be sent as.
+    Render(String),
+
+    /// Set whether the account appears online or offline.
+    Presence(Presence),
+
+    /// Relax the update rate limit for a while (seconds, default if `None`).
+    Fast(Option<u64>),
+
+    /// Show the update rate limit, or set it (seconds) if given.
+    RateLimit(Option<u64>),
+
+    /// Add the bio currently on the profile as a description with this ID.
+    ImportCurrent(String),
+
+    /// Change the display name once (independent of the bio).
+    SetName { first: String, last: Option<String> },
+
+    /// Show per-description display statistics.
+    Stats,
+
+    /// Clear the display statistics (`confirmed` when sent with `confirm`).
+    StatsReset { confirmed: bool },
+
+    /// Show a runtime setting, or all of them if `None`.
+    Config(Option<String>),
+
+    /// Change a runtime setting and save it to the settings file.
+    ConfigSet { key: String, value: String },
+
+    /// Show the last failed bio update since the last success.
+    LastError,
+
+    /// Keep the current description this many seconds longer.
+    Extend(u64),
+
+    /// Move the descriptions or state file without restarting.
+    Relocate {
+        what: RelocateTarget,
+        new_path: PathBuf,
+        /// Remove the old file instead of leaving it in place.
+        remove_old: bool,
+    },
+
+    /// Write the scheduler state to disk now and show where it went.
+    Save,
+
+    /// List the saved snapshots of the descriptions.
+    Snapshots,
+
+    /// Save the descriptions as a named snapshot.
+    SnapshotSave(String),
+
+    /// Replace the descriptions with a named snapshot.
+    SnapshotRestore(String),
+
+    /// Show the last entries of the changelog (default 10).
+    Log(Option<usize>),
 }
 
 impl BotCommand {
@@ -78,13 +305,15 @@ impl BotCommand {
     ///
     /// Returns `None` if the message is not a valid command.
     #[must_use]
-    pub fn parse(text: &str, prefix: &str) -> Option<Self> {
+    pub fn parse<S: AsRef<str>>(text: &str, prefixes: &[S]) -> Option<Self> {
         let text = text.trim();
 
-        // Check if message starts with the command prefix
-        if !text.starts_with(prefix) {
-            return None;
-        }
+        // The longest matching prefix wins, so `/db` does not shadow `/dbx`
+        let prefix = prefixes
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|p| text.starts_with(p))
+            .max_by_key(|p| p.len())?;
 
         // Extract the command part after the prefix
         let after_prefix = text[prefix.len()..].trim_start();
@@ -98,31 +327,220 @@ impl BotCommand {
         match cmd.as_str() {
             "skip" | "next" => Some(Self::Skip),
             "status" | "stat" | "s" => Some(Self::Status),
-            "list" | "ls" | "l" => Some(Self::List),
+            "list" | "ls" | "l" => Self::parse_list(args),
+            "ids" | "list-ids" => Some(Self::Ids),
             "view" | "show" => args
                 .filter(|a| !a.is_empty())
                 .map(|a| Self::View(a.to_owned())),
             "goto" | "go" | "jump" => args
                 .filter(|a| !a.is_empty())
                 .map(|a| Self::Goto(a.to_owned())),
-            "pause" | "stop" => Some(Self::Pause),
+            "pause" | "stop" => match args.filter(|a| !a.is_empty()) {
+                Some(mode) => PauseMode::parse(mode).map(Self::Pause),
+                None => Some(Self::Pause(PauseMode::All)),
+            },
             "resume" | "start" | "continue" => Some(Self::Resume),
             "reload" | "refresh" => Some(Self::Reload),
             "help" | "h" | "?" => Some(Self::Help),
-            "set" => args
+            "set" => Self::parse_set(args?),
+            "queue" => args
                 .filter(|a| !a.is_empty())
-                .map(|a| Self::Set(a.to_owned())),
+                .map(|a| Self::Queue(a.to_owned())),
             "add" | "new" => Self::parse_add(args?),
+            "bulk-add" | "bulkadd" | "bulk" => args
+                .filter(|a| !a.is_empty())
+                .map(|a| Self::BulkAdd(a.to_owned())),
             "edit" | "change" => Self::parse_edit(args?),
+            "replace-all" | "replace" => Self::parse_replace_all(args?),
             "duration" | "time" => Self::parse_duration(args?),
+            "duration-all" | "set-duration-all" => {
+                parse_human_duration(args?).map(Self::DurationAll)
+            }
             "delete" | "remove" | "rm" | "del" => args
                 .filter(|a| !a.is_empty())
                 .map(|a| Self::Delete(a.to_owned())),
             "info" | "about" | "version" => Some(Self::Info),
+            "profile" | "use" => args
+                .filter(|a| !a.is_empty())
+                .map(|a| Self::Profile(a.to_owned())),
+            "only" => args
+                .filter(|a| !a.is_empty())
+                .map(|a| Self::Only(a.to_owned())),
+            "all" => Some(Self::All),
+            "templates" | "template" | "tpl" => Self::parse_template(args),
+            "timeline" | "schedule-list" | "today" => Some(Self::Timeline),
+            "lint" | "check" => Some(Self::Lint),
+            "debug" | "explain" => Some(Self::Debug),
+            "why" | "why-skipped" => args
+                .filter(|a| !a.is_empty())
+                .map(|a| Self::Why(a.to_owned())),
+            "render" | "preview-render" => args
+                .filter(|a| !a.is_empty())
+                .map(|a| Self::Render(a.to_owned())),
+            "presence" => args.and_then(Presence::parse).map(Self::Presence),
+            "fast" | "burst" => match args.filter(|a| !a.is_empty()) {
+                Some(secs) => secs
+                    .parse()
+                    .ok()
+                    .filter(|&s| s > 0)
+                    .map(|s| Self::Fast(Some(s))),
+                None => Some(Self::Fast(None)),
+            },
+            "rate-limit" | "ratelimit" => match args.filter(|a| !a.is_empty()) {
+                Some(duration) => parse_human_duration(duration).map(|s| Self::RateLimit(Some(s))),
+                None => Some(Self::RateLimit(None)),
+            },
+            "import-current" | "import-from-telegram" => args
+                .filter(|a| !a.is_empty() && !a.contains(char::is_whitespace))
+                .map(|a| Self::ImportCurrent(a.to_owned())),
+            "set-name" | "setname" | "name" => Self::parse_set_name(args?),
+            "stats" | "statistics" => Self::parse_stats(args),
+            "config" | "settings" => Self::parse_config(args),
+            "relocate" | "rename-file" => Self::parse_relocate(args?),
+            "extend" | "boost" => parse_human_duration(args?).map(Self::Extend),
+            "last-error" | "lasterror" | "error" | "errors" => Some(Self::LastError),
+            "save" | "save-state" | "flush" => Some(Self::Save),
+            "snapshot" | "snapshots" => Self::parse_snapshot(args),
+            "log" | "changelog" => match args.filter(|a| !a.is_empty()) {
+                Some(count) => count
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .map(|n| Self::Log(Some(n))),
+                None => Some(Self::Log(None)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Parses config command arguments: `[get [key]]` or `set <key> <value>`
+    fn parse_config(args: Option<&str>) -> Option<Self> {
+        let Some(args) = args.filter(|a| !a.is_empty()) else {
+            return Some(Self::Config(None));
+        };
+
+        let (sub, rest) = args
+            .split_once(char::is_whitespace)
+            .map_or((args, ""), |(sub, rest)| (sub, rest.trim()));
+        match sub.to_lowercase().as_str() {
+            "get" if rest.is_empty() => Some(Self::Config(None)),
+            "get" if !rest.contains(char::is_whitespace) => {
+                Some(Self::Config(Some(rest.to_owned())))
+            }
+            "set" => {
+                let (key, value) = rest.split_once(char::is_whitespace)?;
+                let value = value.trim();
+                (!value.is_empty()).then(|| Self::ConfigSet {
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses snapshot command arguments: `[list]`, `save <name>` or
+    /// `restore <name>`
+    fn parse_snapshot(args: Option<&str>) -> Option<Self> {
+        let Some(args) = args.filter(|a| !a.is_empty()) else {
+            return Some(Self::Snapshots);
+        };
+
+        match args.split_once(char::is_whitespace) {
+            Some((sub, name)) if !name.trim().is_empty() => match sub.to_lowercase().as_str() {
+                "save" => Some(Self::SnapshotSave(name.trim().to_owned())),
+                "restore" | "load" => Some(Self::SnapshotRestore(name.trim().to_owned())),
+                _ => None,
+            },
+            None if args.eq_ignore_ascii_case("list") => Some(Self::Snapshots),
+            _ => None,
+        }
+    }
+
+    /// Parses relocate command arguments: `<config|state> <path> [--remove]`
+    fn parse_relocate(args: &str) -> Option<Self> {
+        let (what, rest) = args.split_once(char::is_whitespace)?;
+        let what = RelocateTarget::parse(what)?;
+        let rest = rest.trim();
+        let (path, remove_old) = match rest.strip_suffix("--remove") {
+            Some(path) => (path.trim_end(), true),
+            None => (rest, false),
+        };
+
+        if path.is_empty() {
+            return None;
+        }
+
+        Some(Self::Relocate {
+            what,
+            new_path: PathBuf::from(path),
+            remove_old,
+        })
+    }
+
+    /// Parses list command arguments: `[N]`, `[--page N]` or `ids`
+    fn parse_list(args: Option<&str>) -> Option<Self> {
+        let Some(args) = args.filter(|a| !a.is_empty()) else {
+            return Some(Self::List(None));
+        };
+        if args.eq_ignore_ascii_case("ids") {
+            return Some(Self::Ids);
+        }
+
+        let page = args.strip_prefix("--page").unwrap_or(args).trim();
+        page.parse().ok().map(|page| Self::List(Some(page)))
+    }
+
+    /// Parses template command arguments: `[list]` or `add <name>`
+    fn parse_template(args: Option<&str>) -> Option<Self> {
+        let Some(args) = args.filter(|a| !a.is_empty()) else {
+            return Some(Self::Templates);
+        };
+
+        match args.split_once(char::is_whitespace) {
+            Some((sub, name)) if sub.eq_ignore_ascii_case("add") && !name.trim().is_empty() => {
+                Some(Self::TemplateAdd(name.trim().to_owned()))
+            }
+            None if args.eq_ignore_ascii_case("list") => Some(Self::Templates),
             _ => None,
         }
     }
 
+    /// Parses stats command arguments: `[reset [confirm]]`
+    fn parse_stats(args: Option<&str>) -> Option<Self> {
+        let Some(args) = args.filter(|a| !a.is_empty()) else {
+            return Some(Self::Stats);
+        };
+
+        let mut words = args.split_whitespace().map(str::to_lowercase);
+        match (
+            words.next()?.as_str(),
+            words.next().as_deref(),
+            words.next(),
+        ) {
+            ("reset", None, None) => Some(Self::StatsReset { confirmed: false }),
+            ("reset", Some("confirm" | "yes"), None) => Some(Self::StatsReset { confirmed: true }),
+            _ => None,
+        }
+    }
+
+    /// Parses set-name command arguments: `<first> [last]`
+    fn parse_set_name(args: &str) -> Option<Self> {
+        let (first, last) = match args.split_once(char::is_whitespace) {
+            Some((first, last)) => (first, Some(last.trim())),
+            None => (args, None),
+        };
+
+        if first.is_empty() {
+            return None;
+        }
+
+        Some(Self::SetName {
+            first: first.to_owned(),
+            last: last.filter(|l| !l.is_empty()).map(str::to_owned),
+        })
+    }
+
     /// Parses add command arguments: `<id> <duration_secs> <text>`
     fn parse_add(args: &str) -> Option<Self> {
         let mut parts = args.splitn(3, char::is_whitespace);
@@ -156,6 +574,41 @@ impl BotCommand {
         Some(Self::Edit(EditArgs { id, text }))
     }
 
+    /// Parses replace-all command arguments: `<from> => <to>` (`to` may be
+    /// empty to remove the text)
+    fn parse_replace_all(args: &str) -> Option<Self> {
+        let (from, to) = args.split_once("=>")?;
+        let from = from.trim();
+        if from.is_empty() {
+            return None;
+        }
+
+        Some(Self::ReplaceAll {
+            from: from.to_owned(),
+            to: to.trim().to_owned(),
+        })
+    }
+
+    /// Parses set command arguments: `[--for <secs>] <text>`
+    fn parse_set(args: &str) -> Option<Self> {
+        let (duration_secs, text) = match args.strip_prefix("--for") {
+            Some(rest) => {
+                let (secs, text) = rest.trim_start().split_once(char::is_whitespace)?;
+                (Some(secs.parse().ok().filter(|&s| s > 0)?), text.trim())
+            }
+            None => (None, args),
+        };
+
+        if text.is_empty() {
+            return None;
+        }
+
+        Some(Self::Set {
+            text: text.to_owned(),
+            duration_secs,
+        })
+    }
+
     /// Parses duration command arguments: `<id> <duration_secs>`
     fn parse_duration(args: &str) -> Option<Self> {
         let mut parts = args.split_whitespace();
@@ -177,19 +630,51 @@ impl BotCommand {
         match self {
             Self::Skip => "skip",
             Self::Status => "status",
-            Self::List => "list",
+            Self::List(_) => "list",
+            Self::Ids => "ids",
             Self::View(_) => "view",
             Self::Goto(_) => "goto",
-            Self::Pause => "pause",
+            Self::Pause(_) => "pause",
             Self::Resume => "resume",
             Self::Reload => "reload",
             Self::Help => "help",
-            Self::Set(_) => "set",
+            Self::Set { .. } => "set",
             Self::Add(_) => "add",
+            Self::Queue(_) => "queue",
+            Self::BulkAdd(_) => "bulk-add",
             Self::Edit(_) => "edit",
+            Self::ReplaceAll { .. } => "replace-all",
             Self::Duration(_) => "duration",
+            Self::DurationAll(_) => "duration-all",
             Self::Delete(_) => "delete",
             Self::Info => "info",
+            Self::Profile(_) => "profile",
+            Self::Only(_) => "only",
+            Self::All => "all",
+            Self::Templates => "templates",
+            Self::TemplateAdd(_) => "template add",
+            Self::Timeline => "timeline",
+            Self::Lint => "lint",
+            Self::Debug => "debug",
+            Self::Why(_) => "why",
+            Self::Render(_) => "render",
+            Self::Presence(_) => "presence",
+            Self::Fast(_) => "fast",
+            Self::RateLimit(_) => "rate-limit",
+            Self::ImportCurrent(_) => "import-current",
+            Self::SetName { .. } => "set-name",
+            Self::Stats => "stats",
+            Self::StatsReset { .. } => "stats reset",
+            Self::Config(_) => "config",
+            Self::ConfigSet { .. } => "config set",
+            Self::Relocate { .. } => "relocate",
+            Self::Extend(_) => "extend",
+            Self::LastError => "last-error",
+            Self::Save => "save",
+            Self::Snapshots => "snapshots",
+            Self::SnapshotSave(_) => "snapshot save",
+            Self::SnapshotRestore(_) => "snapshot restore",
+            Self::Log(_) => "log",
         }
     }
 
@@ -199,62 +684,344 @@ impl BotCommand {
         match self {
             Self::Skip => "Skip current description, move to next",
             Self::Status => "Show current status and time remaining",
-            Self::List => "List all configured descriptions",
+            Self::List(_) => "List all configured descriptions",
+            Self::Ids => "List description IDs only (current marked with *)",
             Self::View(_) => "View details of a specific description",
             Self::Goto(_) => "Jump to a specific description (by ID or index)",
-            Self::Pause => "Pause description rotation",
+            Self::Pause(_) => "Pause description rotation",
             Self::Resume => "Resume description rotation",
             Self::Reload => "Reload descriptions from file",
             Self::Help => "Show this help message",
-            Self::Set(_) => "Set a custom description temporarily",
+            Self::Set { .. } => "Set a custom description temporarily",
             Self::Add(_) => "Add a new description",
+            Self::BulkAdd(_) => "Add several descriptions at once",
+            Self::Queue(_) => "Show a text once after the current description",
             Self::Edit(_) => "Edit an existing description",
+            Self::ReplaceAll { .. } => "Replace text in every description",
             Self::Duration(_) => "Change description duration",
+            Self::DurationAll(_) => "Set one duration for every description",
             Self::Delete(_) => "Delete a description",
             Self::Info => "Show bot information",
+            Self::Profile(_) => "Switch the active description profile",
+            Self::Only(_) => "Rotate only through descriptions with a tag",
+            Self::All => "Rotate through every description again",
+            Self::Templates => "List built-in bio templates",
+            Self::TemplateAdd(_) => "Add a built-in template as a description",
+            Self::Timeline => "Show scheduled descriptions for the next 24 hours",
+            Self::Lint => "Point out likely mistakes in the descriptions",
+            Self::Debug => "Show how the next rotation is decided",
+            Self::Why(_) => "Explain why a description is not shown right now",
+            Self::Render(_) => "Show the exact bio a description would be sent as",
+            Self::Presence(_) => "Appear online or offline while the bot runs",
+            Self::Fast(_) => "Allow quick manual changes for a short time",
+            Self::RateLimit(_) => "Show or change the minimum interval between updates",
+            Self::ImportCurrent(_) => "Add the current profile bio as a description",
+            Self::SetName { .. } => "Change the display name once",
+            Self::Stats => "Show display statistics per description",
+            Self::StatsReset { .. } => "Clear the display statistics",
+            Self::Config(_) => "Show runtime settings",
+            Self::ConfigSet { .. } => "Change a runtime setting",
+            Self::Relocate { .. } => "Move the descriptions or state file",
+            Self::Extend(_) => "Keep the current description longer",
+            Self::LastError => "Show why the last bio update failed",
+            Self::Save => "Write the state to disk and show where it is",
+            Self::Snapshots => "List saved snapshots of the descriptions",
+            Self::SnapshotSave(_) => "Save the descriptions as a named snapshot",
+            Self::SnapshotRestore(_) => "Switch to a saved snapshot of the descriptions",
+            Self::Log(_) => "Show the latest changes to the descriptions",
+        }
+    }
+
+    /// Checks if the command changes the descriptions file, so it is
+    /// recorded in the changelog.
+    #[must_use]
+    pub const fn edits_descriptions(&self) -> bool {
+        matches!(
+            self,
+            Self::Add(_)
+                | Self::BulkAdd(_)
+                | Self::Edit(_)
+                | Self::ReplaceAll { .. }
+                | Self::Duration(_)
+                | Self::DurationAll(_)
+                | Self::Delete(_)
+                | Self::Profile(_)
+                | Self::TemplateAdd(_)
+                | Self::ImportCurrent(_)
+                | Self::SnapshotRestore(_)
+        )
+    }
+
+    /// Returns the description the command names, as typed (an ID or a
+    /// 1-based number).
+    #[must_use]
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            Self::Add(AddArgs { id, .. })
+            | Self::Edit(EditArgs { id, .. })
+            | Self::Duration(DurationArgs { id, .. })
+            | Self::Delete(id)
+            | Self::ImportCurrent(id) => Some(id),
+            _ => None,
         }
     }
 
     /// Returns all available commands with their descriptions.
     #[must_use]
+    #[allow(clippy::too_many_lines)] // One entry per command
     pub fn all_commands() -> Vec<(&'static str, &'static str, &'static str)> {
         vec![
             ("skip", "", "Skip current description, move to next"),
             ("status", "(s)", "Show current status and time remaining"),
-            ("list", "(ls)", "List all configured descriptions"),
+            ("list [page]", "(ls)", "List all configured descriptions"),
+            (
+                "ids",
+                "(list ids)",
+                "List description IDs only (current marked with *)",
+            ),
             ("view <id>", "", "View details of a specific description"),
             ("goto <id>", "", "Jump to a specific description"),
-            ("pause", "", "Pause description rotation"),
+            (
+                "pause [auto|all]",
+                "",
+                "Pause description rotation (auto: 'set' still applies)",
+            ),
             ("resume", "", "Resume description rotation"),
             ("reload", "", "Reload descriptions from file"),
-            ("set <text>", "", "Set a custom description temporarily"),
+            (
+                "extend <time>",
+                "(boost)",
+                "Keep the current description longer (e.g. 30m, 1h)",
+            ),
+            (
+                "set [--for <sec>] <text>",
+                "",
+                "Set a custom description temporarily",
+            ),
+            (
+                "queue <text|id>",
+                "",
+                "Show a text once after the current description",
+            ),
+            (
+                "set-name <first> [last]",
+                "",
+                "Change the display name once",
+            ),
             ("add <id> <sec> <text>", "", "Add a new description"),
+            (
+                "bulk-add <id|sec|text lines>",
+                "(bulk)",
+                "Add several descriptions at once",
+            ),
             ("edit <id> <text>", "", "Edit description text"),
+            (
+                "replace-all <from> => <to>",
+                "",
+                "Replace text in every description",
+            ),
             ("duration <id> <sec>", "", "Change description duration"),
+            (
+                "duration-all <time>",
+                "",
+                "Set one duration for every description (e.g. 90, 15m, 1h30m)",
+            ),
             ("delete <id>", "(rm)", "Delete a description"),
+            (
+                "profile <name>",
+                "",
+                "Switch the active description profile",
+            ),
+            (
+                "only <tag>",
+                "",
+                "Rotate only through descriptions with a tag",
+            ),
+            ("all", "", "Rotate through every description again"),
+            ("templates", "(tpl)", "List built-in bio templates"),
+            (
+                "template add <name>",
+                "",
+                "Add a built-in template as a description",
+            ),
+            (
+                "timeline",
+                "(today)",
+                "Show scheduled descriptions for the next 24 hours",
+            ),
+            (
+                "lint",
+                "(check)",
+                "Point out likely mistakes in the descriptions",
+            ),
+            (
+                "debug",
+                "(explain)",
+                "Show how the next rotation is decided",
+            ),
+            (
+                "why <id>",
+                "",
+                "Explain why a description is not shown right now",
+            ),
+            (
+                "render <id>",
+                "(preview-render)",
+                "Show the exact bio a description would be sent as",
+            ),
+            (
+                "presence <online|offline>",
+                "",
+                "Appear online or offline while the bot runs",
+            ),
+            (
+                "fast [sec]",
+                "",
+                "Allow quick manual changes for a short time",
+            ),
+            (
+                "rate-limit [duration]",
+                "",
+                "Show or change the minimum interval between updates",
+            ),
+            (
+                "import-current <id>",
+                "",
+                "Add the current profile bio as a description",
+            ),
+            ("stats", "", "Show display statistics per description"),
+            (
+                "last-error",
+                "(errors)",
+                "Show why the last bio update failed",
+            ),
+            (
+                "save",
+                "(flush)",
+                "Write the state to disk and show where it is",
+            ),
+            ("stats reset", "", "Clear the display statistics"),
+            ("config [get <key>]", "", "Show runtime settings"),
+            ("config set <key> <value>", "", "Change a runtime setting"),
+            (
+                "relocate <config|state> <path> [--remove]",
+                "",
+                "Move the descriptions or state file",
+            ),
+            (
+                "snapshot [list]",
+                "",
+                "List saved snapshots of the descriptions",
+            ),
+            (
+                "snapshot save <name>",
+                "",
+                "Save the descriptions as a named snapshot",
+            ),
+            (
+                "snapshot restore <name>",
+                "",
+                "Switch to a saved snapshot of the descriptions",
+            ),
+            (
+                "log [count]",
+                "(changelog)",
+                "Show the latest changes to the descriptions",
+            ),
             ("info", "", "Show bot information"),
             ("help", "(h, ?)", "Show this help message"),
         ]
     }
 }
 
+/// Parses a duration like `90`, `45s`, `15m`, `1h30m` or `1d` into seconds.
+///
+/// A bare number is seconds. Returns `None` for zero or malformed input.
+fn parse_human_duration(s: &str) -> Option<u64> {
+    if let Ok(secs) = s.parse::<u64>() {
+        return (secs > 0).then_some(secs);
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            _ => return None,
+        };
+        let value: u64 = digits.parse().ok()?;
+        total = total.checked_add(value.checked_mul(unit)?)?;
+        digits.clear();
+    }
+
+    (digits.is_empty() && total > 0).then_some(total)
+}
+
 impl fmt::Display for BotCommand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::View(id) => write!(f, "view {id}"),
             Self::Goto(target) => write!(f, "goto {target}"),
-            Self::Set(text) => write!(f, "set {text}"),
+            Self::Set {
+                text,
+                duration_secs: Some(secs),
+            } => write!(f, "set --for {secs} {text}"),
+            Self::Set { text, .. } => write!(f, "set {text}"),
             Self::Add(args) => write!(f, "add {} {} {}", args.id, args.duration_secs, args.text),
+            Self::Queue(item) => write!(f, "queue {item}"),
+            Self::BulkAdd(lines) => write!(f, "bulk-add\n{lines}"),
             Self::Edit(args) => write!(f, "edit {} {}", args.id, args.text),
+            Self::ReplaceAll { from, to } => write!(f, "replace-all {from} => {to}"),
             Self::Duration(args) => write!(f, "duration {} {}", args.id, args.duration_secs),
+            Self::DurationAll(secs) => write!(f, "duration-all {secs}"),
+            Self::Extend(secs) => write!(f, "extend {secs}"),
             Self::Delete(id) => write!(f, "delete {id}"),
+            Self::Pause(PauseMode::Auto) => write!(f, "pause auto"),
+            Self::Profile(name) => write!(f, "profile {name}"),
+            Self::Only(tag) => write!(f, "only {tag}"),
+            Self::TemplateAdd(name) => write!(f, "template add {name}"),
+            Self::List(Some(page)) => write!(f, "list {page}"),
+            Self::Why(id) => write!(f, "why {id}"),
+            Self::Render(id) => write!(f, "render {id}"),
+            Self::Presence(presence) => write!(f, "presence {presence}"),
+            Self::Fast(Some(secs)) => write!(f, "fast {secs}"),
+            Self::RateLimit(Some(secs)) => write!(f, "rate-limit {secs}"),
+            Self::ImportCurrent(id) => write!(f, "import-current {id}"),
+            Self::StatsReset { confirmed: true } => write!(f, "stats reset confirm"),
+            Self::Config(Some(key)) => write!(f, "config get {key}"),
+            Self::ConfigSet { key, value } => write!(f, "config set {key} {value}"),
+            Self::Relocate {
+                what,
+                new_path,
+                remove_old,
+            } => {
+                write!(f, "relocate {what} {}", new_path.display())?;
+                if *remove_old {
+                    write!(f, " --remove")?;
+                }
+                Ok(())
+            }
+            Self::SnapshotSave(name) => write!(f, "snapshot save {name}"),
+            Self::SnapshotRestore(name) => write!(f, "snapshot restore {name}"),
+            Self::Log(Some(count)) => write!(f, "log {count}"),
+            Self::SetName { first, last } => match last {
+                Some(last) => write!(f, "set-name {first} {last}"),
+                None => write!(f, "set-name {first}"),
+            },
             _ => write!(f, "{}", self.name()),
         }
     }
 }
 
 /// Result of command execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CommandResult {
     /// Whether the command was successful.
     pub success: bool,
@@ -264,6 +1031,22 @@ pub struct CommandResult {
 
     /// Whether to trigger an immediate description update.
     pub trigger_update: bool,
+
+    /// Display name change to apply before replying.
+    pub name_change: Option<NameChange>,
+
+    /// Online status to apply before replying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence: Option<Presence>,
+
+    /// Rate limit relaxation to apply before replying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst: Option<Burst>,
+
+    /// Machine-readable payload of read commands (`status`, `list`, `stats`)
+    /// for the control interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 impl CommandResult {
@@ -274,6 +1057,10 @@ impl CommandResult {
             success: true,
             message: message.into(),
             trigger_update: false,
+            name_change: None,
+            presence: None,
+            burst: None,
+            data: None,
         }
     }
 
@@ -284,6 +1071,10 @@ impl CommandResult {
             success: true,
             message: message.into(),
             trigger_update: true,
+            name_change: None,
+            presence: None,
+            burst: None,
+            data: None,
         }
     }
 
@@ -294,15 +1085,72 @@ impl CommandResult {
             success: false,
             message: message.into(),
             trigger_update: false,
+            name_change: None,
+            presence: None,
+            burst: None,
+            data: None,
         }
     }
+
+    /// Attaches a display name change to the result.
+    #[must_use]
+    pub fn with_name_change(mut self, change: NameChange) -> Self {
+        self.name_change = Some(change);
+        self
+    }
+
+    /// Attaches an online status change to the result.
+    #[must_use]
+    pub const fn with_presence(mut self, presence: Presence) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+
+    /// Attaches a rate limit relaxation to the result.
+    #[must_use]
+    pub const fn with_burst(mut self, burst: Burst) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Attaches a structured payload to the result.
+    #[must_use]
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const PREFIX: &str = "/description_bot";
+    const PREFIX: &[&str] = &["/description_bot"];
+
+    #[test]
+    fn test_parse_with_several_prefixes() {
+        let prefixes = ["/description_bot", "/db"];
+        assert_eq!(
+            BotCommand::parse("/description_bot status", &prefixes),
+            Some(BotCommand::Status)
+        );
+        assert_eq!(
+            BotCommand::parse("/db status", &prefixes),
+            Some(BotCommand::Status)
+        );
+        assert_eq!(BotCommand::parse("/bot status", &prefixes), None);
+
+        // "/d" also matches "/db ...", but the longer prefix wins
+        let prefixes = ["/d", "/db"];
+        assert_eq!(
+            BotCommand::parse("/db goto 2", &prefixes),
+            Some(BotCommand::Goto("2".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/d goto 2", &prefixes),
+            Some(BotCommand::Goto("2".to_owned()))
+        );
+    }
 
     #[test]
     fn test_parse_skip() {
@@ -328,6 +1176,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_list_page() {
+        assert_eq!(
+            BotCommand::parse("/description_bot ls", PREFIX),
+            Some(BotCommand::List(None))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot list 2", PREFIX),
+            Some(BotCommand::List(Some(2)))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot list --page 3", PREFIX),
+            Some(BotCommand::List(Some(3)))
+        );
+        assert_eq!(BotCommand::parse("/description_bot list two", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        for text in ["ids", "list-ids", "list ids", "ls IDS"] {
+            assert_eq!(
+                BotCommand::parse(&format!("/description_bot {text}"), PREFIX),
+                Some(BotCommand::Ids),
+                "{text}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_goto_with_arg() {
         assert_eq!(
@@ -345,7 +1221,25 @@ mod tests {
     fn test_parse_set_with_arg() {
         assert_eq!(
             BotCommand::parse("/description_bot set Hello World", PREFIX),
-            Some(BotCommand::Set("Hello World".to_owned()))
+            Some(BotCommand::Set {
+                text: "Hello World".to_owned(),
+                duration_secs: None,
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot set --for 1800 In a meeting", PREFIX),
+            Some(BotCommand::Set {
+                text: "In a meeting".to_owned(),
+                duration_secs: Some(1800),
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot set --for 0 Hi", PREFIX),
+            None
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot set --for 60", PREFIX),
+            None
         );
     }
 
@@ -361,6 +1255,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_bulk_add() {
+        assert_eq!(
+            BotCommand::parse("/description_bot bulk-add\na|60|One\nb|120|Two", PREFIX),
+            Some(BotCommand::BulkAdd("a|60|One\nb|120|Two".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot bulk", PREFIX), None);
+
+        assert_eq!(
+            AddArgs::parse_bulk_line(" a | 60 | Tea | coffee "),
+            Ok(AddArgs {
+                id: "a".to_owned(),
+                duration_secs: 60,
+                text: "Tea | coffee".to_owned(),
+            })
+        );
+        assert!(AddArgs::parse_bulk_line("a|60").is_err());
+        assert!(AddArgs::parse_bulk_line("a|soon|Text").is_err());
+        assert!(AddArgs::parse_bulk_line("|60|Text").is_err());
+    }
+
+    #[test]
+    fn test_parse_stats() {
+        assert_eq!(
+            BotCommand::parse("/description_bot stats", PREFIX),
+            Some(BotCommand::Stats)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot stats reset", PREFIX),
+            Some(BotCommand::StatsReset { confirmed: false })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot stats reset confirm", PREFIX),
+            Some(BotCommand::StatsReset { confirmed: true })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot stats wipe", PREFIX),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_edit() {
         assert_eq!(
@@ -372,6 +1307,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_replace_all() {
+        assert_eq!(
+            BotCommand::parse(
+                "/description_bot replace-all example.com => example.org/me",
+                PREFIX
+            ),
+            Some(BotCommand::ReplaceAll {
+                from: "example.com".to_owned(),
+                to: "example.org/me".to_owned(),
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot replace-all 🌙 =>", PREFIX),
+            Some(BotCommand::ReplaceAll {
+                from: "🌙".to_owned(),
+                to: String::new(),
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot replace-all => x", PREFIX),
+            None
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot replace-all a b", PREFIX),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_delete() {
         assert_eq!(
@@ -395,6 +1359,400 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_duration_all() {
+        for (text, secs) in [
+            ("90", 90),
+            ("45s", 45),
+            ("15m", 900),
+            ("1h30m", 5400),
+            ("1d", 86_400),
+        ] {
+            assert_eq!(
+                BotCommand::parse(&format!("/description_bot duration-all {text}"), PREFIX),
+                Some(BotCommand::DurationAll(secs)),
+                "{text}"
+            );
+        }
+        for text in ["0", "0m", "h", "1x", "1h 30m", "-5"] {
+            assert_eq!(
+                BotCommand::parse(&format!("/description_bot duration-all {text}"), PREFIX),
+                None,
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_pause_modes() {
+        assert_eq!(
+            BotCommand::parse("/description_bot pause", PREFIX),
+            Some(BotCommand::Pause(PauseMode::All))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot pause all", PREFIX),
+            Some(BotCommand::Pause(PauseMode::All))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot pause auto", PREFIX),
+            Some(BotCommand::Pause(PauseMode::Auto))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot pause soon", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(
+            BotCommand::parse("/description_bot profile work", PREFIX),
+            Some(BotCommand::Profile("work".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot profile", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_queue() {
+        assert_eq!(
+            BotCommand::parse("/description_bot queue Back at 3", PREFIX),
+            Some(BotCommand::Queue("Back at 3".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot queue working", PREFIX),
+            Some(BotCommand::Queue("working".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot queue", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_config() {
+        assert_eq!(
+            BotCommand::parse("/description_bot config", PREFIX),
+            Some(BotCommand::Config(None))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot config get min-interval", PREFIX),
+            Some(BotCommand::Config(Some("min-interval".to_owned())))
+        );
+        assert_eq!(
+            BotCommand::parse(
+                "/description_bot config set quiet-hours 23:00-07:00",
+                PREFIX
+            ),
+            Some(BotCommand::ConfigSet {
+                key: "quiet-hours".to_owned(),
+                value: "23:00-07:00".to_owned(),
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot config set min-interval", PREFIX),
+            None
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot config reset", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_last_error() {
+        for text in ["last-error", "errors", "ERROR"] {
+            assert_eq!(
+                BotCommand::parse(&format!("/description_bot {text}"), PREFIX),
+                Some(BotCommand::LastError),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_extend() {
+        assert_eq!(
+            BotCommand::parse("/description_bot extend 1h", PREFIX),
+            Some(BotCommand::Extend(3600))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot boost 90", PREFIX),
+            Some(BotCommand::Extend(90))
+        );
+        assert_eq!(BotCommand::parse("/description_bot extend", PREFIX), None);
+        assert_eq!(BotCommand::parse("/description_bot extend 0", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_relocate() {
+        assert_eq!(
+            BotCommand::parse(
+                "/description_bot relocate state /srv/bot/state.json",
+                PREFIX
+            ),
+            Some(BotCommand::Relocate {
+                what: RelocateTarget::State,
+                new_path: PathBuf::from("/srv/bot/state.json"),
+                remove_old: false,
+            })
+        );
+        let command = BotCommand::parse(
+            "/description_bot rename-file config my bios.json --remove",
+            PREFIX,
+        );
+        assert_eq!(
+            command,
+            Some(BotCommand::Relocate {
+                what: RelocateTarget::Config,
+                new_path: PathBuf::from("my bios.json"),
+                remove_old: true,
+            })
+        );
+        assert_eq!(
+            command.unwrap().to_string(),
+            "relocate config my bios.json --remove"
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot relocate session new.db", PREFIX),
+            None
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot relocate state --remove", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_save() {
+        for text in ["save", "SAVE-STATE", "flush"] {
+            assert_eq!(
+                BotCommand::parse(&format!("/description_bot {text}"), PREFIX),
+                Some(BotCommand::Save),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_snapshot() {
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshot", PREFIX),
+            Some(BotCommand::Snapshots)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshots list", PREFIX),
+            Some(BotCommand::Snapshots)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshot save weekend", PREFIX),
+            Some(BotCommand::SnapshotSave("weekend".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshot RESTORE work", PREFIX),
+            Some(BotCommand::SnapshotRestore("work".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshot save", PREFIX),
+            None
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot snapshot drop work", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_log() {
+        assert_eq!(
+            BotCommand::parse("/description_bot log", PREFIX),
+            Some(BotCommand::Log(None))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot changelog 25", PREFIX),
+            Some(BotCommand::Log(Some(25)))
+        );
+        assert_eq!(BotCommand::parse("/description_bot log 0", PREFIX), None);
+        assert_eq!(BotCommand::parse("/description_bot log all", PREFIX), None);
+
+        let edit = BotCommand::parse("/description_bot edit 2 New text", PREFIX).unwrap();
+        assert!(edit.edits_descriptions());
+        assert_eq!(edit.target(), Some("2"));
+        assert!(!BotCommand::Log(None).edits_descriptions());
+    }
+
+    #[test]
+    fn test_parse_only_and_all() {
+        assert_eq!(
+            BotCommand::parse("/description_bot only work", PREFIX),
+            Some(BotCommand::Only("work".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot only", PREFIX), None);
+        assert_eq!(
+            BotCommand::parse("/description_bot all", PREFIX),
+            Some(BotCommand::All)
+        );
+    }
+
+    #[test]
+    fn test_parse_set_name() {
+        assert_eq!(
+            BotCommand::parse("/description_bot set-name Alex Smith", PREFIX),
+            Some(BotCommand::SetName {
+                first: "Alex".to_owned(),
+                last: Some("Smith".to_owned())
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot set-name Alex", PREFIX),
+            Some(BotCommand::SetName {
+                first: "Alex".to_owned(),
+                last: None
+            })
+        );
+        assert_eq!(BotCommand::parse("/description_bot set-name", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_fast() {
+        assert_eq!(
+            BotCommand::parse("/description_bot fast", PREFIX),
+            Some(BotCommand::Fast(None))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot burst 300", PREFIX),
+            Some(BotCommand::Fast(Some(300)))
+        );
+        assert_eq!(BotCommand::parse("/description_bot fast 0", PREFIX), None);
+        assert_eq!(
+            BotCommand::parse("/description_bot fast soon", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(
+            BotCommand::parse("/description_bot rate-limit", PREFIX),
+            Some(BotCommand::RateLimit(None))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot ratelimit 2m", PREFIX),
+            Some(BotCommand::RateLimit(Some(120)))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot rate-limit 0", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_timeline() {
+        assert_eq!(
+            BotCommand::parse("/description_bot timeline", PREFIX),
+            Some(BotCommand::Timeline)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot schedule-list", PREFIX),
+            Some(BotCommand::Timeline)
+        );
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        assert_eq!(
+            BotCommand::parse("/description_bot lint", PREFIX),
+            Some(BotCommand::Lint)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot check", PREFIX),
+            Some(BotCommand::Lint)
+        );
+    }
+
+    #[test]
+    fn test_parse_debug() {
+        assert_eq!(
+            BotCommand::parse("/description_bot debug", PREFIX),
+            Some(BotCommand::Debug)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot explain", PREFIX),
+            Some(BotCommand::Debug)
+        );
+    }
+
+    #[test]
+    fn test_parse_why() {
+        assert_eq!(
+            BotCommand::parse("/description_bot why evening", PREFIX),
+            Some(BotCommand::Why("evening".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot why-skipped 2", PREFIX),
+            Some(BotCommand::Why("2".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot why", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_render() {
+        assert_eq!(
+            BotCommand::parse("/description_bot render evening", PREFIX),
+            Some(BotCommand::Render("evening".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot preview-render 2", PREFIX),
+            Some(BotCommand::Render("2".to_owned()))
+        );
+        assert_eq!(BotCommand::parse("/description_bot render", PREFIX), None);
+    }
+
+    #[test]
+    fn test_parse_presence() {
+        assert_eq!(
+            BotCommand::parse("/description_bot presence offline", PREFIX),
+            Some(BotCommand::Presence(Presence::Offline))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot presence ON", PREFIX),
+            Some(BotCommand::Presence(Presence::Online))
+        );
+        assert_eq!(BotCommand::parse("/description_bot presence", PREFIX), None);
+        assert_eq!(
+            BotCommand::parse("/description_bot presence away", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_import_current() {
+        assert_eq!(
+            BotCommand::parse("/description_bot import-current mine", PREFIX),
+            Some(BotCommand::ImportCurrent("mine".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot import-from-telegram mine", PREFIX),
+            Some(BotCommand::ImportCurrent("mine".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot import-current my bio", PREFIX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_templates() {
+        assert_eq!(
+            BotCommand::parse("/description_bot templates", PREFIX),
+            Some(BotCommand::Templates)
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot template add coffee", PREFIX),
+            Some(BotCommand::TemplateAdd("coffee".to_owned()))
+        );
+        assert_eq!(
+            BotCommand::parse("/description_bot template add", PREFIX),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_wrong_prefix() {
         assert_eq!(BotCommand::parse("/other_bot skip", PREFIX), None);
@@ -0,0 +1,252 @@
+//! Several accounts run from one process.
+//!
+//! An `accounts.json` next to the other files switches the bot into
+//! multi-account mode: each entry gets its own Telegram connection,
+//! scheduler, descriptions, state and session, as if it were started with
+//! a `--config-dir` of its own.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{BotPaths, ConfigError, TelegramConfig};
+
+/// Default file name of the multi-account configuration.
+pub const ACCOUNTS_FILE: &str = "accounts.json";
+
+/// One account of a multi-account configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountConfig {
+    /// Name used in logs and for the default state and settings files.
+    pub name: String,
+
+    /// Telegram session file.
+    pub session_path: PathBuf,
+
+    /// Descriptions JSON file.
+    pub descriptions_path: PathBuf,
+
+    /// State file (default: `<name>.state.json`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_path: Option<PathBuf>,
+
+    /// Telegram API ID (default: `TG_API_ID`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_id: Option<i32>,
+
+    /// Telegram API hash (default: `TG_API_HASH`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_hash: Option<String>,
+
+    /// Unix socket for this account's control interface (`CONTROL_SOCKET`
+    /// is ignored in multi-account mode).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_socket: Option<PathBuf>,
+}
+
+impl AccountConfig {
+    /// Resolves the account's files against `config_dir`, like
+    /// [`BotPaths::resolve`].
+    #[must_use]
+    pub fn paths(&self, config_dir: Option<&Path>) -> BotPaths {
+        let state = self
+            .state_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("{}.state.json", self.name)));
+        let mut paths = BotPaths::resolve(
+            config_dir,
+            Some(&self.descriptions_path),
+            Some(&state),
+            Some(&self.session_path),
+        );
+        paths.settings = config_dir
+            .unwrap_or_else(|| Path::new(""))
+            .join(format!("{}.settings.json", self.name));
+        paths
+    }
+
+    /// Builds the Telegram configuration, taking the API ID and hash from
+    /// `defaults` (the environment) unless the account sets its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither the account nor `defaults` provide the
+    /// credentials.
+    pub fn telegram_config(
+        &self,
+        defaults: Option<&TelegramConfig>,
+        session_path: PathBuf,
+    ) -> Result<TelegramConfig, ConfigError> {
+        let api_id = self
+            .api_id
+            .or_else(|| defaults.map(|d| d.api_id))
+            .ok_or(ConfigError::MissingEnvVar("TG_API_ID"))?;
+        let api_hash = self
+            .api_hash
+            .clone()
+            .or_else(|| defaults.map(|d| d.api_hash.clone()))
+            .ok_or(ConfigError::MissingEnvVar("TG_API_HASH"))?;
+
+        Ok(TelegramConfig {
+            session_path,
+            session_passphrase: defaults.and_then(|d| d.session_passphrase.clone()),
+            ..TelegramConfig::new(api_id, api_hash)
+        })
+    }
+}
+
+/// The accounts of a multi-account configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountsConfig {
+    /// Accounts to run, each independently.
+    pub accounts: Vec<AccountConfig>,
+}
+
+impl AccountsConfig {
+    /// Loads the accounts from a JSON file, `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or the
+    /// accounts are invalid (see [`Self::validate`]).
+    pub fn load(path: &Path, config_dir: Option<&Path>) -> Result<Option<Self>, ConfigError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(ConfigError::InvalidAccounts(format!(
+                    "cannot read {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+
+        let accounts: Self = serde_json::from_str(&content)
+            .map_err(|e| ConfigError::InvalidAccounts(format!("{}: {e}", path.display())))?;
+        accounts.validate(config_dir)?;
+        Ok(Some(accounts))
+    }
+
+    /// Checks that there is at least one account and that no two share a
+    /// name, session, state or descriptions file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem found.
+    pub fn validate(&self, config_dir: Option<&Path>) -> Result<(), ConfigError> {
+        if self.accounts.is_empty() {
+            return Err(ConfigError::InvalidAccounts(
+                "no accounts listed".to_owned(),
+            ));
+        }
+
+        let mut names = HashSet::new();
+        let mut files = HashSet::new();
+        for account in &self.accounts {
+            if account.name.trim().is_empty() {
+                return Err(ConfigError::InvalidAccounts(
+                    "an account has an empty name".to_owned(),
+                ));
+            }
+            if !names.insert(account.name.as_str()) {
+                return Err(ConfigError::InvalidAccounts(format!(
+                    "account '{}' is listed twice",
+                    account.name
+                )));
+            }
+
+            let paths = account.paths(config_dir);
+            for file in [paths.session, paths.state, paths.descriptions] {
+                if !files.insert(file.clone()) {
+                    return Err(ConfigError::InvalidAccounts(format!(
+                        "{} is used by more than one account",
+                        file.display()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(name: &str) -> AccountConfig {
+        AccountConfig {
+            name: name.to_owned(),
+            session_path: PathBuf::from(format!("{name}.session")),
+            descriptions_path: PathBuf::from(format!("{name}.json")),
+            state_path: None,
+            api_id: None,
+            api_hash: None,
+            control_socket: None,
+        }
+    }
+
+    #[test]
+    fn test_two_accounts_get_separate_files() {
+        let accounts = AccountsConfig {
+            accounts: vec![account("work"), account("home")],
+        };
+        let dir = Some(Path::new("/srv/bots"));
+        accounts.validate(dir).unwrap();
+
+        let [work, home] = [&accounts.accounts[0], &accounts.accounts[1]].map(|a| a.paths(dir));
+        assert_eq!(work.state, Path::new("/srv/bots/work.state.json"));
+        assert_eq!(home.state, Path::new("/srv/bots/home.state.json"));
+        assert_eq!(home.session, Path::new("/srv/bots/home.session"));
+        assert_eq!(home.settings, Path::new("/srv/bots/home.settings.json"));
+        assert_ne!(work.descriptions, home.descriptions);
+    }
+
+    #[test]
+    fn test_shared_files_rejected() {
+        let mut home = account("home");
+        home.state_path = Some(PathBuf::from("work.state.json"));
+        let accounts = AccountsConfig {
+            accounts: vec![account("work"), home],
+        };
+        assert!(matches!(
+            accounts.validate(None),
+            Err(ConfigError::InvalidAccounts(msg)) if msg.contains("work.state.json")
+        ));
+
+        let accounts = AccountsConfig {
+            accounts: vec![account("work"), account("work")],
+        };
+        assert!(accounts.validate(None).is_err());
+        assert!(AccountsConfig::default().validate(None).is_err());
+    }
+
+    #[test]
+    fn test_credentials_fall_back_to_environment() {
+        let defaults = TelegramConfig::new(1, "env-hash".to_owned());
+        let mut work = account("work");
+
+        let config = work
+            .telegram_config(Some(&defaults), PathBuf::from("work.session"))
+            .unwrap();
+        assert_eq!((config.api_id, config.api_hash.as_str()), (1, "env-hash"));
+        assert_eq!(config.session_path, Path::new("work.session"));
+
+        work.api_id = Some(2);
+        work.api_hash = Some("own-hash".to_owned());
+        let config = work.telegram_config(None, PathBuf::new()).unwrap();
+        assert_eq!((config.api_id, config.api_hash.as_str()), (2, "own-hash"));
+
+        assert!(
+            account("home")
+                .telegram_config(None, PathBuf::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_missing_file_is_single_account_mode() {
+        let path = std::env::temp_dir().join(format!("no_accounts_{}.json", std::process::id()));
+        assert_eq!(AccountsConfig::load(&path, None).unwrap(), None);
+    }
+}
@@ -0,0 +1,163 @@
+//! Append-only log of the commands that changed the descriptions.
+//!
+//! Each line of `descriptions.changelog` (next to the descriptions file)
+//! is one tab-separated entry: time, affected description ID (`-` for
+//! none) and the command as typed. The log keeps only the newest
+//! [`MAX_CHANGELOG_ENTRIES`] entries.
+
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// File extension of the changelog, replacing the descriptions file's.
+pub const CHANGELOG_EXTENSION: &str = "changelog";
+
+/// Entries kept in the changelog; older ones are dropped.
+pub const MAX_CHANGELOG_ENTRIES: usize = 500;
+
+/// One change to the descriptions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogEntry {
+    /// When the command ran.
+    pub at: DateTime<Utc>,
+
+    /// ID of the description the command changed, if it names one.
+    pub id: Option<String>,
+
+    /// The command as typed, without the prefix.
+    pub command: String,
+}
+
+impl ChangelogEntry {
+    /// Parses a line written by [`append_entry`], `None` if malformed.
+    #[must_use]
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(3, '\t');
+        let at = DateTime::parse_from_rfc3339(parts.next()?).ok()?.to_utc();
+        let id = parts.next()?;
+        let command = parts.next()?.replace("\\n", "\n");
+        Some(Self {
+            at,
+            id: (id != "-").then(|| id.to_owned()),
+            command,
+        })
+    }
+}
+
+impl fmt::Display for ChangelogEntry {
+    /// Formats the entry as one line, with newlines in the command escaped.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}",
+            self.at.format("%Y-%m-%dT%H:%M:%SZ"),
+            self.id.as_deref().unwrap_or("-"),
+            self.command.replace('\n', "\\n")
+        )
+    }
+}
+
+/// Returns the changelog for the descriptions file at `config_path`.
+#[must_use]
+pub fn changelog_path(config_path: impl AsRef<Path>) -> PathBuf {
+    config_path.as_ref().with_extension(CHANGELOG_EXTENSION)
+}
+
+/// Appends `entry` to the changelog at `path`, dropping the oldest entries
+/// beyond [`MAX_CHANGELOG_ENTRIES`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or written.
+pub fn append_entry(path: &Path, entry: &ChangelogEntry) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{entry}")?;
+    drop(file);
+
+    let content = std::fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() > MAX_CHANGELOG_ENTRIES {
+        let kept = &lines[lines.len() - MAX_CHANGELOG_ENTRIES..];
+        std::fs::write(path, kept.join("\n") + "\n")?;
+    }
+    Ok(())
+}
+
+/// Reads the last `count` entries of the changelog at `path`, oldest
+/// first. A missing file has no entries.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn read_entries(path: &Path, count: usize) -> std::io::Result<Vec<ChangelogEntry>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let entries: Vec<ChangelogEntry> = content
+        .lines()
+        .filter_map(ChangelogEntry::parse_line)
+        .collect();
+    let skip = entries.len().saturating_sub(count);
+    Ok(entries.into_iter().skip(skip).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: Option<&str>, command: &str) -> ChangelogEntry {
+        ChangelogEntry {
+            at: DateTime::from_timestamp(1_760_000_000, 0).unwrap(),
+            id: id.map(str::to_owned),
+            command: command.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_entry_line_roundtrip() {
+        let edit = entry(Some("working"), "edit working 💻 Busy");
+        assert_eq!(
+            edit.to_string(),
+            "2025-10-09T08:53:20Z\tworking\tedit working 💻 Busy"
+        );
+        assert_eq!(ChangelogEntry::parse_line(&edit.to_string()), Some(edit));
+
+        let bulk = entry(None, "bulk-add\na|60|A\nb|60|B");
+        assert_eq!(
+            bulk.to_string(),
+            "2025-10-09T08:53:20Z\t-\tbulk-add\\na|60|A\\nb|60|B"
+        );
+        assert_eq!(ChangelogEntry::parse_line(&bulk.to_string()), Some(bulk));
+        assert_eq!(ChangelogEntry::parse_line("not an entry"), None);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let path = std::env::temp_dir().join(format!("bounded_{}.changelog", std::process::id()));
+        for i in 0..=MAX_CHANGELOG_ENTRIES {
+            append_entry(&path, &entry(Some("a"), &format!("duration a {}", i + 1))).unwrap();
+        }
+
+        let entries = read_entries(&path, usize::MAX).unwrap();
+        assert_eq!(entries.len(), MAX_CHANGELOG_ENTRIES);
+        assert_eq!(entries[0].command, "duration a 2");
+
+        let last = read_entries(&path, 2).unwrap();
+        assert_eq!(
+            last[1].command,
+            format!("duration a {}", MAX_CHANGELOG_ENTRIES + 1)
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_entries(&path, 10).unwrap().is_empty());
+    }
+}
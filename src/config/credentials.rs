@@ -0,0 +1,146 @@
+//! API credentials kept in the OS keyring.
+//!
+//! Environment variables (and `.env`) stay the primary source; the keyring
+//! is only asked for a credential that is not set there. Keyring support is
+//! behind the `keyring` cargo feature.
+
+use super::ConfigError;
+
+/// Keyring service name the credentials are stored under.
+pub const KEYRING_SERVICE: &str = "description_bot";
+
+/// A store of named secrets.
+pub trait SecretStore {
+    /// Returns the secret stored under `name`, if any.
+    fn get(&self, name: &str) -> Option<String>;
+
+    /// Stores `value` under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is unavailable or refuses the value.
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError>;
+}
+
+/// Looks a credential up in the environment first, then in `store`.
+///
+/// Empty values count as unset.
+pub fn lookup(
+    name: &str,
+    env: impl Fn(&str) -> Option<String>,
+    store: &dyn SecretStore,
+) -> Option<String> {
+    env(name)
+        .filter(|v| !v.is_empty())
+        .or_else(|| store.get(name).filter(|v| !v.is_empty()))
+}
+
+/// A store that holds nothing (builds without the `keyring` feature).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoStore;
+
+impl SecretStore for NoStore {
+    fn get(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    fn set(&self, _name: &str, _value: &str) -> Result<(), ConfigError> {
+        Err(ConfigError::CredentialStore(
+            "built without keyring support (rebuild with --features keyring)".to_owned(),
+        ))
+    }
+}
+
+/// The OS keyring (Keychain, Windows Credential Manager, kernel keyutils).
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsKeyring;
+
+#[cfg(feature = "keyring")]
+impl SecretStore for OsKeyring {
+    fn get(&self, name: &str) -> Option<String> {
+        match keyring::Entry::new(KEYRING_SERVICE, name).and_then(|e| e.get_password()) {
+            Ok(value) => Some(value),
+            Err(keyring::Error::NoEntry) => None,
+            Err(e) => {
+                tracing::debug!("Keyring lookup of {} failed: {}", name, e);
+                None
+            }
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        keyring::Entry::new(KEYRING_SERVICE, name)
+            .and_then(|e| e.set_password(value))
+            .map_err(|e| ConfigError::CredentialStore(e.to_string()))
+    }
+}
+
+/// Returns the keyring of this build ([`NoStore`] without the feature).
+#[must_use]
+pub fn default_store() -> Box<dyn SecretStore> {
+    #[cfg(feature = "keyring")]
+    return Box::new(OsKeyring);
+
+    #[cfg(not(feature = "keyring"))]
+    Box::new(NoStore)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// An in-memory store for tests.
+    #[derive(Default)]
+    pub(crate) struct MemoryStore(RefCell<HashMap<String, String>>);
+
+    impl SecretStore for MemoryStore {
+        fn get(&self, name: &str) -> Option<String> {
+            self.0.borrow().get(name).cloned()
+        }
+
+        fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+            self.0
+                .borrow_mut()
+                .insert(name.to_owned(), value.to_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_env_wins_over_keyring() {
+        let store = MemoryStore::default();
+        store.set("TG_API_HASH", "from_keyring").unwrap();
+
+        let env = |name: &str| (name == "TG_API_HASH").then(|| "from_env".to_owned());
+        assert_eq!(
+            lookup("TG_API_HASH", env, &store).as_deref(),
+            Some("from_env")
+        );
+    }
+
+    #[test]
+    fn test_keyring_used_when_env_missing_or_empty() {
+        let store = MemoryStore::default();
+        store.set("TG_API_HASH", "from_keyring").unwrap();
+
+        assert_eq!(
+            lookup("TG_API_HASH", |_| None, &store).as_deref(),
+            Some("from_keyring")
+        );
+        assert_eq!(
+            lookup("TG_API_HASH", |_| Some(String::new()), &store).as_deref(),
+            Some("from_keyring")
+        );
+        assert_eq!(lookup("TG_API_ID", |_| None, &store), None);
+    }
+
+    #[test]
+    fn test_no_store_is_empty_and_read_only() {
+        assert_eq!(NoStore.get("TG_API_HASH"), None);
+        assert!(NoStore.set("TG_API_HASH", "x").is_err());
+    }
+}
@@ -1,11 +1,18 @@
 //! Description configuration and validation.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use unicode_segmentation::UnicodeSegmentation;
 
-use super::{MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM};
+use super::{
+    MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM, MAX_DURATION_SECS, OverlongPolicy, TimeWindow,
+    TimelineSegment,
+};
+use crate::util::retry_io;
 
 /// Errors that can occur during description validation.
 #[derive(Debug, Error)]
@@ -20,14 +27,38 @@ pub enum ValidationError {
         max_length: usize,
     },
 
+    #[error(
+        "Description at index {index} (id: {id}) has invalid max_len_override {max_len}: must be 1 to {hard_max}"
+    )]
+    InvalidMaxLength {
+        index: usize,
+        id: String,
+        max_len: usize,
+        hard_max: usize,
+    },
+
     #[error("Description at index {index} (id: {id}) is empty")]
     Empty { index: usize, id: String },
 
+    #[error("Description at index {index} (id: {id}) contains a control character (U+{code:04X})")]
+    InvalidCharacter { index: usize, id: String, code: u32 },
+
+    #[error("Description at index {index} (id: {id}) contains an embedded object (U+FFFC)")]
+    EmbeddedObject { index: usize, id: String },
+
+    #[error(
+        "Description at index {index} (id: {id}) contains an invisible character (U+{code:04X})"
+    )]
+    InvisibleCharacter { index: usize, id: String, code: u32 },
+
+    #[error("Description ID is empty")]
+    EmptyId,
+
     #[error("Duplicate description ID found: {id}")]
     DuplicateId { id: String },
 
     #[error(
-        "Description at index {index} (id: {id}) has invalid duration: {duration_secs} seconds (must be > 0)"
+        "Description at index {index} (id: {id}) has invalid duration: {duration_secs} seconds (must be 1 to {MAX_DURATION_SECS})"
     )]
     InvalidDuration {
         index: usize,
@@ -35,9 +66,49 @@ pub enum ValidationError {
         duration_secs: u64,
     },
 
+    #[error(
+        "Description at index {index} (id: {id}) has invalid username '{username}' (5-32 letters, digits or underscores)"
+    )]
+    InvalidUsername {
+        index: usize,
+        id: String,
+        username: String,
+    },
+
+    #[error("Fallback description '{id}' not found in any profile")]
+    UnknownFallback { id: String },
+
     #[error("No descriptions configured")]
     NoDescriptions,
 
+    #[error("Unknown profile: {name}")]
+    UnknownProfile { name: String },
+
+    #[error("Too many descriptions in profile '{profile}': {count} > {max}")]
+    TooManyDescriptions {
+        profile: String,
+        count: usize,
+        max: usize,
+    },
+
+    #[error(
+        "Configuration file not found: {} (run with --generate-config to create an example)",
+        path.display()
+    )]
+    NotFound { path: PathBuf },
+
+    #[error(
+        "{} is a directory, not a configuration file (check --config / DESCRIPTIONS_PATH)",
+        path.display()
+    )]
+    IsDirectory { path: PathBuf },
+
+    #[error(
+        "Permission denied for {} (check the file's owner and permissions)",
+        path.display()
+    )]
+    PermissionDenied { path: PathBuf },
+
     #[error("Failed to read configuration file: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -45,6 +116,46 @@ pub enum ValidationError {
     ParseError(#[from] serde_json::Error),
 }
 
+impl ValidationError {
+    /// Returns step-by-step guidance for a first run without a descriptions
+    /// file, `None` for any other error.
+    #[must_use]
+    pub fn first_run_help(&self) -> Option<String> {
+        let Self::NotFound { path } = self else {
+            return None;
+        };
+        Some(format!(
+            "No descriptions file at {path}.\n\
+             \n\
+             The bot rotates the descriptions listed in this file. To get started:\n\
+             1. Run `description_bot --generate-config` to write descriptions.example.json\n\
+             2. Copy it to {path} and edit the descriptions\n\
+             3. Start the bot again\n\
+             \n\
+             If the file is elsewhere, point to it with --config (DESCRIPTIONS_PATH) or --config-dir.",
+            path = path.display()
+        ))
+    }
+}
+
+/// Where a random emoji from [`Description::emoji_pool`] is added.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Decoration {
+    /// Before the text: `🌙 Good night`.
+    #[default]
+    Prefix,
+    /// After the text: `Good night 🌙`.
+    Suffix,
+}
+
+impl Decoration {
+    #[allow(clippy::trivially_copy_pass_by_ref)] // Signature required by serde
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// A single description entry with its display duration.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Description {
@@ -56,23 +167,241 @@ pub struct Description {
 
     /// How long to display this description in seconds.
     pub duration_secs: u64,
+
+    /// Daily window during which this description is pinned.
+    /// Scheduled descriptions are skipped by the regular rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<TimeWindow>,
+
+    /// Days of the week this description is shown on (in `BOT_TIMEZONE`).
+    /// Empty means every day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<Weekday>,
+
+    /// Custom emoji document ID to set as emoji status (Premium only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji_status_id: Option<i64>,
+
+    /// Public @username to set with this description (without the `@`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Emoji of which one, picked at random, decorates the text each time
+    /// it is shown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emoji_pool: Vec<String>,
+
+    /// Where the emoji from `emoji_pool` goes.
+    #[serde(default, skip_serializing_if = "Decoration::is_default")]
+    pub decoration: Decoration,
+
+    /// Labels for restricting the rotation with the `only` command.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Once shown, stays until `skip` or `goto` moves on, regardless of
+    /// its duration.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sticky: bool,
+
+    /// Length limit checked instead of the account's free/premium one, for
+    /// texts meant for a context with another limit. Cannot exceed the
+    /// Telegram maximum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_len_override: Option<usize>,
 }
 
 impl Description {
     /// Creates a new description entry.
+    ///
+    /// Prefer [`Description::builder`], which names each argument.
     #[must_use]
     pub const fn new(id: String, text: String, duration_secs: u64) -> Self {
         Self {
             id,
             text,
             duration_secs,
+            schedule: None,
+            weekdays: Vec::new(),
+            emoji_status_id: None,
+            username: None,
+            emoji_pool: Vec::new(),
+            decoration: Decoration::Prefix,
+            tags: Vec::new(),
+            sticky: false,
+            max_len_override: None,
+        }
+    }
+
+    /// Starts building a description with named setters.
+    #[must_use]
+    pub fn builder() -> DescriptionBuilder {
+        DescriptionBuilder::default()
+    }
+
+    /// Checks if the description carries `tag` (case-insensitive).
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.to_lowercase();
+        self.tags.iter().any(|t| t.to_lowercase() == tag)
+    }
+
+    /// Checks if the description may be shown on the given day of the week.
+    #[must_use]
+    pub fn active_on(&self, weekday: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&weekday)
+    }
+
+    /// Returns `text` with an emoji from the pool added, chosen by `seed`.
+    ///
+    /// Without a pool the text is returned unchanged.
+    #[must_use]
+    pub fn decorate(&self, text: &str, seed: u64) -> String {
+        if self.emoji_pool.is_empty() {
+            return text.to_owned();
+        }
+        let pick = seed.checked_rem(self.emoji_pool.len() as u64).unwrap_or(0);
+        let emoji = &self.emoji_pool[usize::try_from(pick).unwrap_or(0)];
+        match self.decoration {
+            Decoration::Prefix => format!("{emoji} {text}"),
+            Decoration::Suffix => format!("{text} {emoji}"),
+        }
+    }
+
+    /// Returns the length in UTF-16 units with the longest emoji of the
+    /// pool added, which is what has to fit the bio limit.
+    #[must_use]
+    pub fn decorated_utf16_len(&self) -> usize {
+        let decoration = self
+            .emoji_pool
+            .iter()
+            .map(|emoji| utf16_len(emoji) + 1)
+            .max()
+            .unwrap_or(0);
+        utf16_len(normalize_bio(&self.text)) + decoration
+    }
+
+    /// Checks that the description can be put up as a bio under `config`.
+    ///
+    /// This is the single check shared by file loading and the commands
+    /// that add or change texts: non-empty, within the bio limit in UTF-16
+    /// units (decoration included), text-only, positive duration and a
+    /// well-formed username.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found. The reported index is the
+    /// description's position in the active set, or the end of the set
+    /// for a description that is not in it yet.
+    pub fn validate_against(&self, config: &DescriptionConfig) -> Result<(), ValidationError> {
+        let descriptions = config.active_descriptions();
+        let index = descriptions
+            .iter()
+            .position(|d| d.id == self.id)
+            .unwrap_or(descriptions.len());
+        self.validate_at(index, Some(config.max_bio_length()), config.max_lengths().1)
+    }
+
+    /// Returns the length limit of this description: its override, or
+    /// `account_max` (the account's free/premium limit).
+    #[must_use]
+    pub fn max_length(&self, account_max: usize) -> usize {
+        self.max_len_override.unwrap_or(account_max)
+    }
+
+    /// Checks the description at `index` of its set.
+    ///
+    /// Length is only checked when `max_length` is set; an override may
+    /// not exceed `hard_max`, the Telegram maximum.
+    fn validate_at(
+        &self,
+        index: usize,
+        max_length: Option<usize>,
+        hard_max: usize,
+    ) -> Result<(), ValidationError> {
+        let id = || self.id.clone();
+        let text = normalize_bio(&self.text);
+
+        if text.is_empty() {
+            return Err(ValidationError::Empty { index, id: id() });
+        }
+
+        if let Some(max_len) = self.max_len_override
+            && !(1..=hard_max).contains(&max_len)
+        {
+            return Err(ValidationError::InvalidMaxLength {
+                index,
+                id: id(),
+                max_len,
+                hard_max,
+            });
+        }
+
+        // Check length, with the longest possible decoration
+        let length = self.decorated_utf16_len();
+        if let Some(max_length) = max_length.map(|max| self.max_length(max))
+            && length > max_length
+        {
+            return Err(ValidationError::TooLong {
+                index,
+                id: id(),
+                length,
+                max_length,
+            });
+        }
+
+        // Control characters other than common whitespace
+        if let Some(ch) = text
+            .chars()
+            .find(|&ch| ch.is_control() && ch != '\n' && ch != '\t')
+        {
+            return Err(ValidationError::InvalidCharacter {
+                index,
+                id: id(),
+                code: u32::from(ch),
+            });
+        }
+
+        // Object replacement character, left behind by embedded media
+        if text.contains('\u{FFFC}') {
+            return Err(ValidationError::EmbeddedObject { index, id: id() });
+        }
+
+        // Zero-width characters that might hide content
+        if let Some(ch) = find_invisible(text) {
+            return Err(ValidationError::InvisibleCharacter {
+                index,
+                id: id(),
+                code: u32::from(ch),
+            });
+        }
+
+        if !(1..=MAX_DURATION_SECS).contains(&self.duration_secs) {
+            return Err(ValidationError::InvalidDuration {
+                index,
+                id: id(),
+                duration_secs: self.duration_secs,
+            });
         }
+
+        if let Some(username) = &self.username
+            && !is_valid_username(username)
+        {
+            return Err(ValidationError::InvalidUsername {
+                index,
+                id: id(),
+                username: username.clone(),
+            });
+        }
+
+        Ok(())
     }
 
-    /// Returns the character count of the description text.
+    /// Returns the character count of the description text as Telegram
+    /// stores it (see [`normalize_bio`]).
     #[must_use]
     pub fn char_count(&self) -> usize {
-        self.text.chars().count()
+        normalize_bio(&self.text).chars().count()
     }
 
     /// Checks if the description fits within the free user limit.
@@ -88,12 +417,307 @@ impl Description {
     }
 }
 
+/// Builds a [`Description`] field by field.
+#[derive(Debug, Clone, Default)]
+pub struct DescriptionBuilder {
+    id: String,
+    text: String,
+    duration_secs: u64,
+    schedule: Option<TimeWindow>,
+    weekdays: Vec<Weekday>,
+    emoji_status_id: Option<i64>,
+    username: Option<String>,
+    emoji_pool: Vec<String>,
+    decoration: Decoration,
+    tags: Vec<String>,
+    sticky: bool,
+    max_len_override: Option<usize>,
+}
+
+impl DescriptionBuilder {
+    /// Sets the unique identifier.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Sets the bio text.
+    #[must_use]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets how long the description is shown, in seconds.
+    #[must_use]
+    pub const fn duration_secs(mut self, duration_secs: u64) -> Self {
+        self.duration_secs = duration_secs;
+        self
+    }
+
+    /// Pins the description to a daily window.
+    #[must_use]
+    pub const fn schedule(mut self, window: TimeWindow) -> Self {
+        self.schedule = Some(window);
+        self
+    }
+
+    /// Limits the description to these days of the week.
+    #[must_use]
+    pub fn weekdays(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        self.weekdays = weekdays.into_iter().collect();
+        self
+    }
+
+    /// Sets the custom emoji status shown with the description.
+    #[must_use]
+    pub const fn emoji_status_id(mut self, emoji_status_id: i64) -> Self {
+        self.emoji_status_id = Some(emoji_status_id);
+        self
+    }
+
+    /// Sets the @username applied with the description (without the `@`).
+    #[must_use]
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Decorates the text with a random emoji from `pool` at `decoration`.
+    #[must_use]
+    pub fn emoji_pool(
+        mut self,
+        pool: impl IntoIterator<Item = impl Into<String>>,
+        decoration: Decoration,
+    ) -> Self {
+        self.emoji_pool = pool.into_iter().map(Into::into).collect();
+        self.decoration = decoration;
+        self
+    }
+
+    /// Sets the labels used by the `only` command.
+    #[must_use]
+    pub fn tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keeps the description on display until `skip` or `goto`.
+    #[must_use]
+    pub const fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self
+    }
+
+    /// Checks the text against `max_len` instead of the account's limit.
+    #[must_use]
+    pub const fn max_len_override(mut self, max_len: usize) -> Self {
+        self.max_len_override = Some(max_len);
+        self
+    }
+
+    /// Returns the description if its ID, text, duration and username are
+    /// valid.
+    ///
+    /// The length limit depends on the account, so it is checked by
+    /// [`DescriptionConfig::validate`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ValidationError`] found.
+    pub fn build(self) -> Result<Description, ValidationError> {
+        if self.id.trim().is_empty() {
+            return Err(ValidationError::EmptyId);
+        }
+        if normalize_bio(&self.text).is_empty() {
+            return Err(ValidationError::Empty {
+                index: 0,
+                id: self.id,
+            });
+        }
+        if !(1..=MAX_DURATION_SECS).contains(&self.duration_secs) {
+            return Err(ValidationError::InvalidDuration {
+                index: 0,
+                id: self.id,
+                duration_secs: self.duration_secs,
+            });
+        }
+        if let Some(username) = &self.username
+            && !is_valid_username(username)
+        {
+            return Err(ValidationError::InvalidUsername {
+                index: 0,
+                id: self.id,
+                username: username.clone(),
+            });
+        }
+
+        Ok(Description {
+            id: self.id,
+            text: self.text,
+            duration_secs: self.duration_secs,
+            schedule: self.schedule,
+            weekdays: self.weekdays,
+            emoji_status_id: self.emoji_status_id,
+            username: self.username,
+            emoji_pool: self.emoji_pool,
+            decoration: self.decoration,
+            tags: self.tags,
+            sticky: self.sticky,
+            max_len_override: self.max_len_override,
+        })
+    }
+}
+
+/// Checks if a username has a form Telegram accepts: 5-32 ASCII letters,
+/// digits or underscores.
+#[must_use]
+pub fn is_valid_username(username: &str) -> bool {
+    (5..=32).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Zero-width characters rejected in bios, since they can hide content.
+const INVISIBLE_CHARS: [char; 5] = [
+    '\u{200B}', // Zero-width space
+    '\u{200C}', // Zero-width non-joiner
+    '\u{200D}', // Zero-width joiner
+    '\u{2060}', // Word joiner
+    '\u{FEFF}', // BOM / Zero-width no-break space
+];
+
+/// Returns the first hidden zero-width character in `text`.
+///
+/// A zero-width joiner between two emoji is allowed: it builds sequences
+/// such as 👩‍💻 or 🏳️‍🌈.
+fn find_invisible(text: &str) -> Option<char> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.iter().enumerate().find_map(|(i, &ch)| {
+        let joins_emoji = ch == '\u{200D}'
+            && i > 0
+            && (is_emoji_part(chars[i - 1]) || chars[i - 1] == '\u{FE0F}')
+            && chars.get(i + 1).copied().is_some_and(is_emoji_part);
+        (INVISIBLE_CHARS.contains(&ch) && !joins_emoji).then_some(ch)
+    })
+}
+
+/// Checks if a character can be part of an emoji ZWJ sequence
+/// (pictographs, symbols such as ❤ or ♀, skin tone modifiers).
+fn is_emoji_part(ch: char) -> bool {
+    matches!(u32::from(ch), 0x2300..=0x2BFF | 0x1F000..=0x1FAFF)
+}
+
+/// Returns the bio as Telegram stores it: the server drops trailing
+/// whitespace from `about`, so the bot sends, compares and measures the
+/// trimmed text to match what it reads back.
+#[must_use]
+pub fn normalize_bio(text: &str) -> &str {
+    text.trim_end()
+}
+
+/// Returns the length of a text in UTF-16 code units (how Telegram counts).
+#[must_use]
+pub fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Truncates text to fit within `max_len` UTF-16 code units.
+///
+/// Cuts on a grapheme cluster boundary so emoji sequences stay intact and
+/// appends an ellipsis. Text that already fits is returned unchanged.
+#[must_use]
+pub fn truncate_to_limit(text: &str, max_len: usize) -> String {
+    const ELLIPSIS: char = '…';
+
+    if utf16_len(text) <= max_len {
+        return text.to_owned();
+    }
+    if max_len == 0 {
+        return String::new();
+    }
+
+    // Reserve one code unit for the ellipsis
+    let budget = max_len - ELLIPSIS.len_utf16();
+    let mut result = String::new();
+    let mut used = 0;
+
+    for grapheme in text.graphemes(true) {
+        let len = utf16_len(grapheme);
+        if used + len > budget {
+            break;
+        }
+        result.push_str(grapheme);
+        used += len;
+    }
+
+    result.truncate(result.trim_end().len());
+    result.push(ELLIPSIS);
+    result
+}
+
+/// Whether a description can be shown at a given time, and if not, why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Eligibility {
+    /// The description would be shown (in rotation or pinned by its window).
+    Eligible,
+
+    /// No description with this ID in the active profile.
+    NotFound,
+
+    /// Limited to other days of the week.
+    OtherWeekday(Vec<Weekday>),
+
+    /// Scheduled, and its window is not active.
+    OutsideWindow(TimeWindow),
+
+    /// Another description's schedule window is active (its ID).
+    PinnedElsewhere(String),
+
+    /// Rotation is limited to another tag by `only` (that tag).
+    OutsideTag(String),
+
+    /// The fallback, only shown when no other description is eligible.
+    Fallback,
+}
+
+impl std::fmt::Display for Eligibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Eligible => write!(f, "eligible"),
+            Self::NotFound => write!(f, "not found"),
+            Self::OtherWeekday(days) => {
+                let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+                write!(f, "only shown on {}", days.join(", "))
+            }
+            Self::OutsideWindow(window) => write!(f, "only shown during {window}"),
+            Self::PinnedElsewhere(id) => write!(f, "[{id}] is pinned by its schedule window"),
+            Self::OutsideTag(tag) => write!(f, "rotation is limited to tag '{tag}' (use 'all')"),
+            Self::Fallback => write!(f, "the fallback, only shown when nothing else is eligible"),
+        }
+    }
+}
+
+/// Name of the implicit profile backed by the top-level `descriptions` list.
+pub const DEFAULT_PROFILE: &str = "default";
+
 /// Configuration containing all descriptions.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DescriptionConfig {
-    /// List of descriptions to rotate through.
+    /// List of descriptions to rotate through (the default profile).
     pub descriptions: Vec<Description>,
 
+    /// Named alternative description sets.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, Vec<Description>>,
+
+    /// Name of the profile currently in rotation.
+    /// `None` means the top-level `descriptions` list is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+
     /// Whether the user has Telegram Premium (affects max bio length).
     /// When `auto_detect_premium` is true, this value is updated at runtime.
     #[serde(default)]
@@ -103,12 +727,44 @@ pub struct DescriptionConfig {
     /// Defaults to true for new configs.
     #[serde(default = "default_auto_detect")]
     pub auto_detect_premium: bool,
+
+    /// ID of the description shown when no other one is eligible.
+    /// It is left out of the rotation and ignores weekday and schedule
+    /// filters. Applies in every profile that has a description with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_id: Option<String>,
+
+    /// Bio limits reported by Telegram as (free, premium), once detected.
+    /// Not saved: [`MAX_BIO_LENGTH_FREE`] and [`MAX_BIO_LENGTH_PREMIUM`]
+    /// apply until [`Self::set_max_lengths`] is called.
+    #[serde(skip)]
+    pub server_max_lengths: Option<(usize, usize)>,
 }
 
 fn default_auto_detect() -> bool {
     true
 }
 
+/// Returns the backup path kept next to a configuration file (`<path>.bak`).
+#[must_use]
+pub fn backup_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut backup = path.as_ref().as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Turns a file I/O error into an actionable error for common causes.
+fn file_error(path: &Path, err: std::io::Error) -> ValidationError {
+    let path = path.to_owned();
+    match err.kind() {
+        std::io::ErrorKind::NotFound => ValidationError::NotFound { path },
+        std::io::ErrorKind::IsADirectory => ValidationError::IsDirectory { path },
+        std::io::ErrorKind::PermissionDenied => ValidationError::PermissionDenied { path },
+        _ if path.is_dir() => ValidationError::IsDirectory { path },
+        _ => ValidationError::IoError(err),
+    }
+}
+
 impl DescriptionConfig {
     /// Loads configuration from a JSON file.
     ///
@@ -116,98 +772,185 @@ impl DescriptionConfig {
     ///
     /// Returns an error if the file cannot be read or parsed.
     pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, ValidationError> {
-        let content = std::fs::read_to_string(path)?;
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| file_error(path, e))?;
         let config: Self = serde_json::from_str(&content)?;
         Ok(config)
     }
 
+    /// Loads configuration, falling back to the backup written by
+    /// [`Self::save_to_file`] if the file cannot be parsed.
+    ///
+    /// Returns the configuration and, if the backup was used, the error
+    /// that made the primary file unusable.
+    ///
+    /// # Errors
+    ///
+    /// Returns the primary file's error if it cannot be read, or if it
+    /// cannot be parsed and no valid backup exists.
+    pub fn load_with_fallback(
+        path: impl AsRef<Path>,
+    ) -> Result<(Self, Option<ValidationError>), ValidationError> {
+        let path = path.as_ref();
+        match Self::load_from_file(path) {
+            Ok(config) => Ok((config, None)),
+            Err(primary @ ValidationError::ParseError(_)) => {
+                match Self::load_from_file(backup_path(path)) {
+                    Ok(config) => Ok((config, Some(primary))),
+                    Err(_) => Err(primary),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Saves configuration to a JSON file.
     ///
+    /// The same content is also written to `<path>.bak`, which keeps the
+    /// last known-good configuration if the file is later corrupted.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns an error if the file or its backup cannot be written.
     pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), ValidationError> {
+        self.save_with_retry(path, 1)
+    }
+
+    /// Saves configuration like [`Self::save_to_file`], trying each write
+    /// up to `attempts` times while it fails with a transient error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its backup cannot be written.
+    pub fn save_with_retry(
+        &self,
+        path: impl AsRef<Path>,
+        attempts: u32,
+    ) -> Result<(), ValidationError> {
+        let path = path.as_ref();
         let content = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, content)?;
+        retry_io(attempts, || std::fs::write(path, &content)).map_err(|e| file_error(path, e))?;
+        let backup = backup_path(path);
+        retry_io(attempts, || std::fs::write(&backup, &content))
+            .map_err(|e| file_error(&backup, e))?;
         Ok(())
     }
 
     /// Validates all descriptions in the configuration.
     ///
+    /// Every profile is checked, with IDs required to be unique within
+    /// each profile (the same ID may appear in different profiles).
+    ///
     /// # Errors
     ///
     /// Returns the first validation error encountered.
     pub fn validate(&self) -> Result<(), ValidationError> {
-        if self.descriptions.is_empty() {
+        self.validate_with(OverlongPolicy::Reject)
+    }
+
+    /// Validates the configuration, skipping length checks when
+    /// over-limit text will be truncated at update time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first validation error encountered.
+    pub fn validate_with(&self, on_overlong: OverlongPolicy) -> Result<(), ValidationError> {
+        if let Some(name) = &self.active_profile
+            && !self.profiles.contains_key(name)
+        {
+            return Err(ValidationError::UnknownProfile { name: name.clone() });
+        }
+
+        if self.active_descriptions().is_empty() {
             return Err(ValidationError::NoDescriptions);
         }
 
-        let max_length = if self.is_premium {
-            MAX_BIO_LENGTH_PREMIUM
-        } else {
-            MAX_BIO_LENGTH_FREE
+        let max_length = match on_overlong {
+            OverlongPolicy::Reject => Some(self.max_bio_length()),
+            OverlongPolicy::Truncate => None,
         };
 
-        let mut seen_ids = std::collections::HashSet::new();
+        let hard_max = self.max_lengths().1;
+        Self::validate_set(&self.descriptions, max_length, hard_max)?;
+        for descriptions in self.profiles.values() {
+            Self::validate_set(descriptions, max_length, hard_max)?;
+        }
 
-        for (index, desc) in self.descriptions.iter().enumerate() {
-            // Check for duplicate IDs
-            if !seen_ids.insert(&desc.id) {
-                return Err(ValidationError::DuplicateId {
-                    id: desc.id.clone(),
-                });
-            }
+        if let Some(id) = &self.fallback_id
+            && !self.has_id_in_any_profile(id)
+        {
+            return Err(ValidationError::UnknownFallback { id: id.clone() });
+        }
 
-            // Check for empty text
-            if desc.text.is_empty() {
-                return Err(ValidationError::Empty {
-                    index,
-                    id: desc.id.clone(),
-                });
-            }
+        Ok(())
+    }
 
-            // Check length
-            let char_count = desc.char_count();
-            if char_count > max_length {
-                return Err(ValidationError::TooLong {
-                    index,
-                    id: desc.id.clone(),
-                    length: char_count,
-                    max_length,
+    /// Checks that no profile has more than `max` descriptions
+    /// (`None` is unlimited).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValidationError::TooManyDescriptions`] for the first
+    /// profile over the cap.
+    pub fn validate_count(&self, max: Option<usize>) -> Result<(), ValidationError> {
+        let Some(max) = max else {
+            return Ok(());
+        };
+
+        let sets = std::iter::once((DEFAULT_PROFILE, &self.descriptions))
+            .chain(self.profiles.iter().map(|(name, set)| (name.as_str(), set)));
+        for (profile, descriptions) in sets {
+            if descriptions.len() > max {
+                return Err(ValidationError::TooManyDescriptions {
+                    profile: profile.to_owned(),
+                    count: descriptions.len(),
+                    max,
                 });
             }
+        }
+        Ok(())
+    }
+
+    /// Validates a single description set (one profile).
+    ///
+    /// Length is only checked when `max_length` is set.
+    fn validate_set(
+        descriptions: &[Description],
+        max_length: Option<usize>,
+        hard_max: usize,
+    ) -> Result<(), ValidationError> {
+        let mut seen_ids = std::collections::HashSet::new();
 
-            // Check duration
-            if desc.duration_secs == 0 {
-                return Err(ValidationError::InvalidDuration {
-                    index,
+        for (index, desc) in descriptions.iter().enumerate() {
+            // Check for duplicate IDs
+            if !seen_ids.insert(&desc.id) {
+                return Err(ValidationError::DuplicateId {
                     id: desc.id.clone(),
-                    duration_secs: desc.duration_secs,
                 });
             }
+
+            desc.validate_at(index, max_length, hard_max)?;
         }
 
         Ok(())
     }
 
-    /// Returns detailed validation results for all descriptions.
+    /// Returns detailed validation results for all descriptions
+    /// in the active profile.
     #[must_use]
     pub fn validate_all(&self) -> Vec<Result<(), ValidationError>> {
-        let max_length = if self.is_premium {
-            MAX_BIO_LENGTH_PREMIUM
-        } else {
-            MAX_BIO_LENGTH_FREE
-        };
+        let max_length = self.max_bio_length();
+        let hard_max = self.max_lengths().1;
 
         let mut results = Vec::new();
         let mut seen_ids = std::collections::HashSet::new();
 
-        if self.descriptions.is_empty() {
+        if self.active_descriptions().is_empty() {
             results.push(Err(ValidationError::NoDescriptions));
             return results;
         }
 
-        for (index, desc) in self.descriptions.iter().enumerate() {
+        for (index, desc) in self.active_descriptions().iter().enumerate() {
             // Check for duplicate IDs
             if !seen_ids.insert(&desc.id) {
                 results.push(Err(ValidationError::DuplicateId {
@@ -216,59 +959,316 @@ impl DescriptionConfig {
                 continue;
             }
 
-            // Check for empty text
-            if desc.text.is_empty() {
-                results.push(Err(ValidationError::Empty {
-                    index,
-                    id: desc.id.clone(),
-                }));
-                continue;
-            }
+            results.push(desc.validate_at(index, Some(max_length), hard_max));
+        }
 
-            // Check length
-            let char_count = desc.char_count();
-            if char_count > max_length {
-                results.push(Err(ValidationError::TooLong {
-                    index,
-                    id: desc.id.clone(),
-                    length: char_count,
-                    max_length,
-                }));
-                continue;
-            }
+        results
+    }
 
-            // Check duration
-            if desc.duration_secs == 0 {
-                results.push(Err(ValidationError::InvalidDuration {
-                    index,
-                    id: desc.id.clone(),
-                    duration_secs: desc.duration_secs,
-                }));
-                continue;
+    /// Returns non-fatal configuration issues worth reporting.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if !self.is_premium {
+            let with_status: Vec<&str> = self
+                .active_descriptions()
+                .iter()
+                .filter(|d| d.emoji_status_id.is_some())
+                .map(|d| d.id.as_str())
+                .collect();
+
+            if !with_status.is_empty() {
+                warnings.push(format!(
+                    "Emoji statuses require Telegram Premium and will be skipped: {}",
+                    with_status.join(", ")
+                ));
             }
+        }
+
+        warnings
+    }
 
-            results.push(Ok(()));
+    /// Returns the descriptions of the active profile.
+    ///
+    /// Falls back to the top-level list if the active profile does not exist.
+    #[must_use]
+    pub fn active_descriptions(&self) -> &[Description] {
+        self.active_profile
+            .as_ref()
+            .and_then(|name| self.profiles.get(name))
+            .unwrap_or(&self.descriptions)
+    }
+
+    /// Returns the descriptions of the active profile for modification.
+    pub fn active_descriptions_mut(&mut self) -> &mut Vec<Description> {
+        match self.active_profile.as_ref() {
+            Some(name) if self.profiles.contains_key(name) => self
+                .profiles
+                .get_mut(name)
+                .unwrap_or(&mut self.descriptions),
+            _ => &mut self.descriptions,
         }
+    }
 
-        results
+    /// Returns the name of the active profile.
+    #[must_use]
+    pub fn active_profile_name(&self) -> &str {
+        self.active_profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Returns the names of all profiles, starting with the default one.
+    #[must_use]
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names.insert(0, DEFAULT_PROFILE);
+        names
+    }
+
+    /// Switches the active profile.
+    ///
+    /// `"default"` selects the top-level `descriptions` list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no profile with the given name exists.
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), ValidationError> {
+        if name == DEFAULT_PROFILE {
+            self.active_profile = None;
+            return Ok(());
+        }
+
+        if !self.profiles.contains_key(name) {
+            return Err(ValidationError::UnknownProfile {
+                name: name.to_owned(),
+            });
+        }
+
+        self.active_profile = Some(name.to_owned());
+        Ok(())
+    }
+
+    /// Returns an ID based on `base` that is not used in the active profile.
+    ///
+    /// Appends `_2`, `_3`, ... if `base` is already taken.
+    #[must_use]
+    pub fn unique_id(&self, base: &str) -> String {
+        let taken = |id: &str| self.active_descriptions().iter().any(|d| d.id == id);
+
+        if !taken(base) {
+            return base.to_owned();
+        }
+
+        // One of the first len + 1 suffixes is always free
+        (2..=self.len() + 2)
+            .map(|n| format!("{base}_{n}"))
+            .find(|id| !taken(id))
+            .unwrap_or_else(|| base.to_owned())
+    }
+
+    /// Returns the index of the description pinned at the given local time.
+    ///
+    /// When windows overlap, the description listed first wins. Windows of
+    /// descriptions not active on that weekday are ignored.
+    #[must_use]
+    pub fn scheduled_index_at(&self, at: NaiveDateTime) -> Option<usize> {
+        let fallback = self.fallback_index();
+        self.active_descriptions()
+            .iter()
+            .enumerate()
+            .position(|(i, d)| {
+                Some(i) != fallback
+                    && d.active_on(at.weekday())
+                    && d.schedule.is_some_and(|w| w.contains(at.time()))
+            })
+    }
+
+    /// Returns how many descriptions of the active profile carry `tag`.
+    #[must_use]
+    pub fn count_tagged(&self, tag: &str) -> usize {
+        self.active_descriptions()
+            .iter()
+            .filter(|d| d.has_tag(tag))
+            .count()
+    }
+
+    /// Returns the index of the fallback description in the active
+    /// profile, if one is configured there.
+    #[must_use]
+    pub fn fallback_index(&self) -> Option<usize> {
+        let id = self.fallback_id.as_deref()?;
+        self.active_descriptions().iter().position(|d| d.id == id)
     }
 
-    /// Gets a description by its index.
+    /// Checks if any profile has a description with the given ID.
+    #[must_use]
+    pub fn has_id_in_any_profile(&self, id: &str) -> bool {
+        std::iter::once(&self.descriptions)
+            .chain(self.profiles.values())
+            .any(|set| set.iter().any(|d| d.id == id))
+    }
+
+    /// Checks whether a description of the active profile can be shown at
+    /// `now` in `tz` with rotation limited to `active_tag` (`only`),
+    /// reporting the first filter that excludes it.
+    #[must_use]
+    pub fn eligibility<Tz: TimeZone>(
+        &self,
+        id: &str,
+        active_tag: Option<&str>,
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Eligibility {
+        let Some(index) = self.active_descriptions().iter().position(|d| d.id == id) else {
+            return Eligibility::NotFound;
+        };
+        let Some(desc) = self.get(index) else {
+            return Eligibility::NotFound;
+        };
+        let local = now.with_timezone(tz).naive_local();
+
+        if self.fallback_index() == Some(index) {
+            return Eligibility::Fallback;
+        }
+
+        if !desc.active_on(local.weekday()) {
+            return Eligibility::OtherWeekday(desc.weekdays.clone());
+        }
+
+        if let Some(window) = desc.schedule
+            && !window.contains(local.time())
+        {
+            return Eligibility::OutsideWindow(window);
+        }
+
+        // Schedule windows pin a description whatever the tag
+        if desc.schedule.is_none()
+            && let Some(tag) = active_tag
+            && !desc.has_tag(tag)
+        {
+            return Eligibility::OutsideTag(tag.to_owned());
+        }
+
+        match self.scheduled_index_at(local) {
+            Some(pinned) if pinned != index => {
+                self.get(pinned).map_or(Eligibility::Eligible, |d| {
+                    Eligibility::PinnedElsewhere(d.id.clone())
+                })
+            }
+            _ => Eligibility::Eligible,
+        }
+    }
+
+    /// Computes which description is pinned over `[from, to)` in `tz`.
+    ///
+    /// Returns ordered, non-overlapping segments covering the whole range.
+    /// Segments without a description ID are regular rotation.
+    #[must_use]
+    pub fn timeline<Tz: TimeZone>(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Vec<TimelineSegment<Tz>> {
+        if from >= to {
+            return Vec::new();
+        }
+
+        // The decision can only change where some window opens or closes,
+        // or at midnight if scheduled descriptions are limited to weekdays
+        let scheduled = || {
+            self.active_descriptions()
+                .iter()
+                .filter(|d| d.schedule.is_some())
+        };
+        let mut times: Vec<NaiveTime> = scheduled()
+            .filter_map(|d| d.schedule)
+            .flat_map(|w| [w.start, w.end])
+            .collect();
+        if scheduled().any(|d| !d.weekdays.is_empty()) {
+            times.push(NaiveTime::MIN);
+        }
+
+        let mut points = vec![from];
+        let last_day = to.with_timezone(tz).date_naive();
+        for day in from.with_timezone(tz).date_naive().iter_days() {
+            if day > last_day {
+                break;
+            }
+            for &time in &times {
+                let Some(local) = tz.from_local_datetime(&day.and_time(time)).earliest() else {
+                    continue; // Skipped by a DST transition
+                };
+                let point = local.with_timezone(&Utc);
+                if from < point && point < to {
+                    points.push(point);
+                }
+            }
+        }
+        points.sort_unstable();
+        points.dedup();
+
+        let mut segments: Vec<TimelineSegment<Tz>> = Vec::new();
+        for (i, start) in points.iter().enumerate() {
+            let end = points.get(i + 1).unwrap_or(&to).with_timezone(tz);
+            let start = start.with_timezone(tz);
+            let description_id = self
+                .scheduled_index_at(start.naive_local())
+                .and_then(|index| self.get(index))
+                .map(|d| d.id.clone());
+
+            match segments.last_mut() {
+                Some(last) if last.description_id == description_id => last.end = end,
+                _ => segments.push(TimelineSegment {
+                    start,
+                    end,
+                    description_id,
+                }),
+            }
+        }
+
+        segments
+    }
+
+    /// Gets a description of the active profile by its index.
     #[must_use]
     pub fn get(&self, index: usize) -> Option<&Description> {
-        self.descriptions.get(index)
+        self.active_descriptions().get(index)
     }
 
-    /// Returns the number of descriptions.
+    /// Returns the number of descriptions in the active profile.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.descriptions.len()
+        self.active_descriptions().len()
     }
 
-    /// Checks if there are no descriptions.
+    /// Checks if the active profile has no descriptions.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.descriptions.is_empty()
+        self.active_descriptions().is_empty()
+    }
+
+    /// Returns a copy to start another account from.
+    ///
+    /// Account-specific fields are reset: premium status is detected anew,
+    /// the default profile is active, and usernames are dropped since no two
+    /// accounts can share one.
+    #[must_use]
+    pub fn for_new_account(&self) -> Self {
+        let mut config = self.clone();
+        config.active_profile = None;
+        config.is_premium = false;
+        config.auto_detect_premium = true;
+
+        let all = config
+            .descriptions
+            .iter_mut()
+            .chain(config.profiles.values_mut().flatten());
+        for desc in all {
+            desc.username = None;
+        }
+        config
     }
 
     /// Creates an example configuration for users to reference.
@@ -292,8 +1292,12 @@ impl DescriptionConfig {
                     3600, // 1 hour
                 ),
             ],
+            profiles: HashMap::new(),
+            active_profile: None,
             is_premium: false,
             auto_detect_premium: true,
+            fallback_id: None,
+            server_max_lengths: None,
         }
     }
 
@@ -302,14 +1306,40 @@ impl DescriptionConfig {
         self.is_premium = is_premium;
     }
 
+    /// Chooses the premium status to assume when auto-detection failed.
+    ///
+    /// If any description (in any profile) only fits the premium limit,
+    /// assuming Free would fail validation and abort startup, so Premium is
+    /// assumed; Telegram rejects over-limit bios later if that was wrong.
+    /// Otherwise the configured value is kept.
+    #[must_use]
+    pub fn premium_fallback(&self) -> bool {
+        self.is_premium
+            || self
+                .descriptions
+                .iter()
+                .chain(self.profiles.values().flatten())
+                .any(|d| !d.fits_free_limit())
+    }
+
+    /// Uses the bio limits reported by Telegram instead of the built-in
+    /// 70/140 for all length checks.
+    pub fn set_max_lengths(&mut self, free: usize, premium: usize) {
+        self.server_max_lengths = Some((free, premium));
+    }
+
+    /// Returns the (free, premium) bio limits in effect.
+    #[must_use]
+    pub fn max_lengths(&self) -> (usize, usize) {
+        self.server_max_lengths
+            .unwrap_or((MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM))
+    }
+
     /// Returns the maximum bio length based on premium status.
     #[must_use]
     pub fn max_bio_length(&self) -> usize {
-        if self.is_premium {
-            MAX_BIO_LENGTH_PREMIUM
-        } else {
-            MAX_BIO_LENGTH_FREE
-        }
+        let (free, premium) = self.max_lengths();
+        if self.is_premium { premium } else { free }
     }
 }
 
@@ -317,6 +1347,95 @@ impl DescriptionConfig {
 mod tests {
     use super::*;
 
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "description_bot_config_test_{}_{name}.json",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_clone_for_new_account_normalizes_and_round_trips() {
+        let mut config = profile_config();
+        config.switch_profile("work").unwrap();
+        config.is_premium = true;
+        config.auto_detect_premium = false;
+        config.descriptions[0].username = Some("alex_home".to_owned());
+        config.profiles.get_mut("work").unwrap()[0].username = Some("alex_work".to_owned());
+
+        let clone = config.for_new_account();
+        assert_eq!(clone.active_profile, None);
+        assert!(!clone.is_premium);
+        assert!(clone.auto_detect_premium);
+        assert!(clone.descriptions[0].username.is_none());
+        assert!(clone.profiles["work"][0].username.is_none());
+        assert_eq!(clone.descriptions[0].text, config.descriptions[0].text);
+
+        let path = temp_path("clone");
+        clone.save_to_file(&path).unwrap();
+        let loaded = DescriptionConfig::load_from_file(&path).unwrap();
+        assert_eq!(loaded.descriptions, clone.descriptions);
+        assert_eq!(loaded.profiles, clone.profiles);
+        assert_eq!(loaded.active_profile, None);
+        assert!(loaded.auto_detect_premium);
+
+        let _ = std::fs::remove_file(backup_path(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_save_writes_backup() {
+        let path = temp_path("backup");
+        let config = DescriptionConfig::example();
+        config.save_to_file(&path).unwrap();
+
+        assert!(backup_path(&path).ends_with(format!(
+            "description_bot_config_test_{}_backup.json.bak",
+            std::process::id()
+        )));
+        let backup = DescriptionConfig::load_from_file(backup_path(&path)).unwrap();
+        assert_eq!(backup.len(), config.len());
+
+        let _ = std::fs::remove_file(backup_path(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_falls_back_to_backup() {
+        let path = temp_path("fallback");
+        DescriptionConfig::example().save_to_file(&path).unwrap();
+        std::fs::write(&path, "{ \"descriptions\": [").unwrap();
+
+        let (config, primary_error) = DescriptionConfig::load_with_fallback(&path).unwrap();
+        assert_eq!(config.len(), 3);
+        assert!(matches!(
+            primary_error,
+            Some(ValidationError::ParseError(_))
+        ));
+
+        // Without a usable backup the primary error is returned
+        std::fs::remove_file(backup_path(&path)).unwrap();
+        assert!(matches!(
+            DescriptionConfig::load_with_fallback(&path),
+            Err(ValidationError::ParseError(_))
+        ));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_with_fallback_prefers_primary() {
+        let path = temp_path("primary");
+        DescriptionConfig::example().save_to_file(&path).unwrap();
+
+        let (config, primary_error) = DescriptionConfig::load_with_fallback(&path).unwrap();
+        assert_eq!(config.len(), 3);
+        assert!(primary_error.is_none());
+
+        let _ = std::fs::remove_file(backup_path(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_description_char_count() {
         let desc = Description::new("test".to_owned(), "Hello, World!".to_owned(), 60);
@@ -330,6 +1449,240 @@ mod tests {
         assert_eq!(desc.char_count(), 8); // "Hello " (6) + 2 emoji = 8
     }
 
+    #[test]
+    fn test_builder_builds_valid_description() {
+        let desc = Description::builder()
+            .id("gym")
+            .text("🏋️ At the gym")
+            .duration_secs(1800)
+            .weekdays([Weekday::Mon, Weekday::Thu])
+            .username("gym_rat_42")
+            .build()
+            .unwrap();
+
+        assert_eq!(desc.id, "gym");
+        assert_eq!(desc.text, "🏋️ At the gym");
+        assert_eq!(desc.duration_secs, 1800);
+        assert_eq!(desc.weekdays, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(desc.username.as_deref(), Some("gym_rat_42"));
+        assert!(desc.schedule.is_none());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_fields() {
+        let valid = || Description::builder().id("a").text("A").duration_secs(60);
+
+        assert!(matches!(
+            valid().text("").build(),
+            Err(ValidationError::Empty { .. })
+        ));
+        assert!(matches!(
+            valid().id(" ").build(),
+            Err(ValidationError::EmptyId)
+        ));
+        assert!(matches!(
+            valid().duration_secs(0).build(),
+            Err(ValidationError::InvalidDuration { .. })
+        ));
+        assert!(valid().duration_secs(MAX_DURATION_SECS).build().is_ok());
+        assert!(matches!(
+            valid().duration_secs(u64::MAX - 10).build(),
+            Err(ValidationError::InvalidDuration { .. })
+        ));
+        assert!(matches!(
+            valid().username("no").build(),
+            Err(ValidationError::InvalidUsername { .. })
+        ));
+    }
+
+    #[test]
+    fn test_trailing_whitespace_is_not_counted() {
+        assert_eq!(normalize_bio("Hello  \n\t"), "Hello");
+        assert_eq!(normalize_bio("  Hello"), "  Hello");
+
+        // 70 chars plus trailing spaces still fit the free limit
+        let desc = Description::new("pad".to_owned(), format!("{}   ", "a".repeat(70)), 60);
+        assert_eq!(desc.char_count(), 70);
+        assert!(desc.fits_free_limit());
+
+        // Whitespace only is empty once trimmed
+        let config = DescriptionConfig {
+            descriptions: vec![Description::new("blank".to_owned(), "   ".to_owned(), 60)],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::Empty { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decorate_picks_from_pool() {
+        let desc = Description::builder()
+            .id("night")
+            .text("Good night")
+            .duration_secs(60)
+            .emoji_pool(["🌙", "⭐"], Decoration::Prefix)
+            .build()
+            .unwrap();
+
+        for seed in 0..10 {
+            let decorated = desc.decorate(&desc.text, seed);
+            assert!(
+                decorated == "🌙 Good night" || decorated == "⭐ Good night",
+                "{decorated}"
+            );
+        }
+        assert_ne!(desc.decorate(&desc.text, 0), desc.decorate(&desc.text, 1));
+
+        let suffix = Description {
+            decoration: Decoration::Suffix,
+            ..desc
+        };
+        assert_eq!(suffix.decorate("Hi", 1), "Hi ⭐");
+
+        // No pool, no decoration
+        let plain = Description::new("a".to_owned(), "A".to_owned(), 60);
+        assert_eq!(plain.decorate("A", 7), "A");
+    }
+
+    #[test]
+    fn test_max_len_override_replaces_account_limit() {
+        let with_text = |len: usize, max_len: usize| DescriptionConfig {
+            descriptions: vec![
+                Description::builder()
+                    .id("channel")
+                    .text("a".repeat(len))
+                    .duration_secs(60)
+                    .max_len_override(max_len)
+                    .build()
+                    .unwrap(),
+            ],
+            ..DescriptionConfig::default()
+        };
+
+        // Longer than the free limit, but within the override
+        let config = with_text(100, 120);
+        assert!(!config.is_premium);
+        config.validate().unwrap();
+
+        // Shorter than the free limit, but over a stricter override
+        assert!(matches!(
+            with_text(50, 40).validate(),
+            Err(ValidationError::TooLong { max_length: 40, .. })
+        ));
+
+        // Never beyond what Telegram allows at all
+        assert!(matches!(
+            with_text(10, MAX_BIO_LENGTH_PREMIUM + 1).validate(),
+            Err(ValidationError::InvalidMaxLength { hard_max, .. }) if hard_max == MAX_BIO_LENGTH_PREMIUM
+        ));
+        assert!(with_text(10, 0).validate().is_err());
+    }
+
+    #[test]
+    fn test_decoration_counts_against_the_limit() {
+        let decorated = |len: usize| DescriptionConfig {
+            descriptions: vec![
+                Description::builder()
+                    .id("d")
+                    .text("a".repeat(len))
+                    .duration_secs(60)
+                    .emoji_pool(["🌙", "🏋️"], Decoration::Suffix)
+                    .build()
+                    .unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        // The widest emoji ("🏋️" is three UTF-16 units) plus a space must fit
+        let fits = decorated(MAX_BIO_LENGTH_FREE - 4);
+        assert_eq!(
+            fits.descriptions[0].decorated_utf16_len(),
+            MAX_BIO_LENGTH_FREE
+        );
+        assert!(fits.validate().is_ok());
+        for seed in 0..4 {
+            let desc = &fits.descriptions[0];
+            assert!(utf16_len(&desc.decorate(&desc.text, seed)) <= MAX_BIO_LENGTH_FREE);
+        }
+
+        assert!(matches!(
+            decorated(MAX_BIO_LENGTH_FREE - 3).validate(),
+            Err(ValidationError::TooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_zero_width_text_rejected_at_load() {
+        let path = temp_path("zero_width");
+        let mut config = DescriptionConfig::example();
+        config.descriptions[1].text = "Hidden\u{200B}text".to_owned();
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+
+        let loaded = DescriptionConfig::load_from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        // Even when overlong text would be truncated, the characters are checked
+        for policy in [OverlongPolicy::Reject, OverlongPolicy::Truncate] {
+            assert!(matches!(
+                loaded.validate_with(policy),
+                Err(ValidationError::InvisibleCharacter {
+                    index: 1,
+                    code: 0x200B,
+                    ..
+                })
+            ));
+        }
+        assert!(matches!(
+            config.validate_all()[1],
+            Err(ValidationError::InvisibleCharacter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_zwj_emoji_sequences_load() {
+        let path = temp_path("zwj_emoji");
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].text = "👩\u{200D}💻 Coding".to_owned();
+        config.descriptions[1].text = "🏳\u{FE0F}\u{200D}🌈 👨\u{200D}👩\u{200D}👧".to_owned();
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+
+        let loaded = DescriptionConfig::load_from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(loaded.validate().is_ok());
+
+        // A joiner between letters still hides content
+        config.descriptions[1].text = "Hidden\u{200D}text".to_owned();
+        assert!(matches!(
+            config.validate_all()[1],
+            Err(ValidationError::InvisibleCharacter { code: 0x200D, .. })
+        ));
+        config.descriptions[1].text = "👩\u{200D}".to_owned();
+        assert!(config.validate_all()[1].is_err());
+    }
+
+    #[test]
+    fn test_validate_against_matches_file_load() {
+        let config = DescriptionConfig::example();
+        let ok = Description::new("new".to_owned(), "Fine".to_owned(), 60);
+        assert!(ok.validate_against(&config).is_ok());
+
+        let control = Description::new("new".to_owned(), "Bell\u{7}".to_owned(), 60);
+        assert!(matches!(
+            control.validate_against(&config),
+            Err(ValidationError::InvalidCharacter { code: 0x7, index, .. })
+                if index == config.len()
+        ));
+
+        // Position in the active set is reported for existing descriptions
+        let mut existing = config.descriptions[1].clone();
+        existing.duration_secs = 0;
+        assert!(matches!(
+            existing.validate_against(&config),
+            Err(ValidationError::InvalidDuration { index: 1, .. })
+        ));
+    }
+
     #[test]
     fn test_validation_empty_descriptions() {
         let config = DescriptionConfig {
@@ -391,4 +1744,541 @@ mod tests {
             Err(ValidationError::InvalidDuration { .. })
         ));
     }
+
+    #[test]
+    fn test_validation_username() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].username = Some("alex_at_work".to_owned());
+        assert!(config.validate().is_ok());
+
+        for bad in [
+            "abcd",
+            "@alex_at_work",
+            "alex-work",
+            "имя_пользователя",
+            &"a".repeat(33),
+        ] {
+            config.descriptions[0].username = Some(bad.to_owned());
+            assert!(
+                matches!(
+                    config.validate(),
+                    Err(ValidationError::InvalidUsername { .. })
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    fn profile_config() -> DescriptionConfig {
+        let mut config = DescriptionConfig {
+            descriptions: vec![Description::new(
+                "home".to_owned(),
+                "At home".to_owned(),
+                60,
+            )],
+            ..Default::default()
+        };
+        config.profiles.insert(
+            "work".to_owned(),
+            vec![
+                Description::new("desk".to_owned(), "At my desk".to_owned(), 60),
+                Description::new("meeting".to_owned(), "In a meeting".to_owned(), 60),
+            ],
+        );
+        config
+    }
+
+    #[test]
+    fn test_validation_honors_server_limits() {
+        let mut config = DescriptionConfig {
+            descriptions: vec![Description::new(
+                "long".to_owned(),
+                "a".repeat(MAX_BIO_LENGTH_FREE + 10),
+                60,
+            )],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::TooLong { max_length, .. }) if max_length == MAX_BIO_LENGTH_FREE
+        ));
+
+        config.set_max_lengths(MAX_BIO_LENGTH_FREE + 10, 200);
+        assert_eq!(config.max_bio_length(), MAX_BIO_LENGTH_FREE + 10);
+        assert!(config.validate().is_ok());
+
+        config.set_max_lengths(50, 200);
+        assert!(config.validate().is_err());
+        config.set_premium(true);
+        assert_eq!(config.max_bio_length(), 200);
+        assert!(config.validate().is_ok());
+
+        // Detected limits are not written to the file
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("200"), "{json}");
+    }
+
+    #[test]
+    fn test_fallback_must_exist_in_some_profile() {
+        let mut config = profile_config();
+        config.fallback_id = Some("meeting".to_owned());
+        assert!(config.validate().is_ok());
+        // Only applies in profiles that have it
+        assert_eq!(config.fallback_index(), None);
+        config.switch_profile("work").unwrap();
+        assert_eq!(config.fallback_index(), Some(1));
+
+        config.fallback_id = Some("lunch".to_owned());
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::UnknownFallback { id }) if id == "lunch"
+        ));
+    }
+
+    #[test]
+    fn test_switch_profile() {
+        let mut config = profile_config();
+        assert_eq!(config.active_profile_name(), DEFAULT_PROFILE);
+        assert_eq!(config.len(), 1);
+
+        config.switch_profile("work").unwrap();
+        assert_eq!(config.active_profile_name(), "work");
+        assert_eq!(config.len(), 2);
+        assert_eq!(config.get(0).unwrap().id, "desk");
+
+        config.switch_profile(DEFAULT_PROFILE).unwrap();
+        assert!(config.active_profile.is_none());
+        assert_eq!(config.get(0).unwrap().id, "home");
+    }
+
+    #[test]
+    fn test_switch_unknown_profile() {
+        let mut config = profile_config();
+        assert!(matches!(
+            config.switch_profile("vacation"),
+            Err(ValidationError::UnknownProfile { .. })
+        ));
+        assert_eq!(config.active_profile_name(), DEFAULT_PROFILE);
+    }
+
+    #[test]
+    fn test_validation_ids_unique_per_profile() {
+        let mut config = profile_config();
+        // Same ID in a different profile is fine
+        config.profiles.insert(
+            "travel".to_owned(),
+            vec![Description::new(
+                "home".to_owned(),
+                "Going home".to_owned(),
+                60,
+            )],
+        );
+        assert!(config.validate().is_ok());
+
+        // Duplicate within one profile is not
+        config
+            .profiles
+            .get_mut("work")
+            .unwrap()
+            .push(Description::new(
+                "desk".to_owned(),
+                "Desk again".to_owned(),
+                60,
+            ));
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::DuplicateId { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validation_checks_inactive_profiles() {
+        let mut config = profile_config();
+        config.profiles.insert(
+            "broken".to_owned(),
+            vec![Description::new("empty".to_owned(), String::new(), 60)],
+        );
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::Empty { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validation_unknown_active_profile() {
+        let mut config = profile_config();
+        config.active_profile = Some("missing".to_owned());
+        assert!(matches!(
+            config.validate(),
+            Err(ValidationError::UnknownProfile { .. })
+        ));
+    }
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn two_window_config() -> DescriptionConfig {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[1].schedule = Some(TimeWindow::new(at(9, 0), at(17, 0)));
+        config.descriptions[2].schedule = Some(TimeWindow::new(at(19, 0), at(23, 0)));
+        config
+    }
+
+    #[test]
+    fn test_scheduled_index_at_first_listed_wins() {
+        let mut config = two_window_config();
+        assert_eq!(config.scheduled_index_at(on_wed(10, 0)), Some(1));
+        assert_eq!(config.scheduled_index_at(on_wed(18, 0)), None);
+
+        // Overlapping window listed earlier takes precedence
+        config.descriptions[0].schedule = Some(TimeWindow::new(at(16, 0), at(20, 0)));
+        assert_eq!(config.scheduled_index_at(on_wed(16, 30)), Some(0));
+        assert_eq!(config.scheduled_index_at(on_wed(19, 30)), Some(0));
+        assert_eq!(config.scheduled_index_at(on_wed(20, 0)), Some(2));
+    }
+
+    /// 2024-05-01 was a Wednesday.
+    fn on_wed(h: u32, m: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_time(at(h, m))
+    }
+
+    #[test]
+    fn test_active_on_weekdays() {
+        let mut desc = Description::new("gym".to_owned(), "🏋️".to_owned(), 60);
+        assert!(desc.active_on(Weekday::Sun)); // Empty means every day
+
+        desc.weekdays = vec![Weekday::Mon, Weekday::Wed];
+        assert!(desc.active_on(Weekday::Wed));
+        assert!(!desc.active_on(Weekday::Tue));
+    }
+
+    #[test]
+    fn test_weekdays_serde() {
+        let desc: Description = serde_json::from_str(
+            r#"{"id": "w", "text": "Weekend", "duration_secs": 60, "weekdays": ["Sat", "sunday"]}"#,
+        )
+        .unwrap();
+        assert_eq!(desc.weekdays, vec![Weekday::Sat, Weekday::Sun]);
+
+        // Absent list is not written back
+        let plain = Description::new("a".to_owned(), "A".to_owned(), 60);
+        assert!(!serde_json::to_string(&plain).unwrap().contains("weekdays"));
+    }
+
+    #[test]
+    fn test_scheduled_index_respects_weekdays() {
+        let mut config = two_window_config();
+        config.descriptions[1].weekdays = vec![Weekday::Sat, Weekday::Sun];
+
+        assert_eq!(config.scheduled_index_at(on_wed(10, 0)), None);
+        let saturday = on_wed(10, 0) + chrono::Duration::days(3);
+        assert_eq!(config.scheduled_index_at(saturday), Some(1));
+    }
+
+    #[test]
+    fn test_timeline_weekday_change_at_midnight() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[2].schedule = Some(TimeWindow::new(at(22, 0), at(6, 0)));
+        config.descriptions[2].weekdays = vec![Weekday::Wed];
+
+        // Wednesday 20:00 to Thursday 08:00: the window stops at midnight
+        let from = Utc.with_ymd_and_hms(2024, 5, 1, 20, 0, 0).unwrap();
+        let to = from + chrono::Duration::hours(12);
+        let segments = config.timeline(from, to, &Utc);
+        let starts: Vec<(u32, Option<&str>)> = segments
+            .iter()
+            .map(|s| {
+                (
+                    chrono::Timelike::hour(&s.start),
+                    s.description_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(starts, vec![(20, None), (22, Some("evening")), (0, None)]);
+    }
+
+    #[test]
+    fn test_validate_count_rejects_over_cap() {
+        let mut config = DescriptionConfig::example();
+        assert!(config.validate_count(None).is_ok());
+        assert!(config.validate_count(Some(3)).is_ok());
+        assert!(matches!(
+            config.validate_count(Some(2)),
+            Err(ValidationError::TooManyDescriptions {
+                count: 3,
+                max: 2,
+                ..
+            })
+        ));
+
+        // Every profile is checked, not only the active one
+        config.profiles.insert(
+            "work".to_owned(),
+            (0..5)
+                .map(|i| Description::new(format!("w{i}"), "Busy".to_owned(), 60))
+                .collect(),
+        );
+        assert!(matches!(
+            config.validate_count(Some(4)),
+            Err(ValidationError::TooManyDescriptions { ref profile, count: 5, max: 4 })
+                if profile == "work"
+        ));
+    }
+
+    #[test]
+    fn test_file_errors_are_specific() {
+        let missing = std::env::temp_dir().join(format!(
+            "description_bot_test_{}_missing.json",
+            std::process::id()
+        ));
+        let err = DescriptionConfig::load_from_file(&missing);
+        assert!(matches!(err, Err(ValidationError::NotFound { ref path }) if *path == missing));
+
+        let dir = std::env::temp_dir();
+        assert!(matches!(
+            DescriptionConfig::load_from_file(&dir),
+            Err(ValidationError::IsDirectory { .. })
+        ));
+        assert!(matches!(
+            DescriptionConfig::example().save_to_file(&dir),
+            Err(ValidationError::IsDirectory { .. })
+        ));
+
+        // Permissions are not enforced for root, so map the kind directly
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let err = file_error(Path::new("/etc/bot/descriptions.json"), denied);
+        assert_eq!(
+            err.to_string(),
+            "Permission denied for /etc/bot/descriptions.json \
+             (check the file's owner and permissions)"
+        );
+    }
+
+    #[test]
+    fn test_missing_file_gives_first_run_help() {
+        let missing = std::env::temp_dir().join(format!(
+            "description_bot_test_{}_first_run.json",
+            std::process::id()
+        ));
+        let help = DescriptionConfig::load_with_fallback(&missing)
+            .err()
+            .and_then(|e| e.first_run_help())
+            .unwrap();
+        assert!(help.contains("--generate-config"), "{help}");
+        assert!(help.contains(&missing.display().to_string()), "{help}");
+
+        let io = ValidationError::IoError(std::io::Error::other("disk on fire"));
+        assert_eq!(io.first_run_help(), None);
+    }
+
+    #[test]
+    fn test_eligibility_reasons() {
+        let mut config = two_window_config();
+        config.descriptions[0].weekdays = vec![Weekday::Sat];
+        // Wednesday, 2024-05-01
+        let at_utc = |h| Utc.with_ymd_and_hms(2024, 5, 1, h, 0, 0).unwrap();
+
+        assert_eq!(
+            config.eligibility("missing", None, at_utc(12), &Utc),
+            Eligibility::NotFound
+        );
+        assert_eq!(
+            config.eligibility("morning", None, at_utc(12), &Utc),
+            Eligibility::OtherWeekday(vec![Weekday::Sat])
+        );
+        assert_eq!(
+            config.eligibility("evening", None, at_utc(12), &Utc),
+            Eligibility::OutsideWindow(TimeWindow::new(at(19, 0), at(23, 0)))
+        );
+        assert_eq!(
+            config.eligibility("evening", None, at_utc(20), &Utc),
+            Eligibility::Eligible
+        );
+
+        // "working" is pinned 09:00-17:00, everything else waits
+        config.descriptions[0].weekdays.clear();
+        assert_eq!(
+            config.eligibility("morning", None, at_utc(10), &Utc),
+            Eligibility::PinnedElsewhere("working".to_owned())
+        );
+        assert_eq!(
+            config.eligibility("morning", None, at_utc(18), &Utc),
+            Eligibility::Eligible
+        );
+
+        // `only` excludes untagged descriptions, but not a pinned one
+        assert_eq!(
+            config.eligibility("morning", Some("work"), at_utc(18), &Utc),
+            Eligibility::OutsideTag("work".to_owned())
+        );
+        assert_eq!(
+            config.eligibility("working", Some("home"), at_utc(10), &Utc),
+            Eligibility::Eligible
+        );
+    }
+
+    #[test]
+    fn test_eligibility_uses_timezone() {
+        let config = two_window_config();
+        let tz = chrono::FixedOffset::east_opt(3 * 3600).unwrap();
+
+        // 06:30 UTC is 09:30 at UTC+3, inside the "working" window
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 6, 30, 0).unwrap();
+        assert_eq!(
+            config.eligibility("working", None, now, &tz),
+            Eligibility::Eligible
+        );
+        assert!(matches!(
+            config.eligibility("working", None, now, &Utc),
+            Eligibility::OutsideWindow(_)
+        ));
+    }
+
+    #[test]
+    fn test_timeline_two_windows() {
+        let config = two_window_config();
+        let from = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let to = from + chrono::Duration::hours(24);
+
+        let segments = config.timeline(from, to, &Utc);
+        let summary: Vec<(u32, Option<&str>)> = segments
+            .iter()
+            .map(|s| {
+                (
+                    chrono::Timelike::hour(&s.start),
+                    s.description_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, None),
+                (9, Some("working")),
+                (17, None),
+                (19, Some("evening")),
+                (23, None),
+            ]
+        );
+        assert_eq!(segments.last().unwrap().end, to);
+    }
+
+    #[test]
+    fn test_timeline_respects_timezone() {
+        let config = two_window_config();
+        let tz = chrono::FixedOffset::east_opt(3 * 3600).unwrap();
+        // 06:00 UTC is 09:00 at UTC+3
+        let from = Utc.with_ymd_and_hms(2024, 5, 1, 5, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2024, 5, 1, 7, 0, 0).unwrap();
+
+        let segments = config.timeline(from, to, &tz);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            segments[1].start,
+            Utc.with_ymd_and_hms(2024, 5, 1, 6, 0, 0).unwrap()
+        );
+        assert_eq!(segments[1].description_id.as_deref(), Some("working"));
+    }
+
+    #[test]
+    fn test_timeline_without_schedules() {
+        let config = DescriptionConfig::example();
+        let from = Utc::now();
+        let segments = config.timeline(from, from + chrono::Duration::hours(24), &Utc);
+        assert_eq!(segments.len(), 1);
+        assert!(segments[0].description_id.is_none());
+    }
+
+    #[test]
+    fn test_warnings_emoji_status_without_premium() {
+        let mut config = DescriptionConfig::example();
+        assert!(config.warnings().is_empty());
+
+        config.descriptions[0].emoji_status_id = Some(5_368_324_170_671_202_286);
+        let warnings = config.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("morning"));
+
+        config.is_premium = true;
+        assert!(config.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_premium_fallback() {
+        let mut config = DescriptionConfig::example();
+        assert!(!config.premium_fallback());
+
+        config.is_premium = true;
+        assert!(config.premium_fallback());
+
+        // A long bio in an inactive profile still needs Premium
+        let mut config = profile_config();
+        config.profiles.insert(
+            "long".to_owned(),
+            vec![Description::new("long".to_owned(), "a".repeat(100), 60)],
+        );
+        assert!(config.premium_fallback());
+    }
+
+    #[test]
+    fn test_unique_id() {
+        let config = DescriptionConfig::example();
+        assert_eq!(config.unique_id("coffee"), "coffee");
+        assert_eq!(config.unique_id("morning"), "morning_2");
+
+        let mut config = config;
+        config.descriptions.push(Description::new(
+            "morning_2".to_owned(),
+            "Again".to_owned(),
+            60,
+        ));
+        assert_eq!(config.unique_id("morning"), "morning_3");
+    }
+
+    #[test]
+    fn test_utf16_len() {
+        assert_eq!(utf16_len("Hello"), 5);
+        assert_eq!(utf16_len("Привет"), 6);
+        assert_eq!(utf16_len("👋"), 2); // Surrogate pair
+    }
+
+    #[test]
+    fn test_truncate_to_limit_fits() {
+        let text = "a".repeat(70);
+        assert_eq!(truncate_to_limit(&text, 70), text);
+    }
+
+    #[test]
+    fn test_truncate_to_limit_keeps_grapheme_clusters() {
+        // Family emoji: 5 code points joined by ZWJ, 8 UTF-16 units
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let text = format!("{}{family}{family}", "a".repeat(60));
+
+        let truncated = truncate_to_limit(&text, MAX_BIO_LENGTH_FREE);
+        assert!(utf16_len(&truncated) <= MAX_BIO_LENGTH_FREE);
+        assert_eq!(truncated, format!("{}{family}…", "a".repeat(60)));
+    }
+
+    #[test]
+    fn test_truncate_to_limit_flag_near_premium_limit() {
+        // Flags are two regional indicators (4 UTF-16 units) and must not be split
+        let text = format!("{}🇺🇦🇺🇦", "b".repeat(136));
+
+        let truncated = truncate_to_limit(&text, MAX_BIO_LENGTH_PREMIUM);
+        assert_eq!(truncated, format!("{}…", "b".repeat(136)));
+        assert!(utf16_len(&truncated) <= MAX_BIO_LENGTH_PREMIUM);
+    }
+
+    #[test]
+    fn test_validate_with_truncate_allows_overlong() {
+        let config = DescriptionConfig {
+            descriptions: vec![Description::new("test".to_owned(), "a".repeat(71), 60)],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(config.validate_with(OverlongPolicy::Truncate).is_ok());
+    }
 }
@@ -0,0 +1,240 @@
+//! Non-fatal checks for description configurations.
+//!
+//! Validation rejects configurations the bot cannot use; the linter points
+//! out ones that work but are probably not what the user meant.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{DescriptionConfig, normalize_bio, utf16_len};
+
+/// Share of the length limit (in percent) above which a text is reported
+/// as close to the limit.
+const NEAR_LIMIT_PERCENT: usize = 90;
+
+/// Descriptions needed before identical durations are worth a remark.
+const UNIFORM_DURATION_MIN_COUNT: usize = 3;
+
+/// What a lint warning is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// Text is within a few characters of the length limit.
+    NearLimit,
+    /// Two descriptions have the same text.
+    DuplicateText,
+    /// Duration is shorter than the minimum update interval.
+    BelowRateLimit,
+    /// Every description has the same duration.
+    UniformDurations,
+    /// Text starts or ends with whitespace.
+    Whitespace,
+    /// Filters prevent the description from ever being shown.
+    NeverShown,
+}
+
+/// A configuration issue that is not an error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintWarning {
+    /// Category of the issue.
+    pub kind: LintKind,
+    /// Affected description, if the issue is about a single one.
+    pub id: Option<String>,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(kind: LintKind, id: Option<&str>, message: String) -> Self {
+        Self {
+            kind,
+            id: id.map(str::to_owned),
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl DescriptionConfig {
+    /// Checks the active profile for likely mistakes.
+    ///
+    /// `min_update_interval_secs` is the rate-limit floor; shorter
+    /// durations are reported because they cannot be honored.
+    #[must_use]
+    pub fn lint(&self, min_update_interval_secs: u64) -> Vec<LintWarning> {
+        let descriptions = self.active_descriptions();
+        let max_len = self.max_bio_length();
+        let near_limit = max_len * NEAR_LIMIT_PERCENT / 100;
+        let mut warnings = Vec::new();
+        let mut seen_texts: HashMap<&str, &str> = HashMap::new();
+
+        for desc in descriptions {
+            let id = desc.id.as_str();
+            let text = normalize_bio(&desc.text);
+            let len = utf16_len(text);
+
+            if len > near_limit && len <= max_len {
+                warnings.push(LintWarning::new(
+                    LintKind::NearLimit,
+                    Some(id),
+                    format!("[{id}] uses {len} of {max_len} characters"),
+                ));
+            }
+
+            if let Some(first) = seen_texts.get(text) {
+                warnings.push(LintWarning::new(
+                    LintKind::DuplicateText,
+                    Some(id),
+                    format!("[{id}] has the same text as [{first}]"),
+                ));
+            } else {
+                seen_texts.insert(text, id);
+            }
+
+            if desc.duration_secs < min_update_interval_secs {
+                warnings.push(LintWarning::new(
+                    LintKind::BelowRateLimit,
+                    Some(id),
+                    format!(
+                        "[{id}] lasts {}s, shorter than the {min_update_interval_secs}s \
+                         minimum update interval",
+                        desc.duration_secs
+                    ),
+                ));
+            }
+
+            if desc.text.trim() != desc.text {
+                warnings.push(LintWarning::new(
+                    LintKind::Whitespace,
+                    Some(id),
+                    format!("[{id}] starts or ends with whitespace"),
+                ));
+            }
+
+            if let Some(window) = desc.schedule
+                && window.start == window.end
+            {
+                warnings.push(LintWarning::new(
+                    LintKind::NeverShown,
+                    Some(id),
+                    format!("[{id}] is never shown: its schedule {window} is empty"),
+                ));
+            }
+        }
+
+        if let [first, rest @ ..] = descriptions
+            && descriptions.len() >= UNIFORM_DURATION_MIN_COUNT
+            && rest.iter().all(|d| d.duration_secs == first.duration_secs)
+        {
+            warnings.push(LintWarning::new(
+                LintKind::UniformDurations,
+                None,
+                format!(
+                    "All {} descriptions last {}s; varied durations look less automated",
+                    descriptions.len(),
+                    first.duration_secs
+                ),
+            ));
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Description, TimeWindow};
+
+    fn config(descriptions: Vec<Description>) -> DescriptionConfig {
+        DescriptionConfig {
+            descriptions,
+            ..DescriptionConfig::default()
+        }
+    }
+
+    fn desc(id: &str, text: &str, duration_secs: u64) -> Description {
+        Description::new(id.to_owned(), text.to_owned(), duration_secs)
+    }
+
+    fn kinds(config: &DescriptionConfig) -> Vec<LintKind> {
+        config.lint(5).into_iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn test_clean_config_has_no_warnings() {
+        let clean = config(vec![desc("a", "Alpha", 60), desc("b", "Beta", 120)]);
+        assert!(clean.lint(5).is_empty());
+    }
+
+    #[test]
+    fn test_near_limit() {
+        let near = config(vec![desc("long", &"a".repeat(65), 60)]);
+        assert_eq!(kinds(&near), [LintKind::NearLimit]);
+
+        // Over the limit is a validation error, not a lint
+        let over = config(vec![desc("over", &"a".repeat(71), 60)]);
+        assert!(over.lint(5).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_text() {
+        let warnings = config(vec![
+            desc("a", "Same", 60),
+            desc("b", "Other", 120),
+            desc("c", "Same", 180),
+        ])
+        .lint(5);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::DuplicateText);
+        assert_eq!(warnings[0].id.as_deref(), Some("c"));
+        assert!(warnings[0].message.contains("[a]"));
+    }
+
+    #[test]
+    fn test_below_rate_limit() {
+        let warnings = config(vec![desc("quick", "Quick", 3), desc("slow", "Slow", 60)]).lint(5);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::BelowRateLimit);
+        assert_eq!(warnings[0].id.as_deref(), Some("quick"));
+    }
+
+    #[test]
+    fn test_uniform_durations() {
+        let three = config(vec![
+            desc("a", "A", 60),
+            desc("b", "B", 60),
+            desc("c", "C", 60),
+        ]);
+        assert_eq!(kinds(&three), [LintKind::UniformDurations]);
+
+        // Two descriptions are too few to call it a pattern
+        let two = config(vec![desc("a", "A", 60), desc("b", "B", 60)]);
+        assert!(two.lint(5).is_empty());
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let padded = config(vec![desc("lead", " Hi", 60), desc("trail", "Hi ", 120)]);
+        assert_eq!(kinds(&padded), [LintKind::Whitespace, LintKind::Whitespace]);
+    }
+
+    #[test]
+    fn test_never_shown() {
+        let mut never = desc("never", "Never", 60);
+        never.schedule = Some("09:00-09:00".parse::<TimeWindow>().unwrap());
+        let mut daily = desc("daily", "Daily", 120);
+        daily.schedule = Some("09:00-10:00".parse::<TimeWindow>().unwrap());
+
+        let warnings = config(vec![never, daily]).lint(5);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::NeverShown);
+        assert_eq!(warnings[0].id.as_deref(), Some("never"));
+    }
+}
@@ -3,14 +3,54 @@
 //! Handles loading, validation, and management of bot configuration
 //! including descriptions, timing, and Telegram API credentials.
 
+mod accounts;
+mod changelog;
+pub mod credentials;
 mod descriptions;
+mod lint;
+mod overrides;
+mod schedule;
 mod settings;
+mod snapshots;
+mod templates;
 
-pub use descriptions::{Description, DescriptionConfig, ValidationError};
-pub use settings::{BotSettings, TelegramConfig};
+pub use accounts::{ACCOUNTS_FILE, AccountConfig, AccountsConfig};
+pub use changelog::{
+    CHANGELOG_EXTENSION, ChangelogEntry, MAX_CHANGELOG_ENTRIES, append_entry, changelog_path,
+    read_entries,
+};
+pub use descriptions::{
+    Decoration, Description, DescriptionBuilder, DescriptionConfig, Eligibility, ValidationError,
+    backup_path, normalize_bio, truncate_to_limit, utf16_len,
+};
+pub use lint::{LintKind, LintWarning};
+pub use overrides::{
+    MIN_INTERVAL_CEILING_SECS, MIN_INTERVAL_FLOOR_SECS, SettingError, SettingsOverrides,
+    TUNABLE_KEYS, tunable_key,
+};
+pub use schedule::{TimeWindow, TimelineSegment};
+pub use settings::{
+    BotPaths, BotSettings, CommandSource, ConfigError, EffectiveConfig, Locale, OverlongPolicy,
+    ResumePolicy, SharedSettings, TelegramConfig,
+};
+pub use snapshots::{
+    SNAPSHOTS_DIR, SnapshotError, list_snapshots, load_snapshot, save_snapshot, snapshots_dir,
+};
+pub use templates::{BioTemplate, TEMPLATES, find_template};
 
 /// Maximum bio length for regular Telegram users.
 pub const MAX_BIO_LENGTH_FREE: usize = 70;
 
 /// Maximum bio length for Telegram Premium users.
 pub const MAX_BIO_LENGTH_PREMIUM: usize = 140;
+
+/// Longest display duration of a description, in seconds (one year).
+///
+/// Keeps deadlines far from `u64` overflow.
+pub const MAX_DURATION_SECS: u64 = 365 * 24 * 3600;
+
+/// Maximum length of the first and last name (UTF-16 code units).
+pub const MAX_NAME_LENGTH: usize = 64;
+
+/// Default seconds a `set` text stays before rotation resumes.
+pub const DEFAULT_CUSTOM_DURATION_SECS: u64 = 3600;
@@ -0,0 +1,314 @@
+//! Settings changed at runtime with `config set`.
+//!
+//! Only the settings that can take effect without a restart are tunable.
+//! Changes are kept in a small JSON file of `key → value` strings and
+//! applied on top of the environment at the next startup.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{BotSettings, TimeWindow};
+
+/// Keys accepted by `config get` and `config set`, in display order.
+pub const TUNABLE_KEYS: [&str; 10] = [
+    "min-interval",
+    "custom-duration",
+    "warn-threshold",
+    "list-page-size",
+    "command-cooldown",
+    "failure-threshold",
+    "on-overlong",
+    "locale",
+    "quiet-hours",
+    "confirm-destructive",
+];
+
+/// Shortest allowed minimum update interval, in seconds.
+pub const MIN_INTERVAL_FLOOR_SECS: u64 = 1;
+
+/// Longest allowed minimum update interval, in seconds.
+pub const MIN_INTERVAL_CEILING_SECS: u64 = 3600;
+
+/// Errors from reading or changing a setting at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SettingError {
+    #[error("Unknown setting '{0}'. Known settings: {known}", known = TUNABLE_KEYS.join(", "))]
+    UnknownKey(String),
+
+    #[error("Invalid value '{value}' for {key}: expected {expected}")]
+    InvalidValue {
+        key: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl BotSettings {
+    /// Returns the current value of a tunable setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` is not in [`TUNABLE_KEYS`].
+    pub fn get_setting(&self, key: &str) -> Result<String, SettingError> {
+        let value = match tunable_key(key)? {
+            "min-interval" => self.min_update_interval_secs.to_string(),
+            "custom-duration" => self.custom_duration_secs.to_string(),
+            "warn-threshold" => self.warn_threshold_pct.to_string(),
+            "list-page-size" => self.list_page_size.to_string(),
+            "command-cooldown" => self.command_cooldown_secs.to_string(),
+            "failure-threshold" => self.failure_threshold.to_string(),
+            "on-overlong" => serialized(self.on_overlong),
+            "locale" => serialized(self.locale),
+            "quiet-hours" => self
+                .quiet_hours
+                .map_or_else(|| "off".to_owned(), |w| w.to_string()),
+            _ => self.confirm_destructive.to_string(),
+        };
+        Ok(value)
+    }
+
+    /// Changes a tunable setting, checking the value's range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` is unknown or `value` is out of range.
+    pub fn set_setting(&mut self, key: &str, value: &str) -> Result<(), SettingError> {
+        let key = tunable_key(key)?;
+        let value = value.trim();
+        let invalid = |expected| SettingError::InvalidValue {
+            key,
+            value: value.to_owned(),
+            expected,
+        };
+
+        match key {
+            "min-interval" => {
+                self.min_update_interval_secs =
+                    parse_in(value, MIN_INTERVAL_FLOOR_SECS..=MIN_INTERVAL_CEILING_SECS)
+                        .ok_or_else(|| invalid("seconds from 1 to 3600"))?;
+            }
+            "custom-duration" => {
+                self.custom_duration_secs = parse_in(value, 1..=u64::MAX)
+                    .ok_or_else(|| invalid("a positive number of seconds"))?;
+            }
+            "warn-threshold" => {
+                self.warn_threshold_pct = parse_in(value, 0..=100)
+                    .ok_or_else(|| invalid("a percentage from 0 to 100"))?;
+            }
+            "list-page-size" => {
+                self.list_page_size = parse_in(value, 0..=1000)
+                    .ok_or_else(|| invalid("0 (no paging) to 1000 descriptions"))?;
+            }
+            "command-cooldown" => {
+                self.command_cooldown_secs =
+                    parse_in(value, 0..=3600).ok_or_else(|| invalid("seconds from 0 to 3600"))?;
+            }
+            "failure-threshold" => {
+                self.failure_threshold = parse_in(value, 0..=1000)
+                    .ok_or_else(|| invalid("0 (never pause) to 1000 failures"))?;
+            }
+            "on-overlong" => {
+                self.on_overlong = value
+                    .parse()
+                    .map_err(|_| invalid("'reject' or 'truncate'"))?;
+            }
+            "locale" => {
+                self.locale = value.parse().map_err(|_| invalid("'en' or 'ru'"))?;
+            }
+            "quiet-hours" => {
+                self.quiet_hours = if is_off(value) {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse::<TimeWindow>()
+                            .map_err(|_| invalid("HH:MM-HH:MM or 'off'"))?,
+                    )
+                };
+            }
+            _ => {
+                self.confirm_destructive = parse_bool(value).ok_or_else(|| invalid("on or off"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings changed with `config set`, persisted as a JSON object of
+/// `key → value` strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SettingsOverrides(BTreeMap<String, String>);
+
+impl SettingsOverrides {
+    /// Loads overrides from a JSON file, returns empty ones if not found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the overrides to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+
+    /// Records `value` for `key`, replacing an earlier value.
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_lowercase(), value.trim().to_owned());
+    }
+
+    /// Checks if no setting was changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Applies the overrides to `settings`, skipping invalid entries.
+    ///
+    /// Returns the errors of the skipped entries.
+    pub fn apply(&self, settings: &mut BotSettings) -> Vec<SettingError> {
+        self.0
+            .iter()
+            .filter_map(|(key, value)| settings.set_setting(key, value).err())
+            .collect()
+    }
+}
+
+/// Returns the entry of [`TUNABLE_KEYS`] matching `key` (case-insensitive,
+/// `_` accepted for `-`).
+///
+/// # Errors
+///
+/// Returns an error if the key is unknown.
+pub fn tunable_key(key: &str) -> Result<&'static str, SettingError> {
+    let key = key.trim().to_lowercase().replace('_', "-");
+    TUNABLE_KEYS
+        .into_iter()
+        .find(|&known| known == key)
+        .ok_or(SettingError::UnknownKey(key))
+}
+
+/// Parses a number within `range`.
+fn parse_in<T: FromStr + PartialOrd>(value: &str, range: std::ops::RangeInclusive<T>) -> Option<T> {
+    value.parse().ok().filter(|v| range.contains(v))
+}
+
+/// Parses `on`/`off` and their usual spellings.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Checks if `value` switches an optional setting off.
+fn is_off(value: &str) -> bool {
+    matches!(value.to_lowercase().as_str(), "off" | "none" | "-")
+}
+
+/// Returns the serialized name of an enum setting (e.g. `truncate`).
+fn serialized<T: Serialize>(value: T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OverlongPolicy;
+
+    #[test]
+    fn test_get_and_set_min_interval() {
+        let mut settings = BotSettings::default();
+        assert_eq!(settings.get_setting("min-interval").unwrap(), "5");
+
+        settings.set_setting("min-interval", "30").unwrap();
+        assert_eq!(settings.min_update_interval_secs, 30);
+        assert_eq!(settings.get_setting("MIN_INTERVAL").unwrap(), "30");
+
+        for bad in ["0", "3601", "soon", "-5"] {
+            assert!(matches!(
+                settings.set_setting("min-interval", bad),
+                Err(SettingError::InvalidValue {
+                    key: "min-interval",
+                    ..
+                })
+            ));
+        }
+        assert_eq!(settings.min_update_interval_secs, 30);
+    }
+
+    #[test]
+    fn test_get_and_set_overlong_policy() {
+        let mut settings = BotSettings::default();
+        assert_eq!(settings.get_setting("on-overlong").unwrap(), "reject");
+
+        settings.set_setting("on-overlong", "Truncate").unwrap();
+        assert_eq!(settings.on_overlong, OverlongPolicy::Truncate);
+        assert_eq!(settings.get_setting("on-overlong").unwrap(), "truncate");
+
+        assert!(settings.set_setting("on-overlong", "shorten").is_err());
+    }
+
+    #[test]
+    fn test_quiet_hours_can_be_cleared() {
+        let mut settings = BotSettings::default();
+        settings.set_setting("quiet-hours", "23:00-07:00").unwrap();
+        assert_eq!(settings.get_setting("quiet-hours").unwrap(), "23:00-07:00");
+
+        settings.set_setting("quiet-hours", "off").unwrap();
+        assert!(settings.quiet_hours.is_none());
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let mut settings = BotSettings::default();
+        let err = SettingError::UnknownKey("api-hash".to_owned());
+        assert_eq!(settings.set_setting("api-hash", "x"), Err(err.clone()));
+        assert!(err.to_string().contains("min-interval"));
+        assert!(settings.get_setting("rotation-mode").is_err());
+    }
+
+    #[test]
+    fn test_overrides_roundtrip_and_apply() {
+        let path = std::env::temp_dir().join(format!("settings_{}.json", std::process::id()));
+        let mut overrides = SettingsOverrides::default();
+        overrides.insert("min-interval", "12");
+        overrides.insert("locale", "ru");
+        overrides.insert("warn-threshold", "150");
+        overrides.save(&path).unwrap();
+
+        let loaded = SettingsOverrides::load(&path).unwrap();
+        assert_eq!(loaded, overrides);
+
+        let mut settings = BotSettings::default();
+        let errors = loaded.apply(&mut settings);
+        assert_eq!(settings.min_update_interval_secs, 12);
+        assert_eq!(settings.get_setting("locale").unwrap(), "ru");
+        // The out-of-range entry is skipped, the default stays
+        assert_eq!(errors.len(), 1);
+        assert_eq!(settings.warn_threshold_pct, 90);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(SettingsOverrides::load(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! Daily time windows for scheduled descriptions.
+
+use chrono::{DateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// A daily time-of-day window, e.g. `09:00`–`17:00`.
+///
+/// The start is inclusive and the end exclusive. A window whose end is
+/// before its start wraps around midnight (`22:00`–`06:00`). A window with
+/// equal start and end never matches.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimeWindow {
+    /// Local time the window opens (`HH:MM`).
+    #[serde(with = "hhmm")]
+    pub start: NaiveTime,
+
+    /// Local time the window closes (`HH:MM`).
+    #[serde(with = "hhmm")]
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Creates a new time window.
+    #[must_use]
+    pub const fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Checks if the given local time falls inside the window.
+    #[must_use]
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl std::str::FromStr for TimeWindow {
+    type Err = chrono::ParseError;
+
+    /// Parses `HH:MM-HH:MM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').unwrap_or((s, ""));
+        Ok(Self {
+            start: NaiveTime::parse_from_str(start.trim(), hhmm::FORMAT)?,
+            end: NaiveTime::parse_from_str(end.trim(), hhmm::FORMAT)?,
+        })
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format(hhmm::FORMAT),
+            self.end.format(hhmm::FORMAT)
+        )
+    }
+}
+
+/// A stretch of time during which one schedule decision holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineSegment<Tz: TimeZone> {
+    /// Segment start (inclusive).
+    pub start: DateTime<Tz>,
+
+    /// Segment end (exclusive).
+    pub end: DateTime<Tz>,
+
+    /// ID of the scheduled description, or `None` for regular rotation.
+    pub description_id: Option<String>,
+}
+
+/// Serde helpers for `HH:MM` times.
+mod hhmm {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub const FORMAT: &str = "%H:%M";
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // Signature required by serde
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format(FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&s, FORMAT).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_window_contains() {
+        let window = TimeWindow::new(time(9, 0), time(17, 0));
+        assert!(window.contains(time(9, 0)));
+        assert!(window.contains(time(12, 30)));
+        assert!(!window.contains(time(17, 0)));
+        assert!(!window.contains(time(8, 59)));
+    }
+
+    #[test]
+    fn test_window_wraps_midnight() {
+        let window = TimeWindow::new(time(22, 0), time(6, 0));
+        assert!(window.contains(time(23, 0)));
+        assert!(window.contains(time(0, 0)));
+        assert!(window.contains(time(5, 59)));
+        assert!(!window.contains(time(6, 0)));
+        assert!(!window.contains(time(12, 0)));
+    }
+
+    #[test]
+    fn test_window_from_str() {
+        let window: TimeWindow = "01:00-07:00".parse().unwrap();
+        assert_eq!(window, TimeWindow::new(time(1, 0), time(7, 0)));
+        assert_eq!(window.to_string(), "01:00-07:00");
+        assert!("01:00".parse::<TimeWindow>().is_err());
+        assert!("1am-7am".parse::<TimeWindow>().is_err());
+    }
+
+    #[test]
+    fn test_window_serde_hhmm() {
+        let window: TimeWindow =
+            serde_json::from_str(r#"{"start":"09:00","end":"17:30"}"#).unwrap();
+        assert_eq!(window, TimeWindow::new(time(9, 0), time(17, 30)));
+        assert_eq!(
+            serde_json::to_string(&window).unwrap(),
+            r#"{"start":"09:00","end":"17:30"}"#
+        );
+        assert!(serde_json::from_str::<TimeWindow>(r#"{"start":"9am","end":"17:30"}"#).is_err());
+    }
+}
@@ -1,9 +1,15 @@
 //! Application settings and Telegram configuration.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
+use super::TimeWindow;
+use super::credentials::{self, SecretStore};
+
 /// Telegram API configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelegramConfig {
@@ -16,6 +22,10 @@ pub struct TelegramConfig {
     /// Path to the session file.
     #[serde(default = "default_session_path")]
     pub session_path: PathBuf,
+
+    /// Passphrase for encrypting the session file at rest.
+    #[serde(default, skip_serializing)]
+    pub session_passphrase: Option<String>,
 }
 
 fn default_session_path() -> PathBuf {
@@ -30,45 +40,264 @@ impl TelegramConfig {
             api_id,
             api_hash,
             session_path: default_session_path(),
+            session_passphrase: None,
+        }
+    }
+
+    /// Returns a copy safe to print, with the API hash masked.
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        Self {
+            api_hash: mask_secret(&self.api_hash),
+            ..self.clone()
         }
     }
 
     /// Creates configuration from environment variables.
     ///
-    /// Expects `TG_API_ID` and `TG_API_HASH` to be set.
+    /// Expects `TG_API_ID` and `TG_API_HASH` to be set, either in the
+    /// environment or in the OS keyring (see [`credentials`]).
     ///
     /// # Errors
     ///
     /// Returns an error if environment variables are missing or invalid.
     pub fn from_env() -> Result<Self, ConfigError> {
-        let api_id: i32 = std::env::var("TG_API_ID")
-            .map_err(|_| ConfigError::MissingEnvVar("TG_API_ID"))?
+        Self::from_env_with(&*credentials::default_store())
+    }
+
+    /// Like [`Self::from_env`], falling back to `store` for the API ID and
+    /// hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a credential is missing or invalid.
+    pub fn from_env_with(store: &dyn SecretStore) -> Result<Self, ConfigError> {
+        let env = |name: &str| std::env::var(name).ok();
+
+        let api_id: i32 = credentials::lookup("TG_API_ID", env, store)
+            .ok_or(ConfigError::MissingEnvVar("TG_API_ID"))?
             .parse()
             .map_err(|_| ConfigError::InvalidApiId)?;
 
-        let api_hash =
-            std::env::var("TG_API_HASH").map_err(|_| ConfigError::MissingEnvVar("TG_API_HASH"))?;
+        let api_hash = credentials::lookup("TG_API_HASH", env, store)
+            .ok_or(ConfigError::MissingEnvVar("TG_API_HASH"))?;
 
         let session_path =
             std::env::var("TG_SESSION_PATH").map_or_else(|_| default_session_path(), PathBuf::from);
 
+        let session_passphrase = std::env::var("TG_SESSION_PASSPHRASE")
+            .ok()
+            .filter(|s| !s.is_empty());
+
         Ok(Self {
             api_id,
             api_hash,
             session_path,
+            session_passphrase,
         })
     }
 }
 
+/// Masks a secret, keeping only its first characters for recognition.
+fn mask_secret(secret: &str) -> String {
+    const SHOWN: usize = 4;
+
+    if secret.chars().count() <= SHOWN * 2 {
+        return "****".to_owned();
+    }
+    let prefix: String = secret.chars().take(SHOWN).collect();
+    format!("{prefix}****")
+}
+
+/// File locations of a bot instance.
+///
+/// All paths are relative to an optional config directory, so several
+/// accounts can run from one binary with a directory each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BotPaths {
+    /// Descriptions JSON file.
+    pub descriptions: PathBuf,
+
+    /// Persistent scheduler state (gzipped if it ends in `.gz`).
+    pub state: PathBuf,
+
+    /// Telegram session file.
+    pub session: PathBuf,
+
+    /// Settings changed with `config set`.
+    pub settings: PathBuf,
+}
+
+impl BotPaths {
+    /// Resolves the file paths against `config_dir`.
+    ///
+    /// Explicit paths override the default file names; relative ones are
+    /// still placed in `config_dir`, absolute ones are used as is.
+    #[must_use]
+    pub fn resolve(
+        config_dir: Option<&Path>,
+        descriptions: Option<&Path>,
+        state: Option<&Path>,
+        session: Option<&Path>,
+    ) -> Self {
+        let base = config_dir.unwrap_or_else(|| Path::new(""));
+        Self {
+            descriptions: base.join(descriptions.unwrap_or_else(|| Path::new("descriptions.json"))),
+            state: base.join(state.unwrap_or_else(|| Path::new("state.json"))),
+            session: base.join(session.map_or_else(default_session_path, Path::to_path_buf)),
+            settings: base.join("settings.json"),
+        }
+    }
+}
+
+/// Language used for user-facing formatting (e.g. durations).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English (`1h 1m`).
+    #[default]
+    En,
+    /// Russian (`1ч 1м`).
+    Ru,
+}
+
+impl Locale {
+    /// Returns the (seconds, minutes, hours) unit suffixes for durations.
+    #[must_use]
+    pub const fn duration_units(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Self::En => ("s", "m", "h"),
+            Self::Ru => ("с", "м", "ч"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Self::En),
+            "ru" | "russian" => Ok(Self::Ru),
+            _ => Err(ConfigError::InvalidLocale(s.to_owned())),
+        }
+    }
+}
+
+/// What to do with descriptions longer than the bio limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlongPolicy {
+    /// Treat over-limit text as a validation error.
+    #[default]
+    Reject,
+    /// Trim over-limit text to the limit before updating the bio.
+    Truncate,
+}
+
+impl FromStr for OverlongPolicy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "truncate" => Ok(Self::Truncate),
+            _ => Err(ConfigError::InvalidOverlongPolicy(s.to_owned())),
+        }
+    }
+}
+
+/// What happens to a saved deadline when the bot restarts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumePolicy {
+    /// Show the current description until its saved deadline.
+    #[default]
+    KeepDeadline,
+    /// Show the current description for its full duration again.
+    RestartDuration,
+}
+
+impl FromStr for ResumePolicy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "keep_deadline" | "keep" => Ok(Self::KeepDeadline),
+            "restart_duration" | "restart" => Ok(Self::RestartDuration),
+            _ => Err(ConfigError::InvalidResumePolicy(s.to_owned())),
+        }
+    }
+}
+
+/// Chat that commands are read from; replies go back to the same chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSource {
+    /// The account's own Saved Messages.
+    SavedMessages,
+    /// A private channel by its ID, for a command log of its own.
+    Channel(i64),
+}
+
+impl CommandSource {
+    /// Offset of the `-100…` channel IDs shown by bots and clients.
+    const BOT_API_CHANNEL_OFFSET: i64 = 1_000_000_000_000;
+}
+
+impl FromStr for CommandSource {
+    type Err = ConfigError;
+
+    /// Parses `saved` or `channel:<id>`; the ID may be given in the
+    /// `-100…` form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        if matches!(
+            value.as_str(),
+            "saved" | "saved_messages" | "saved-messages"
+        ) {
+            return Ok(Self::SavedMessages);
+        }
+
+        let id = value
+            .strip_prefix("channel:")
+            .and_then(|id| id.trim().parse::<i64>().ok())
+            .and_then(|id| {
+                if id < -Self::BOT_API_CHANNEL_OFFSET {
+                    id.checked_neg().map(|id| id - Self::BOT_API_CHANNEL_OFFSET)
+                } else {
+                    Some(id)
+                }
+            })
+            .filter(|&id| id > 0)
+            .ok_or_else(|| ConfigError::InvalidCommandSource(s.to_owned()))?;
+        Ok(Self::Channel(id))
+    }
+}
+
+impl std::fmt::Display for CommandSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SavedMessages => f.write_str("Saved Messages"),
+            Self::Channel(id) => write!(f, "channel {id}"),
+        }
+    }
+}
+
 /// Bot-specific settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent feature toggles
 pub struct BotSettings {
     /// Path to the descriptions JSON file.
     pub descriptions_path: PathBuf,
 
-    /// Command prefix for bot commands.
-    #[serde(default = "default_command_prefix")]
-    pub command_prefix: String,
+    /// Accepted command prefixes, e.g. `/description_bot` and `/db`.
+    #[serde(default = "default_command_prefixes")]
+    pub command_prefixes: Vec<String>,
+
+    /// Chats commands are accepted from (Saved Messages by default).
+    #[serde(default = "default_command_sources")]
+    pub allowed_command_sources: Vec<CommandSource>,
 
     /// Minimum interval between bio updates in seconds (rate limit protection).
     #[serde(default = "default_min_update_interval")]
@@ -77,16 +306,266 @@ pub struct BotSettings {
     /// Log level for the application.
     #[serde(default = "default_log_level")]
     pub log_level: String,
+
+    /// Description profile to activate on startup (overrides the config file).
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Language for formatted command output.
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Behavior for descriptions exceeding the bio limit.
+    #[serde(default)]
+    pub on_overlong: OverlongPolicy,
+
+    /// Whether a restart keeps the saved deadline of the current description.
+    #[serde(default)]
+    pub resume_policy: ResumePolicy,
+
+    /// Timezone for schedule windows and displayed times.
+    #[serde(default)]
+    pub timezone: Tz,
+
+    /// Daily window during which the bio is not changed.
+    #[serde(default)]
+    pub quiet_hours: Option<TimeWindow>,
+
+    /// Unix socket path for the local JSON control interface.
+    #[serde(default)]
+    pub control_socket: Option<PathBuf>,
+
+    /// Delay for coalescing config writes from commands, in milliseconds.
+    ///
+    /// `0` saves after every command.
+    #[serde(default = "default_config_save_debounce")]
+    pub config_save_debounce_ms: u64,
+
+    /// Attempts at writing the state or descriptions file before giving
+    /// up, with a short backoff between them (for networked filesystems).
+    #[serde(default = "default_save_attempts")]
+    pub save_attempts: u32,
+
+    /// Start at a random description when there is no saved state.
+    #[serde(default)]
+    pub random_start: bool,
+
+    /// Longest random delay before the scheduler's first check, in
+    /// seconds (`0` starts right away).
+    #[serde(default)]
+    pub startup_jitter_secs: u64,
+
+    /// Require `confirm` for destructive commands (`stats reset`).
+    #[serde(default)]
+    pub confirm_destructive: bool,
+
+    /// Prune statistics of descriptions not shown for this many days.
+    #[serde(default)]
+    pub stats_retention_days: Option<u64>,
+
+    /// Keep statistics of at most this many descriptions (the most
+    /// recently shown), so `state.json` stays small.
+    #[serde(default)]
+    pub max_stats_entries: Option<usize>,
+
+    /// Seconds before the same command may run again (`0` disables).
+    #[serde(default)]
+    pub command_cooldown_secs: u64,
+
+    /// Seconds between checks for bio changes made outside the bot
+    /// (`0` disables).
+    #[serde(default = "default_bio_check_interval")]
+    pub bio_check_interval_secs: u64,
+
+    /// Defer regular rotation while the account is online.
+    #[serde(default)]
+    pub suppress_while_online: bool,
+
+    /// Maximum length of a chat reply before it is split (UTF-16 units).
+    #[serde(default = "default_max_message_length")]
+    pub max_message_length: usize,
+
+    /// Keep the account appearing offline while the bot runs.
+    #[serde(default)]
+    pub offline_mode: bool,
+
+    /// Seconds a `set` text stays before rotation resumes (unless the
+    /// command gives its own duration).
+    #[serde(default = "default_custom_duration")]
+    pub custom_duration_secs: u64,
+
+    /// How often to retry connecting to Telegram at startup.
+    #[serde(default = "default_connect_retries")]
+    pub connect_retries: u32,
+
+    /// Pause before the first connection retry in seconds, doubled after
+    /// each further failure.
+    #[serde(default = "default_connect_retry_base")]
+    pub connect_retry_base_secs: u64,
+
+    /// Failed bio updates in a row before rotation is paused (`0` never
+    /// pauses).
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Maximum number of descriptions per profile (`None` is unlimited).
+    #[serde(default)]
+    pub max_descriptions: Option<usize>,
+
+    /// Descriptions per page of the `list` command (`0` disables paging).
+    #[serde(default = "default_list_page_size")]
+    pub list_page_size: usize,
+
+    /// Write the scheduler state to `state.json` (off for ephemeral runs).
+    #[serde(default = "default_persist_state")]
+    pub persist_state: bool,
+
+    /// Percentage of the bio limit above which `add`, `edit` and `set`
+    /// warn that a text is close to it (`0` disables the warning).
+    #[serde(default = "default_warn_threshold_pct")]
+    pub warn_threshold_pct: u8,
+
+    /// Observer mode: connect and report, but never change the profile or
+    /// write the descriptions and state files.
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Updates after startup that wait twice the minimum interval, so a
+    /// crash loop doesn't run into flood waits (`0` disables it).
+    #[serde(default = "default_slow_start_count")]
+    pub slow_start_count: u32,
+
+    /// Read the bio back after each update and warn if it differs from
+    /// the text sent (costs an extra API call per update).
+    #[serde(default)]
+    pub verify_updates: bool,
+}
+
+/// Bot settings shared between the command handler and the scheduler, so
+/// changes made with `config set` apply to both.
+pub type SharedSettings = std::sync::Arc<std::sync::RwLock<BotSettings>>;
+
+fn default_command_prefixes() -> Vec<String> {
+    vec!["/description_bot".to_owned()]
+}
+
+fn default_command_sources() -> Vec<CommandSource> {
+    vec![CommandSource::SavedMessages]
+}
+
+/// Parses a comma-separated `COMMAND_SOURCES` value, skipping invalid
+/// entries and falling back to Saved Messages if none is left.
+fn parse_command_sources(value: &str) -> Vec<CommandSource> {
+    let mut sources = Vec::new();
+    for source in value.split(',').filter_map(|s| s.parse().ok()) {
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    if sources.is_empty() {
+        default_command_sources()
+    } else {
+        sources
+    }
+}
+
+/// Splits a comma-separated `COMMAND_PREFIX` value into prefixes, falling
+/// back to the default if none is left.
+fn parse_command_prefixes(value: &str) -> Vec<String> {
+    let prefixes: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_owned)
+        .collect();
+    if prefixes.is_empty() {
+        default_command_prefixes()
+    } else {
+        prefixes
+    }
+}
+
+/// Reads and parses an environment variable (`None` if unset or invalid).
+fn env_parse<T: FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|s| s.parse().ok())
+}
+
+/// Checks that `value` of the environment variable `name` parses as `T`;
+/// unset or empty is fine.
+fn check_env_value<T>(name: &'static str, value: Option<&str>) -> Result<(), ConfigError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match value.filter(|v| !v.is_empty()) {
+        Some(value) => value
+            .parse::<T>()
+            .map(drop)
+            .map_err(|e| ConfigError::InvalidEnvVar {
+                name,
+                reason: e.to_string(),
+            }),
+        None => Ok(()),
+    }
 }
 
-fn default_command_prefix() -> String {
-    "/description_bot".to_owned()
+/// Reads a boolean environment variable (`1`/`true`/`yes`, unset is off).
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|s| matches!(s.to_lowercase().as_str(), "1" | "true" | "yes"))
 }
 
 fn default_min_update_interval() -> u64 {
     5 // 5 seconds minimum between updates (Telegram allows ~1 per 5s without flood)
 }
 
+fn default_config_save_debounce() -> u64 {
+    500
+}
+
+fn default_bio_check_interval() -> u64 {
+    600
+}
+
+fn default_max_message_length() -> usize {
+    4096 // Telegram's message limit
+}
+
+fn default_custom_duration() -> u64 {
+    super::DEFAULT_CUSTOM_DURATION_SECS
+}
+
+fn default_connect_retries() -> u32 {
+    5
+}
+
+fn default_connect_retry_base() -> u64 {
+    2
+}
+
+fn default_save_attempts() -> u32 {
+    3
+}
+
+fn default_failure_threshold() -> u32 {
+    10
+}
+
+fn default_list_page_size() -> usize {
+    20
+}
+
+fn default_persist_state() -> bool {
+    true
+}
+
+fn default_warn_threshold_pct() -> u8 {
+    90
+}
+
+fn default_slow_start_count() -> u32 {
+    3
+}
+
 fn default_log_level() -> String {
     "info".to_owned()
 }
@@ -95,9 +574,40 @@ impl Default for BotSettings {
     fn default() -> Self {
         Self {
             descriptions_path: PathBuf::from("descriptions.json"),
-            command_prefix: default_command_prefix(),
+            command_prefixes: default_command_prefixes(),
+            allowed_command_sources: default_command_sources(),
             min_update_interval_secs: default_min_update_interval(),
             log_level: default_log_level(),
+            profile: None,
+            locale: Locale::default(),
+            on_overlong: OverlongPolicy::default(),
+            resume_policy: ResumePolicy::default(),
+            timezone: Tz::UTC,
+            quiet_hours: None,
+            control_socket: None,
+            config_save_debounce_ms: default_config_save_debounce(),
+            save_attempts: default_save_attempts(),
+            random_start: false,
+            startup_jitter_secs: 0,
+            confirm_destructive: false,
+            stats_retention_days: None,
+            max_stats_entries: None,
+            command_cooldown_secs: 0,
+            bio_check_interval_secs: default_bio_check_interval(),
+            suppress_while_online: false,
+            max_message_length: default_max_message_length(),
+            offline_mode: false,
+            custom_duration_secs: default_custom_duration(),
+            connect_retries: default_connect_retries(),
+            connect_retry_base_secs: default_connect_retry_base(),
+            failure_threshold: default_failure_threshold(),
+            max_descriptions: None,
+            list_page_size: default_list_page_size(),
+            persist_state: default_persist_state(),
+            warn_threshold_pct: default_warn_threshold_pct(),
+            read_only: false,
+            slow_start_count: default_slow_start_count(),
+            verify_updates: false,
         }
     }
 }
@@ -109,15 +619,165 @@ impl BotSettings {
         Self {
             descriptions_path: std::env::var("DESCRIPTIONS_PATH")
                 .map_or_else(|_| PathBuf::from("descriptions.json"), PathBuf::from),
-            command_prefix: std::env::var("COMMAND_PREFIX")
-                .unwrap_or_else(|_| default_command_prefix()),
-            min_update_interval_secs: std::env::var("MIN_UPDATE_INTERVAL")
-                .ok()
-                .and_then(|s| s.parse().ok())
+            command_prefixes: std::env::var("COMMAND_PREFIX").map_or_else(
+                |_| default_command_prefixes(),
+                |value| parse_command_prefixes(&value),
+            ),
+            allowed_command_sources: std::env::var("COMMAND_SOURCES").map_or_else(
+                |_| default_command_sources(),
+                |value| parse_command_sources(&value),
+            ),
+            min_update_interval_secs: env_parse("MIN_UPDATE_INTERVAL")
                 .unwrap_or_else(default_min_update_interval),
             log_level: std::env::var("RUST_LOG").unwrap_or_else(|_| default_log_level()),
+            profile: std::env::var("DESCRIPTIONS_PROFILE")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            locale: env_parse("BOT_LOCALE").unwrap_or_default(),
+            on_overlong: env_parse("ON_OVERLONG").unwrap_or_default(),
+            resume_policy: env_parse("RESUME_POLICY").unwrap_or_default(),
+            timezone: env_parse("BOT_TIMEZONE").unwrap_or(Tz::UTC),
+            quiet_hours: env_parse("QUIET_HOURS"),
+            control_socket: std::env::var("CONTROL_SOCKET")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from),
+            config_save_debounce_ms: env_parse("CONFIG_SAVE_DEBOUNCE_MS")
+                .unwrap_or_else(default_config_save_debounce),
+            save_attempts: env_parse("SAVE_ATTEMPTS")
+                .filter(|&attempts| attempts > 0)
+                .unwrap_or_else(default_save_attempts),
+            random_start: env_flag("RANDOM_START"),
+            startup_jitter_secs: env_parse("STARTUP_JITTER_SECS").unwrap_or(0),
+            confirm_destructive: env_flag("CONFIRM_DESTRUCTIVE"),
+            stats_retention_days: env_parse("STATS_RETENTION_DAYS").filter(|&days| days > 0),
+            max_stats_entries: env_parse("MAX_STATS_ENTRIES").filter(|&max| max > 0),
+            command_cooldown_secs: env_parse("COMMAND_COOLDOWN").unwrap_or(0),
+            bio_check_interval_secs: env_parse("BIO_CHECK_INTERVAL")
+                .unwrap_or_else(default_bio_check_interval),
+            suppress_while_online: env_flag("SUPPRESS_WHILE_ONLINE"),
+            max_message_length: env_parse("MAX_MESSAGE_LENGTH")
+                .filter(|&len| len > 0)
+                .unwrap_or_else(default_max_message_length),
+            offline_mode: env_flag("OFFLINE_MODE"),
+            custom_duration_secs: env_parse("CUSTOM_DURATION")
+                .filter(|&secs| secs > 0)
+                .unwrap_or_else(default_custom_duration),
+            connect_retries: env_parse("CONNECT_RETRIES").unwrap_or_else(default_connect_retries),
+            connect_retry_base_secs: env_parse("CONNECT_RETRY_BASE_SECS")
+                .unwrap_or_else(default_connect_retry_base),
+            failure_threshold: env_parse("FAILURE_THRESHOLD")
+                .unwrap_or_else(default_failure_threshold),
+            max_descriptions: env_parse("MAX_DESCRIPTIONS").filter(|&max| max > 0),
+            list_page_size: env_parse("LIST_PAGE_SIZE").unwrap_or_else(default_list_page_size),
+            persist_state: !std::env::var("PERSIST_STATE")
+                .is_ok_and(|s| matches!(s.to_lowercase().as_str(), "0" | "false" | "no")),
+            warn_threshold_pct: env_parse("WARN_THRESHOLD_PCT")
+                .filter(|&pct| pct <= 100)
+                .unwrap_or_else(default_warn_threshold_pct),
+            read_only: env_flag("READ_ONLY"),
+            slow_start_count: env_parse("SLOW_START_COUNT")
+                .unwrap_or_else(default_slow_start_count),
+            verify_updates: env_flag("VERIFY_UPDATES"),
+        }
+    }
+
+    /// Checks the environment variables that choose a behavior, so a typo
+    /// is reported at startup instead of silently falling back to the
+    /// default.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first such variable set to a value that cannot be parsed.
+    pub fn check_env() -> Result<(), ConfigError> {
+        let var = |name| std::env::var(name).ok();
+        check_env_value::<Locale>("BOT_LOCALE", var("BOT_LOCALE").as_deref())?;
+        check_env_value::<OverlongPolicy>("ON_OVERLONG", var("ON_OVERLONG").as_deref())?;
+        check_env_value::<Tz>("BOT_TIMEZONE", var("BOT_TIMEZONE").as_deref())
+    }
+
+    /// Returns the status to send right after connecting: `Some(true)` to
+    /// go offline, `None` to leave the status to Telegram.
+    #[must_use]
+    pub const fn initial_offline_status(&self) -> Option<bool> {
+        if self.offline_mode { Some(true) } else { None }
+    }
+
+    /// Checks if commands from `source` may run.
+    #[must_use]
+    pub fn accepts_commands_from(&self, source: CommandSource) -> bool {
+        self.allowed_command_sources.contains(&source)
+    }
+
+    /// Checks if `now` falls into the quiet hours (in the configured timezone).
+    #[must_use]
+    pub fn is_quiet_at(&self, now: DateTime<Utc>) -> bool {
+        self.quiet_hours
+            .is_some_and(|window| window.contains(now.with_timezone(&self.timezone).time()))
+    }
+
+    /// Returns how long a description actually stays up: updates are never
+    /// made more often than `min_update_interval_secs`.
+    #[must_use]
+    pub fn effective_duration_secs(&self, duration_secs: u64) -> u64 {
+        duration_secs.max(self.min_update_interval_secs)
+    }
+
+    /// Checks if the bot is currently in its quiet hours.
+    #[must_use]
+    pub fn is_quiet_now(&self) -> bool {
+        self.is_quiet_at(Utc::now())
+    }
+}
+
+/// The fully resolved configuration, as printed by `--print-config`.
+///
+/// Secrets are redacted: the API hash is masked and the session passphrase
+/// is only reported as set or not.
+#[derive(Debug, Serialize)]
+pub struct EffectiveConfig<'a> {
+    /// Telegram settings with the API hash masked.
+    pub telegram: TelegramConfig,
+
+    /// Whether `TG_SESSION_PASSPHRASE` is set.
+    pub session_encrypted: bool,
+
+    /// Resolved file locations.
+    pub paths: &'a BotPaths,
+
+    /// Bot settings after environment overrides and defaults.
+    pub settings: &'a BotSettings,
+
+    /// Number of descriptions in the active profile, if the file loaded.
+    pub description_count: Option<usize>,
+}
+
+impl<'a> EffectiveConfig<'a> {
+    /// Collects the configuration, redacting secrets.
+    #[must_use]
+    pub fn new(
+        telegram: &TelegramConfig,
+        paths: &'a BotPaths,
+        settings: &'a BotSettings,
+        description_count: Option<usize>,
+    ) -> Self {
+        Self {
+            telegram: telegram.redacted(),
+            session_encrypted: telegram.session_passphrase.is_some(),
+            paths,
+            settings,
+            description_count,
         }
     }
+
+    /// Renders the configuration as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
 }
 
 /// Configuration errors.
@@ -128,17 +788,209 @@ pub enum ConfigError {
 
     #[error("Invalid API ID format (must be a positive integer)")]
     InvalidApiId,
+
+    #[error("Unsupported locale: {0} (expected 'en' or 'ru')")]
+    InvalidLocale(String),
+
+    #[error("Invalid overlong policy: {0} (expected 'reject' or 'truncate')")]
+    InvalidOverlongPolicy(String),
+
+    #[error("Invalid resume policy: {0} (expected 'keep_deadline' or 'restart_duration')")]
+    InvalidResumePolicy(String),
+
+    #[error("Credential store error: {0}")]
+    CredentialStore(String),
+
+    #[error("Invalid command source: {0} (expected 'saved' or 'channel:<id>')")]
+    InvalidCommandSource(String),
+
+    #[error("Invalid multi-account configuration: {0}")]
+    InvalidAccounts(String),
+
+    #[error("Invalid {name}: {reason}")]
+    InvalidEnvVar { name: &'static str, reason: String },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_env_value() {
+        assert!(check_env_value::<Locale>("BOT_LOCALE", None).is_ok());
+        assert!(check_env_value::<Locale>("BOT_LOCALE", Some("")).is_ok());
+        assert!(check_env_value::<Locale>("BOT_LOCALE", Some("ru")).is_ok());
+        assert_eq!(
+            check_env_value::<Locale>("BOT_LOCALE", Some("de")).map_err(|e| e.to_string()),
+            Err("Invalid BOT_LOCALE: Unsupported locale: de (expected 'en' or 'ru')".to_owned())
+        );
+        assert!(check_env_value::<OverlongPolicy>("ON_OVERLONG", Some("Truncate")).is_ok());
+        assert!(check_env_value::<OverlongPolicy>("ON_OVERLONG", Some("trunc")).is_err());
+        assert!(check_env_value::<Tz>("BOT_TIMEZONE", Some("Europe/Moscow")).is_ok());
+        assert!(check_env_value::<Tz>("BOT_TIMEZONE", Some("Moscow")).is_err());
+    }
+
+    #[test]
+    fn test_paths_in_config_dir() {
+        let paths = BotPaths::resolve(Some(Path::new("/srv/bots/work")), None, None, None);
+        assert_eq!(
+            paths.descriptions,
+            Path::new("/srv/bots/work/descriptions.json")
+        );
+        assert_eq!(paths.state, Path::new("/srv/bots/work/state.json"));
+        assert_eq!(paths.session, Path::new("/srv/bots/work/session.db"));
+        assert_eq!(paths.settings, Path::new("/srv/bots/work/settings.json"));
+
+        // Without a config dir everything stays in the working directory
+        let paths = BotPaths::resolve(None, None, None, None);
+        assert_eq!(paths.descriptions, Path::new("descriptions.json"));
+        assert_eq!(paths.state, Path::new("state.json"));
+    }
+
+    #[test]
+    fn test_paths_explicit_override() {
+        let dir = Some(Path::new("/srv/bots/work"));
+
+        let paths = BotPaths::resolve(
+            dir,
+            Some(Path::new("/etc/bot/descriptions.json")),
+            None,
+            None,
+        );
+        assert_eq!(paths.descriptions, Path::new("/etc/bot/descriptions.json"));
+
+        let paths = BotPaths::resolve(dir, None, None, Some(Path::new("/var/lib/bot/tg.session")));
+        assert_eq!(paths.session, Path::new("/var/lib/bot/tg.session"));
+
+        // Relative overrides are placed in the config dir
+        let paths = BotPaths::resolve(dir, Some(Path::new("work.json")), None, None);
+        assert_eq!(paths.descriptions, Path::new("/srv/bots/work/work.json"));
+
+        let paths = BotPaths::resolve(dir, None, Some(Path::new("state.json.gz")), None);
+        assert_eq!(paths.state, Path::new("/srv/bots/work/state.json.gz"));
+    }
+
     #[test]
     fn test_default_settings() {
         let settings = BotSettings::default();
-        assert_eq!(settings.command_prefix, "/description_bot");
+        assert_eq!(settings.command_prefixes, ["/description_bot"]);
         assert_eq!(settings.min_update_interval_secs, 5);
+        assert_eq!(settings.locale, Locale::En);
+    }
+
+    #[test]
+    fn test_parse_command_prefixes() {
+        assert_eq!(
+            parse_command_prefixes("/description_bot, /db"),
+            ["/description_bot", "/db"]
+        );
+        assert_eq!(parse_command_prefixes("!bot"), ["!bot"]);
+        assert_eq!(parse_command_prefixes(" , "), ["/description_bot"]);
+    }
+
+    #[test]
+    fn test_command_sources_accept_configured_channel_only() {
+        let settings = BotSettings {
+            allowed_command_sources: parse_command_sources("channel:-1001234567890, saved"),
+            ..BotSettings::default()
+        };
+        assert_eq!(
+            settings.allowed_command_sources,
+            [
+                CommandSource::Channel(1_234_567_890),
+                CommandSource::SavedMessages
+            ]
+        );
+        assert!(settings.accepts_commands_from(CommandSource::Channel(1_234_567_890)));
+        assert!(settings.accepts_commands_from(CommandSource::SavedMessages));
+        assert!(!settings.accepts_commands_from(CommandSource::Channel(987_654)));
+
+        // A channel alone keeps commands out of Saved Messages
+        let settings = BotSettings {
+            allowed_command_sources: parse_command_sources("channel:1234567890"),
+            ..BotSettings::default()
+        };
+        assert!(!settings.accepts_commands_from(CommandSource::SavedMessages));
+
+        assert!("channel:abc".parse::<CommandSource>().is_err());
+        assert!("channel:-5".parse::<CommandSource>().is_err());
+        assert_eq!(
+            parse_command_sources("group:1"),
+            [CommandSource::SavedMessages]
+        );
+    }
+
+    #[test]
+    fn test_offline_mode_drives_initial_status() {
+        let mut settings = BotSettings::default();
+        assert_eq!(settings.initial_offline_status(), None);
+
+        settings.offline_mode = true;
+        assert_eq!(settings.initial_offline_status(), Some(true));
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("ru".parse::<Locale>().ok(), Some(Locale::Ru));
+        assert_eq!("EN".parse::<Locale>().ok(), Some(Locale::En));
+        assert!("de".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        use chrono::TimeZone;
+
+        let settings = BotSettings {
+            quiet_hours: "23:00-07:00".parse().ok(),
+            ..Default::default()
+        };
+        let at = |h| Utc.with_ymd_and_hms(2024, 5, 1, h, 30, 0).unwrap();
+
+        assert!(settings.is_quiet_at(at(23)));
+        assert!(settings.is_quiet_at(at(0)));
+        assert!(settings.is_quiet_at(at(6)));
+        assert!(!settings.is_quiet_at(at(7)));
+        assert!(!settings.is_quiet_at(at(12)));
+        assert!(!BotSettings::default().is_quiet_at(at(0)));
+    }
+
+    #[test]
+    fn test_quiet_hours_use_timezone() {
+        use chrono::TimeZone;
+
+        let settings = BotSettings {
+            quiet_hours: "01:00-07:00".parse().ok(),
+            timezone: chrono_tz::Europe::Moscow, // UTC+3
+            ..Default::default()
+        };
+
+        // 23:30 UTC is 02:30 in Moscow
+        assert!(settings.is_quiet_at(Utc.with_ymd_and_hms(2024, 5, 1, 23, 30, 0).unwrap()));
+        // 02:30 UTC is 05:30 in Moscow
+        assert!(settings.is_quiet_at(Utc.with_ymd_and_hms(2024, 5, 1, 2, 30, 0).unwrap()));
+        // 05:00 UTC is 08:00 in Moscow
+        assert!(!settings.is_quiet_at(Utc.with_ymd_and_hms(2024, 5, 1, 5, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_print_config_redacts_secrets() {
+        let mut telegram =
+            TelegramConfig::new(12345, "0123456789abcdef0123456789abcdef".to_owned());
+        telegram.session_passphrase = Some("hunter2-passphrase".to_owned());
+        let paths = BotPaths::resolve(None, None, None, None);
+        let settings = BotSettings::default();
+
+        let json = EffectiveConfig::new(&telegram, &paths, &settings, Some(3))
+            .to_json()
+            .unwrap();
+        assert!(!json.contains("0123456789abcdef"));
+        assert!(!json.contains("hunter2"));
+        assert!(json.contains("\"api_hash\": \"0123****\""));
+        assert!(json.contains("\"session_encrypted\": true"));
+        assert!(json.contains("\"description_count\": 3"));
+
+        // Short values are masked completely
+        assert_eq!(mask_secret("abc123"), "****");
     }
 
     #[test]
@@ -0,0 +1,167 @@
+//! Named copies of the descriptions file (`snapshot save/restore`).
+//!
+//! Each snapshot is a JSON file in `snapshots/<stem>` next to the
+//! descriptions file (`<stem>` being its name without the extension), so
+//! setups such as "work" and "weekend" can be kept side by side and
+//! switched quickly, and accounts sharing a directory keep theirs apart.
+
+use std::path::{Path, PathBuf};
+
+use super::{DescriptionConfig, ValidationError};
+
+/// Directory of the snapshots next to the descriptions file, with one
+/// subdirectory per descriptions file.
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Errors from saving or restoring a snapshot.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("Invalid snapshot name '{0}': use letters, digits, '-' and '_'")]
+    InvalidName(String),
+
+    #[error("No snapshot named '{0}'")]
+    NotFound(String),
+
+    #[error("Snapshot file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Config(#[from] ValidationError),
+}
+
+/// Returns the snapshots directory for the descriptions file at
+/// `config_path`: `snapshots/<file stem>` next to it.
+#[must_use]
+pub fn snapshots_dir(config_path: impl AsRef<Path>) -> PathBuf {
+    let config_path = config_path.as_ref();
+    let stem = config_path
+        .file_stem()
+        .unwrap_or_else(|| std::ffi::OsStr::new("descriptions"));
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(SNAPSHOTS_DIR)
+        .join(stem)
+}
+
+/// Saves `config` as the snapshot `name` in `dir`, replacing an older one.
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid or the file cannot be written.
+pub fn save_snapshot(
+    config: &DescriptionConfig,
+    dir: &Path,
+    name: &str,
+) -> Result<PathBuf, SnapshotError> {
+    let path = snapshot_path(dir, name)?;
+    std::fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(config).map_err(ValidationError::from)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Loads the snapshot `name` from `dir`.
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid, there is no such snapshot or
+/// it cannot be parsed.
+pub fn load_snapshot(dir: &Path, name: &str) -> Result<DescriptionConfig, SnapshotError> {
+    let path = snapshot_path(dir, name)?;
+    if !path.is_file() {
+        return Err(SnapshotError::NotFound(name.to_owned()));
+    }
+    Ok(DescriptionConfig::load_from_file(path)?)
+}
+
+/// Returns the names of the snapshots in `dir`, sorted.
+///
+/// # Errors
+///
+/// Returns an error if the directory exists but cannot be read.
+pub fn list_snapshots(dir: &Path) -> std::io::Result<Vec<String>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?.strip_suffix(".json")?;
+            is_valid_name(name).then(|| name.to_owned())
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Returns the file of the snapshot `name`.
+fn snapshot_path(dir: &Path, name: &str) -> Result<PathBuf, SnapshotError> {
+    if !is_valid_name(name) {
+        return Err(SnapshotError::InvalidName(name.to_owned()));
+    }
+    Ok(dir.join(format!("{name}.json")))
+}
+
+/// Checks that a name is safe to use as a file name (no path separators).
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("snapshots_{name}_{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_save_list_load_roundtrip() {
+        let dir = temp_dir("roundtrip");
+        let mut config = DescriptionConfig::example();
+        save_snapshot(&config, &dir, "work").unwrap();
+        config.active_descriptions_mut().truncate(1);
+        save_snapshot(&config, &dir, "weekend").unwrap();
+
+        assert_eq!(list_snapshots(&dir).unwrap(), ["weekend", "work"]);
+        assert_eq!(load_snapshot(&dir, "work").unwrap().len(), 3);
+        assert_eq!(load_snapshot(&dir, "weekend").unwrap().len(), 1);
+        assert!(matches!(
+            load_snapshot(&dir, "holiday"),
+            Err(SnapshotError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(list_snapshots(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_names_cannot_escape_the_directory() {
+        let dir = temp_dir("names");
+        let config = DescriptionConfig::example();
+        for name in ["../state", "a/b", "", "work setup"] {
+            assert!(matches!(
+                save_snapshot(&config, &dir, name),
+                Err(SnapshotError::InvalidName(_))
+            ));
+        }
+        assert_eq!(
+            snapshots_dir("/srv/bot/descriptions.json"),
+            Path::new("/srv/bot/snapshots/descriptions")
+        );
+        // Accounts sharing a directory keep their snapshots apart
+        assert_eq!(
+            snapshots_dir("/srv/bot/work.json"),
+            Path::new("/srv/bot/snapshots/work")
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Built-in bio templates for new users.
+
+/// A built-in example bio that can be added as a description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BioTemplate {
+    /// Short name used to select the template.
+    pub name: &'static str,
+
+    /// The bio text (fits the free 70 char limit).
+    pub text: &'static str,
+
+    /// Suggested display duration in seconds.
+    pub duration_secs: u64,
+}
+
+/// Built-in templates. Add new entries here.
+pub const TEMPLATES: &[BioTemplate] = &[
+    BioTemplate {
+        name: "morning",
+        text: "☀️ Good morning! Ready for a new day",
+        duration_secs: 3600,
+    },
+    BioTemplate {
+        name: "working",
+        text: "💻 Currently working...",
+        duration_secs: 7200,
+    },
+    BioTemplate {
+        name: "busy",
+        text: "🔕 Busy right now, will reply later",
+        duration_secs: 3600,
+    },
+    BioTemplate {
+        name: "coffee",
+        text: "☕ Coffee break",
+        duration_secs: 1800,
+    },
+    BioTemplate {
+        name: "travel",
+        text: "✈️ Travelling, slow to respond",
+        duration_secs: 7200,
+    },
+    BioTemplate {
+        name: "evening",
+        text: "🌙 Relaxing in the evening",
+        duration_secs: 3600,
+    },
+    BioTemplate {
+        name: "sleeping",
+        text: "😴 Sleeping, see you tomorrow",
+        duration_secs: 28800,
+    },
+];
+
+/// Finds a built-in template by name (case-insensitive).
+#[must_use]
+pub fn find_template(name: &str) -> Option<&'static BioTemplate> {
+    TEMPLATES.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MAX_BIO_LENGTH_FREE;
+
+    #[test]
+    fn test_find_template() {
+        assert_eq!(find_template("coffee").map(|t| t.name), Some("coffee"));
+        assert_eq!(find_template("COFFEE").map(|t| t.name), Some("coffee"));
+        assert!(find_template("unknown").is_none());
+    }
+
+    #[test]
+    fn test_templates_fit_free_limit() {
+        for template in TEMPLATES {
+            assert!(!template.text.is_empty());
+            assert!(template.text.chars().count() <= MAX_BIO_LENGTH_FREE);
+            assert!(template.duration_secs > 0);
+        }
+    }
+}
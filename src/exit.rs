@@ -0,0 +1,252 @@
+//! Process exit codes of the main binary.
+//!
+//! Failures are grouped into categories with distinct exit codes, so a
+//! supervisor (e.g. systemd's `RestartPreventExitStatus=`) can tell a
+//! configuration mistake from a dropped connection.
+
+use crate::config::{ConfigError, ValidationError};
+use crate::telegram::{TelegramError, session_crypto::SessionCryptoError};
+use tracing::{error, info};
+
+/// Category of a fatal error, each with its own exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Anything not covered below.
+    Other,
+
+    /// Missing or invalid settings, unreadable descriptions file.
+    Config,
+
+    /// Not signed in, or signing in failed.
+    Auth,
+
+    /// The session was revoked or the account deactivated.
+    SessionRevoked,
+
+    /// Could not reach Telegram.
+    Connection,
+
+    /// The descriptions file loaded but is not valid.
+    Validation,
+}
+
+impl FailureKind {
+    /// Returns the process exit code for this category.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::Config => 3,
+            Self::Auth => 4,
+            Self::SessionRevoked => 5,
+            Self::Connection => 6,
+            Self::Validation => 7,
+        }
+    }
+
+    /// Classifies an error by the first known error type in its chain.
+    #[must_use]
+    pub fn of(err: &anyhow::Error) -> Self {
+        err.chain()
+            .find_map(|cause| {
+                if let Some(e) = cause.downcast_ref::<TelegramError>() {
+                    Some(Self::of_telegram(e))
+                } else if let Some(e) = cause.downcast_ref::<ValidationError>() {
+                    Some(match e {
+                        ValidationError::NotFound { .. }
+                        | ValidationError::IsDirectory { .. }
+                        | ValidationError::PermissionDenied { .. }
+                        | ValidationError::IoError(_)
+                        | ValidationError::ParseError(_) => Self::Config,
+                        _ => Self::Validation,
+                    })
+                } else if cause.is::<ConfigError>() || cause.is::<SessionCryptoError>() {
+                    Some(Self::Config)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Other)
+    }
+
+    /// Classifies a Telegram error.
+    fn of_telegram(err: &TelegramError) -> Self {
+        match err {
+            TelegramError::NotAuthorized
+            | TelegramError::SignInFailed(_)
+            | TelegramError::LoginCodeExpired
+            | TelegramError::LoginCodeInvalid
+            | TelegramError::PasswordRequired(_)
+            | TelegramError::InvalidPassword(_)
+            | TelegramError::BotAccount => Self::Auth,
+            TelegramError::Connection(_) => Self::Connection,
+            TelegramError::Session(_) => Self::SessionRevoked,
+            TelegramError::Invocation(message) if is_session_revoked(message) => {
+                Self::SessionRevoked
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Waits for every account task of a multi-account run.
+///
+/// One account failing does not stop the others. If any failed, the
+/// error of the first one with a known category is returned (or the
+/// first failure at all), so the process still exits with its code.
+///
+/// # Errors
+///
+/// Returns an error if at least one of the `total` accounts failed.
+pub async fn join_accounts(
+    mut tasks: tokio::task::JoinSet<(String, anyhow::Result<()>)>,
+    total: usize,
+) -> anyhow::Result<()> {
+    let mut failed = 0;
+    let mut first: Option<anyhow::Error> = None;
+    while let Some(joined) = tasks.join_next().await {
+        let err = match joined {
+            Ok((name, Ok(()))) => {
+                info!("Account '{}' stopped", name);
+                continue;
+            }
+            Ok((name, Err(e))) => {
+                error!("Account '{}' failed: {:?}", name, e);
+                e.context(format!("Account '{name}' failed"))
+            }
+            Err(e) => {
+                error!("Account task ended abnormally: {}", e);
+                anyhow::Error::new(e)
+            }
+        };
+        failed += 1;
+        let known = FailureKind::of(&err) != FailureKind::Other;
+        if first
+            .as_ref()
+            .is_none_or(|f| known && FailureKind::of(f) == FailureKind::Other)
+        {
+            first = Some(err);
+        }
+    }
+
+    match first {
+        Some(err) => Err(err.context(format!("{failed} of {total} accounts failed"))),
+        None => Ok(()),
+    }
+}
+
+/// Checks if an RPC error means the session can no longer be used.
+fn is_session_revoked(message: &str) -> bool {
+    [
+        "AUTH_KEY_UNREGISTERED",
+        "SESSION_REVOKED",
+        "SESSION_EXPIRED",
+        "USER_DEACTIVATED",
+    ]
+    .iter()
+    .any(|code| message.contains(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_of(err: impl std::error::Error + Send + Sync + 'static) -> FailureKind {
+        FailureKind::of(&anyhow::Error::new(err).context("Startup failed"))
+    }
+
+    #[test]
+    fn test_config_errors() {
+        assert_eq!(
+            kind_of(ConfigError::MissingEnvVar("TG_API_ID")),
+            FailureKind::Config
+        );
+        let parse = <serde_json::Error as serde::de::Error>::custom("expected value");
+        assert_eq!(
+            kind_of(ValidationError::ParseError(parse)),
+            FailureKind::Config
+        );
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        assert_eq!(
+            kind_of(ValidationError::NoDescriptions),
+            FailureKind::Validation
+        );
+    }
+
+    #[test]
+    fn test_telegram_errors() {
+        assert_eq!(kind_of(TelegramError::NotAuthorized), FailureKind::Auth);
+        assert_eq!(kind_of(TelegramError::LoginCodeInvalid), FailureKind::Auth);
+        assert_eq!(kind_of(TelegramError::BotAccount), FailureKind::Auth);
+        assert_eq!(
+            kind_of(TelegramError::Connection("timed out".to_owned())),
+            FailureKind::Connection
+        );
+        assert_eq!(
+            kind_of(TelegramError::Invocation(
+                "rpc error 401: AUTH_KEY_UNREGISTERED".to_owned()
+            )),
+            FailureKind::SessionRevoked
+        );
+        assert_eq!(kind_of(TelegramError::FloodWait(30)), FailureKind::Other);
+    }
+
+    #[test]
+    fn test_untyped_errors_and_distinct_codes() {
+        assert_eq!(
+            FailureKind::of(&anyhow::anyhow!("something else")),
+            FailureKind::Other
+        );
+
+        let kinds = [
+            FailureKind::Other,
+            FailureKind::Config,
+            FailureKind::Auth,
+            FailureKind::SessionRevoked,
+            FailureKind::Connection,
+            FailureKind::Validation,
+        ];
+        let codes: std::collections::HashSet<u8> = kinds.iter().map(|k| k.code()).collect();
+        assert_eq!(codes.len(), kinds.len());
+        assert!(!codes.contains(&0) && !codes.contains(&2)); // 2 is clap's usage error
+    }
+
+    #[tokio::test]
+    async fn test_accounts_run_independently() {
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut tasks = tokio::task::JoinSet::new();
+
+        // The second account only finishes once the first has run, so
+        // both must be running at the same time.
+        tasks.spawn(async move {
+            let result = ready_rx.await.map_err(anyhow::Error::new);
+            ("second".to_owned(), result)
+        });
+        tasks.spawn(async move {
+            let _ = ready_tx.send(());
+            let err = anyhow::Error::new(TelegramError::NotAuthorized);
+            ("first".to_owned(), Err(err))
+        });
+        tasks.spawn(async { ("third".to_owned(), Err(anyhow::anyhow!("boom"))) });
+
+        let result =
+            tokio::time::timeout(std::time::Duration::from_secs(5), join_accounts(tasks, 3))
+                .await
+                .map_err(|_| "accounts did not run concurrently");
+        let Ok(Err(err)) = result else {
+            panic!("expected a failure, got {result:?}");
+        };
+        assert!(err.to_string().contains("2 of 3 accounts failed"));
+        assert_eq!(FailureKind::of(&err), FailureKind::Auth);
+    }
+
+    #[tokio::test]
+    async fn test_accounts_all_stopped() {
+        let mut tasks = tokio::task::JoinSet::new();
+        tasks.spawn(async { ("only".to_owned(), Ok(())) });
+        assert!(join_accounts(tasks, 1).await.is_ok());
+    }
+}
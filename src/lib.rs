@@ -7,8 +7,11 @@
 //! - Connecting to Telegram via `MTProto`
 //! - Rotating profile descriptions on a schedule
 //! - Handling user commands via chat messages
+//! - Mapping fatal errors to process exit codes
 
 pub mod commands;
 pub mod config;
+pub mod exit;
 pub mod scheduler;
 pub mod telegram;
+pub mod util;
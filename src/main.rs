@@ -3,35 +3,65 @@
 //! A Telegram userbot that dynamically updates your profile description
 //! based on configured rotation schedules.
 
-use std::io::Write;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use base64::Engine;
 use clap::Parser;
-use dialoguer::{Input, Password};
+use dialoguer::{Confirm, Input, Password};
 use qrcode::QrCode;
 use tokio::sync::{RwLock, mpsc};
-use tracing::{debug, info};
+use tracing::{Instrument, debug, info};
 use tracing_subscriber::EnvFilter;
 
-use description_user_bot::commands::CommandHandler;
-use description_user_bot::config::{BotSettings, DescriptionConfig, TelegramConfig};
+use description_user_bot::commands::{
+    BioSource, CommandHandler, MessageTracker, PremiumSource, apply_command_effects, split_message,
+};
+use description_user_bot::config::{
+    ACCOUNTS_FILE, AccountConfig, AccountsConfig, BotPaths, BotSettings, CommandSource,
+    DescriptionConfig, EffectiveConfig, SettingsOverrides, SharedSettings, TelegramConfig,
+    ValidationError, backup_path, credentials,
+};
+use description_user_bot::exit::{FailureKind, join_accounts};
 use description_user_bot::scheduler::{
-    DescriptionScheduler, PersistentState, SchedulerMessage, SchedulerState,
+    Clock, DescriptionScheduler, HEALTH_MARGIN_SECS, PersistentState, ReadOnlyStateStore,
+    SchedulerMessage, StateStore, SystemClock, initial_state, simulate, state_store,
+};
+use description_user_bot::telegram::{
+    LOGIN_CODE_TIMEOUT_SECS, LoginCodeSource, MAX_QR_MIGRATIONS, MigrationCounter, PasswordSource,
+    PasswordToken, QR_CONFIRM_INTERVAL_MS, QR_POLL_INTERVAL_MS, QrAction, QrAuthResult,
+    TelegramBot, TelegramError, connect_with_retry, ensure_user_account, jittered, parse_code,
+    parse_password, qr_auth_step, session_crypto, wait_for_code_file,
 };
-use description_user_bot::telegram::{QrAuthResult, TelegramBot, TelegramError};
+
+/// Seconds after running a command during which edits of the same message
+/// are not executed again.
+const EDIT_DEDUP_WINDOW_SECS: u64 = 30;
+
+/// How often the offline status is sent again in offline mode (replies to
+/// commands show the account as online).
+const OFFLINE_REFRESH_SECS: u64 = 60;
 
 /// Telegram userbot for dynamic profile description updates.
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Independent CLI flags
 #[command(name = "description_bot")]
 #[command(about = "Dynamically update your Telegram profile description")]
 #[command(version)]
 struct Args {
-    /// Path to the descriptions JSON configuration file.
-    #[arg(short, long, default_value = "descriptions.json")]
-    config: String,
+    /// Path to the descriptions JSON configuration file
+    /// [default: descriptions.json, env: `DESCRIPTIONS_PATH`].
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Base directory for the descriptions, state and session files
+    /// [env: `CONFIG_DIR`].
+    #[arg(long)]
+    config_dir: Option<PathBuf>,
 
     /// Path to the .env file for environment variables.
     #[arg(long, default_value = ".env")]
@@ -45,14 +75,63 @@ struct Args {
     #[arg(long)]
     generate_config: bool,
 
+    /// Write a copy of the descriptions for another account to this path
+    /// and exit (premium detection and profile reset, usernames dropped).
+    #[arg(long, value_name = "DEST")]
+    clone_config: Option<PathBuf>,
+
+    /// Save the API ID and hash in the OS keyring and exit, so they can be
+    /// left out of the .env file (needs the `keyring` feature).
+    #[arg(long)]
+    store_credentials: bool,
+
     /// Use QR code for authentication instead of phone number.
     #[arg(long)]
     qr: bool,
+
+    /// Read the login code from this file instead of prompting, waiting
+    /// for it to appear (`TG_LOGIN_CODE_TIMEOUT` seconds, default 300).
+    #[arg(long, value_name = "PATH")]
+    code_file: Option<PathBuf>,
+
+    /// Apply the description that is due now, save state and exit (for cron).
+    #[arg(long)]
+    once: bool,
+
+    /// Check that a running bot keeps its state fresh and exit 0/1
+    /// (for container probes). Does not connect to Telegram.
+    #[arg(long)]
+    health: bool,
+
+    /// Print the resolved configuration (secrets masked) and exit.
+    /// Does not connect to Telegram.
+    #[arg(long)]
+    print_config: bool,
+
+    /// Observer mode: connect and report what would be done, but never
+    /// change the profile or write the descriptions and state files
+    /// [env: `READ_ONLY`].
+    #[arg(long)]
+    read_only: bool,
+
+    /// Print the rotation the next HOURS would produce and exit.
+    /// Does not connect to Telegram.
+    #[arg(long, value_name = "HOURS")]
+    simulate: Option<u64>,
 }
 
 #[tokio::main]
-#[allow(clippy::too_many_lines)]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(FailureKind::of(&e).code())
+        }
+    }
+}
+
+async fn run() -> Result<()> {
     let args = Args::parse();
 
     // Initialize logging
@@ -63,19 +142,145 @@ async fn main() -> Result<()> {
         return generate_example_config();
     }
 
+    if args.store_credentials {
+        return store_credentials();
+    }
+
     // Load environment variables
     if let Err(e) = dotenvy::from_filename(&args.env_file) {
         debug!("Could not load .env file ({}): {}", args.env_file, e);
     }
+    BotSettings::check_env()?;
+
+    // Resolve file locations (CLI flags win over the environment)
+    let config_dir = args.config_dir.clone().or_else(|| env_path("CONFIG_DIR"));
+    let paths = BotPaths::resolve(
+        config_dir.as_deref(),
+        args.config
+            .clone()
+            .or_else(|| env_path("DESCRIPTIONS_PATH"))
+            .as_deref(),
+        env_path("STATE_PATH").as_deref(),
+        env_path("TG_SESSION_PATH").as_deref(),
+    );
+
+    if args.health {
+        return check_health(&paths);
+    }
+
+    if args.print_config {
+        return print_config(&paths);
+    }
+
+    if let Some(hours) = args.simulate {
+        return simulate_rotation(&paths.descriptions, hours);
+    }
+
+    if let Some(dest) = &args.clone_config {
+        return clone_config(&paths.descriptions, dest);
+    }
+
+    if let Some(dir) = &config_dir {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create config directory {}", dir.display()))?;
+    }
+
+    // Several accounts from one process if accounts.json exists
+    let accounts_path = config_dir
+        .as_deref()
+        .unwrap_or_else(|| Path::new(""))
+        .join(env_path("ACCOUNTS_PATH").unwrap_or_else(|| PathBuf::from(ACCOUNTS_FILE)));
+    if let Some(accounts) = AccountsConfig::load(&accounts_path, config_dir.as_deref())? {
+        return run_accounts(&args, accounts, config_dir.as_deref()).await;
+    }
 
     // Load configurations
-    let tg_config = TelegramConfig::from_env()
+    let mut tg_config = TelegramConfig::from_env()
         .context("Failed to load Telegram configuration from environment")?;
+    tg_config.session_path.clone_from(&paths.session);
 
-    let bot_settings = BotSettings::from_env_with_defaults();
+    run_account(&args, tg_config, paths, None).await
+}
 
-    let mut desc_config = DescriptionConfig::load_from_file(&args.config)
-        .context("Failed to load descriptions configuration")?;
+/// Runs every account of a multi-account configuration independently,
+/// each with its own connection, scheduler and files, until Ctrl+C.
+async fn run_accounts(
+    args: &Args,
+    accounts: AccountsConfig,
+    config_dir: Option<&Path>,
+) -> Result<()> {
+    let defaults = TelegramConfig::from_env().ok();
+    let total = accounts.accounts.len();
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for account in accounts.accounts {
+        let paths = account.paths(config_dir);
+        let tg_config = account
+            .telegram_config(defaults.as_ref(), paths.session.clone())
+            .with_context(|| format!("Failed to configure account '{}'", account.name))?;
+        info!(
+            "Starting account '{}' (state: {})",
+            account.name,
+            paths.state.display()
+        );
+
+        let args = args.clone();
+        let span = tracing::info_span!("account", name = %account.name);
+        tasks.spawn(
+            async move {
+                let result = run_account(&args, tg_config, paths, Some(&account)).await;
+                (account.name, result)
+            }
+            .instrument(span),
+        );
+    }
+
+    join_accounts(tasks, total).await
+}
+
+/// Runs the bot for one account until Ctrl+C (or once, with `--once`).
+///
+/// `account` is set in multi-account mode, where the account must already
+/// be signed in and only its own control socket is used.
+#[allow(clippy::too_many_lines)]
+async fn run_account(
+    args: &Args,
+    tg_config: TelegramConfig,
+    paths: BotPaths,
+    account: Option<&AccountConfig>,
+) -> Result<()> {
+    let mut bot_settings = load_settings(&paths);
+    bot_settings.read_only |= args.read_only;
+    if let Some(account) = account {
+        bot_settings
+            .control_socket
+            .clone_from(&account.control_socket);
+    }
+
+    let (mut desc_config, primary_error) =
+        match DescriptionConfig::load_with_fallback(&paths.descriptions) {
+            Err(e) if e.first_run_help().is_some() => {
+                return offer_example_config(&paths.descriptions, e, account.is_none());
+            }
+            loaded => loaded.context("Failed to load descriptions configuration")?,
+        };
+
+    if let Some(e) = primary_error {
+        tracing::error!(
+            "{} is corrupted ({}); loaded the last known-good backup {} instead. \
+             Fix the file — the next change made via commands will overwrite it.",
+            paths.descriptions.display(),
+            e,
+            backup_path(&paths.descriptions).display()
+        );
+    }
+
+    if let Some(profile) = &bot_settings.profile {
+        desc_config
+            .switch_profile(profile)
+            .context("Failed to select profile from DESCRIPTIONS_PROFILE")?;
+        info!("Using description profile: {}", profile);
+    }
 
     info!(
         "Loaded {} descriptions (auto_detect_premium: {})",
@@ -83,10 +288,36 @@ async fn main() -> Result<()> {
         desc_config.auto_detect_premium
     );
 
-    // Connect to Telegram (rate limit from MIN_UPDATE_INTERVAL env var, default 5s)
-    let (bot, _updates) = TelegramBot::connect(&tg_config, bot_settings.min_update_interval_secs)
-        .await
-        .context("Failed to connect to Telegram")?;
+    // Restore the plain session if it is encrypted at rest; the guard
+    // encrypts it again on every exit from here on
+    let session_seal = tg_config
+        .session_passphrase
+        .as_deref()
+        .map(|passphrase| session_crypto::SealGuard::new(&tg_config.session_path, passphrase));
+    if let Some(passphrase) = &tg_config.session_passphrase
+        && session_crypto::unseal(&tg_config.session_path, passphrase)
+            .context("Failed to decrypt the session (check TG_SESSION_PASSPHRASE)")?
+    {
+        info!(
+            "Decrypted session from {}",
+            session_crypto::encrypted_path(&tg_config.session_path).display()
+        );
+    }
+
+    // Connect to Telegram (rate limit from MIN_UPDATE_INTERVAL env var, default 5s),
+    // retrying while the network comes up
+    let (bot, _updates) = connect_with_retry(
+        bot_settings.connect_retries,
+        Duration::from_secs(bot_settings.connect_retry_base_secs),
+        || TelegramBot::connect(&tg_config, bot_settings.min_update_interval_secs),
+    )
+    .await
+    .context("Failed to connect to Telegram")?;
+
+    if bot_settings.read_only {
+        bot.set_read_only(true);
+        tracing::warn!("Read-only mode: the profile, descriptions and state are not changed");
+    }
 
     // Handle authentication if needed
     if !bot
@@ -94,37 +325,86 @@ async fn main() -> Result<()> {
         .await
         .context("Failed to check authorization")?
     {
+        if let Some(account) = account {
+            anyhow::bail!(
+                "Account '{}' is not signed in; sign it in once without accounts.json, \
+                 using its session file",
+                account.name
+            );
+        }
         if args.qr {
             authenticate_qr(&bot, &tg_config).await?;
         } else {
-            authenticate(&bot, &tg_config).await?;
+            let code_source = LoginCodeSource::select(
+                args.code_file.clone(),
+                std::env::var("TG_LOGIN_CODE").ok().as_deref(),
+            );
+            authenticate(&bot, &tg_config, &code_source).await?;
         }
     }
 
+    // Bots have no bio of their own to rotate
+    match bot.is_bot_account().await {
+        Ok(is_bot) => ensure_user_account(is_bot)?,
+        Err(e) => tracing::warn!("Could not check the account type: {}", e),
+    }
+
+    // Appear offline while the bot runs, if configured
+    if let Some(offline) = bot_settings.initial_offline_status()
+        && let Err(e) = bot.set_offline(offline).await
+    {
+        tracing::warn!("Failed to set the account offline: {}", e);
+    }
+
     // Auto-detect premium status if enabled
+    let mut premium_source = PremiumSource::Configured;
     if desc_config.auto_detect_premium {
-        match bot.is_premium().await {
-            Ok(is_premium) => {
-                desc_config.set_premium(is_premium);
-                info!(
-                    "Auto-detected premium status: {}",
-                    if is_premium { "Premium" } else { "Free" }
-                );
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to auto-detect premium status: {}. Using config value.",
-                    e
-                );
-            }
+        if let Some(is_premium) = detect_premium(&bot).await {
+            desc_config.set_premium(is_premium);
+            premium_source = PremiumSource::Detected;
+            info!(
+                "Auto-detected premium status: {}",
+                if is_premium { "Premium" } else { "Free" }
+            );
+        } else {
+            let assumed = desc_config.premium_fallback();
+            tracing::warn!(
+                "Could not detect premium status, assuming {} ({})",
+                if assumed { "Premium" } else { "Free" },
+                if assumed {
+                    "some descriptions exceed the free limit"
+                } else {
+                    "all descriptions fit the free limit"
+                }
+            );
+            desc_config.set_premium(assumed);
+            premium_source = PremiumSource::Assumed;
         }
     }
 
+    // Prefer the bio limits Telegram reports over the built-in 70/140
+    match bot.bio_limits().await {
+        Ok(Some((free, premium))) => {
+            info!(
+                "Bio limits from Telegram: {} free, {} premium",
+                free, premium
+            );
+            desc_config.set_max_lengths(free, premium);
+        }
+        Ok(None) => tracing::debug!("App config has no bio limits, using the built-in ones"),
+        Err(e) => tracing::warn!("Could not fetch bio limits, using the built-in ones: {}", e),
+    }
+
     // Validate after premium status is determined
     desc_config
-        .validate()
+        .validate_with(bot_settings.on_overlong)
+        .and_then(|()| desc_config.validate_count(bot_settings.max_descriptions))
         .context("Description configuration validation failed")?;
 
+    for warning in desc_config.warnings() {
+        tracing::warn!("{}", warning);
+    }
+
     info!(
         "Configuration validated (premium: {}, max_length: {})",
         desc_config.is_premium,
@@ -134,10 +414,43 @@ async fn main() -> Result<()> {
     let bot = Arc::new(bot);
     let config = Arc::new(RwLock::new(desc_config));
 
+    // The signed-in account, to detect state left by another account
+    let account_id = match bot.get_me().await {
+        Ok((id, _)) => Some(id),
+        Err(e) => {
+            tracing::warn!("Could not identify the signed-in account: {}", e);
+            None
+        }
+    };
+
     // Load persistent state or start fresh
-    let state_path = "state.json";
-    let persistent = PersistentState::load(state_path);
-    let scheduler_state = SchedulerState::from_persistent(&persistent);
+    let state_store: Arc<dyn StateStore> = if bot_settings.read_only {
+        Arc::new(ReadOnlyStateStore::new(&paths.state))
+    } else {
+        state_store(&paths.state, bot_settings.persist_state)
+    };
+    if !bot_settings.persist_state {
+        info!(
+            "State persistence disabled, {} is not written",
+            paths.state.display()
+        );
+    }
+    let persistent = state_store.load();
+    let scheduler_state = initial_state(
+        persistent.as_ref(),
+        account_id,
+        &*config.read().await,
+        bot_settings.resume_policy,
+        bot_settings.random_start,
+        time_seed(),
+    );
+
+    if persistent.is_none() && bot_settings.random_start {
+        info!(
+            "Fresh start, beginning at random index {}",
+            scheduler_state.current_index
+        );
+    }
 
     if scheduler_state.current_index > 0 {
         info!(
@@ -146,44 +459,129 @@ async fn main() -> Result<()> {
         );
     }
 
+    // An interval chosen with 'rate-limit' overrides MIN_UPDATE_INTERVAL
+    if let Some(secs) = scheduler_state.rate_limit_secs {
+        bot.rate_limiter()
+            .set_min_interval(Duration::from_secs(secs))
+            .await;
+    }
+    bot.rate_limiter()
+        .set_slow_start(bot_settings.slow_start_count)
+        .await;
+
     let state = Arc::new(RwLock::new(scheduler_state));
+    let settings: SharedSettings = Arc::new(std::sync::RwLock::new(bot_settings.clone()));
+
+    if args.once {
+        let scheduler = DescriptionScheduler::new(
+            Arc::clone(&bot),
+            Arc::clone(&config),
+            Arc::clone(&state),
+            Arc::clone(&state_store),
+        )
+        .with_shared_settings(Arc::clone(&settings));
+
+        let result = scheduler.run_once().await;
+        bot.disconnect();
+        drop(session_seal);
+
+        if let Some(id) = result.context("Failed to update bio")? {
+            info!("Applied description [{}], exiting", id);
+        } else {
+            info!("No update due, exiting");
+        }
+        return Ok(());
+    }
 
     // Create scheduler channel
     let (scheduler_tx, scheduler_rx) = mpsc::channel::<SchedulerMessage>(32);
 
     // Create command handler
-    let command_handler = Arc::new(CommandHandler::new(
-        bot_settings.command_prefix.clone(),
-        Arc::clone(&state),
-        Arc::clone(&config),
-        args.config.clone(),
-        state_path.to_owned(),
-    ));
+    let command_handler = Arc::new(
+        CommandHandler::new(
+            bot_settings.command_prefixes.clone(),
+            Arc::clone(&state),
+            Arc::clone(&config),
+            paths.descriptions.to_string_lossy().into_owned(),
+            Arc::clone(&state_store),
+        )
+        .with_shared_settings(Arc::clone(&settings))
+        .with_settings_path(&paths.settings)
+        .with_bio_source(Arc::clone(&bot) as Arc<dyn BioSource>)
+        .with_rate_limiter(bot.rate_limiter())
+        .with_premium_source(premium_source),
+    );
 
     // Create scheduler
     let scheduler = DescriptionScheduler::new(
         Arc::clone(&bot),
         Arc::clone(&config),
         Arc::clone(&state),
-        state_path.to_owned(),
-    );
+        Arc::clone(&state_store),
+    )
+    .with_shared_settings(Arc::clone(&settings));
 
     info!("Starting description bot...");
-    info!("Command prefix: {}", bot_settings.command_prefix);
+    info!(
+        "Command prefixes: {}",
+        bot_settings.command_prefixes.join(", ")
+    );
 
     // Spawn scheduler task
     let scheduler_handle = tokio::spawn(async move {
         scheduler.run(scheduler_rx).await;
     });
 
-    // Spawn command polling task
-    let bot_for_commands = Arc::clone(&bot);
-    let scheduler_tx_for_commands = scheduler_tx.clone();
-    let command_handle = tokio::spawn(async move {
-        poll_commands(bot_for_commands, command_handler, scheduler_tx_for_commands).await;
+    // Spawn local control socket if configured
+    if let Some(socket_path) = bot_settings.control_socket.clone() {
+        spawn_control_socket(
+            socket_path,
+            Arc::clone(&command_handler),
+            Arc::clone(&bot),
+            scheduler_tx.clone(),
+        );
+    }
+
+    // Check the command channels before polling them, so a typo fails now
+    for &source in &bot_settings.allowed_command_sources {
+        if let CommandSource::Channel(channel_id) = source {
+            bot.open_command_channel(channel_id)
+                .await
+                .with_context(|| format!("Cannot read commands from {source}"))?;
+        }
+    }
+
+    // Spawn a command polling task per source
+    let pollers: Vec<_> = bot_settings
+        .allowed_command_sources
+        .iter()
+        .map(|&source| {
+            let bot = Arc::clone(&bot);
+            let handler = Arc::clone(&command_handler);
+            let scheduler_tx = scheduler_tx.clone();
+            let max_message_length = bot_settings.max_message_length;
+            tokio::spawn(async move {
+                poll_commands(bot, handler, scheduler_tx, source, max_message_length).await;
+            })
+        })
+        .collect();
+
+    // Spawn external bio change detection
+    let bio_check_handle = (bot_settings.bio_check_interval_secs > 0).then(|| {
+        let bot = Arc::clone(&bot);
+        let interval = Duration::from_secs(bot_settings.bio_check_interval_secs);
+        tokio::spawn(watch_external_bio(bot, interval))
     });
 
-    info!("Bot is running. Send commands to Saved Messages.");
+    // Keep the account offline if offline mode is on (also toggled by command)
+    let presence_handle = tokio::spawn(keep_offline(Arc::clone(&bot)));
+
+    let sources: Vec<String> = bot_settings
+        .allowed_command_sources
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    info!("Bot is running. Send commands to {}.", sources.join(" or "));
 
     // Wait for Ctrl+C
     tokio::select! {
@@ -196,70 +594,362 @@ async fn main() -> Result<()> {
     info!("Shutting down...");
     let _ = scheduler_tx.send(SchedulerMessage::Shutdown).await;
     let _ = scheduler_handle.await;
-    command_handle.abort();
+    for handle in pollers {
+        handle.abort();
+    }
+    presence_handle.abort();
+    if let Some(handle) = bio_check_handle {
+        handle.abort();
+    }
+    if let Err(e) = command_handler.flush_config().await {
+        tracing::warn!("Failed to save pending config changes: {}", e);
+    }
     bot.disconnect();
+    drop(session_seal);
 
     Ok(())
 }
 
-/// Polls Saved Messages for new commands.
+/// Returns a seed for non-cryptographic randomness (random start index).
+fn time_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    // Only the low bits vary between runs
+    #[allow(clippy::cast_possible_truncation)]
+    let seed = nanos as u64;
+    seed
+}
+
+/// Queries premium status, retrying a few times with a short backoff.
+///
+/// Returns `None` if every attempt failed.
+async fn detect_premium(bot: &TelegramBot) -> Option<bool> {
+    const ATTEMPTS: u32 = 3;
+
+    for attempt in 1..=ATTEMPTS {
+        match bot.is_premium().await {
+            Ok(is_premium) => return Some(is_premium),
+            Err(e) => {
+                tracing::warn!(
+                    "Premium detection failed (attempt {}/{}): {}",
+                    attempt,
+                    ATTEMPTS,
+                    e
+                );
+                if attempt < ATTEMPTS {
+                    tokio::time::sleep(Duration::from_secs(u64::from(attempt))).await;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Polls a command source for new commands, replying in the same chat.
 async fn poll_commands(
     bot: Arc<TelegramBot>,
     command_handler: Arc<CommandHandler>,
     scheduler_tx: mpsc::Sender<SchedulerMessage>,
+    source: CommandSource,
+    max_message_length: usize,
 ) {
-    // Track the last processed message ID to avoid duplicates
-    let mut last_processed_id: i32 = 0;
-
     // Get initial state - find the newest message ID to start from
-    if let Ok(messages) = bot.get_saved_messages(1).await
-        && let Some((id, _)) = messages.first()
+    let mut start_id = 0;
+    if let Ok(messages) = bot.get_source_messages(source, 1).await
+        && let Some(message) = messages.first()
     {
-        last_processed_id = *id;
+        start_id = message.id;
         debug!(
-            "Starting command polling from message ID: {}",
-            last_processed_id
+            "Starting command polling in {} from message ID: {}",
+            source, start_id
         );
     }
 
+    // Tracks new and edited messages so each command runs once
+    let mut tracker = MessageTracker::new(start_id, EDIT_DEDUP_WINDOW_SECS);
+
     loop {
         // Poll every second for faster response
         tokio::time::sleep(Duration::from_secs(1)).await;
 
         // Get recent messages
-        match bot.get_saved_messages(10).await {
+        match bot.get_source_messages(source, 10).await {
             Ok(messages) => {
-                // Process new messages (newer than last_processed_id)
+                let now = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or(0);
+
                 // Messages are returned newest first, so we need to reverse
-                for (msg_id, text) in messages.into_iter().rev() {
-                    if msg_id <= last_processed_id {
+                for message in messages.into_iter().rev() {
+                    if !tracker.should_handle(&message, now) {
                         continue;
                     }
 
-                    debug!("New message in Saved Messages (id={}): {}", msg_id, text);
-                    last_processed_id = msg_id;
+                    debug!(
+                        "New or edited message in {} (id={}): {}",
+                        source, message.id, message.text
+                    );
 
                     // Try to handle as command
-                    if let Some(result) = command_handler.try_handle(&text).await {
+                    if let Some(result) = command_handler.try_handle(&message.text).await {
+                        tracker.mark_executed(message.id, now);
                         debug!("Command result: {}", result.message);
 
-                        // Send response
-                        if let Err(e) = bot.send_to_saved_messages(&result.message).await {
-                            tracing::error!("Failed to send command response: {}", e);
-                        }
+                        // Apply name change / trigger update before replying
+                        let mut result = result;
+                        apply_command_effects(&mut result, &bot, &scheduler_tx).await;
 
-                        // Trigger update if needed
-                        if result.trigger_update {
-                            let _ = scheduler_tx.send(SchedulerMessage::TriggerUpdate).await;
+                        // Send response, split to fit the message limit
+                        for chunk in split_message(&result.message, max_message_length) {
+                            if let Err(e) = bot.send_to_source(source, &chunk).await {
+                                tracing::error!("Failed to send command response: {}", e);
+                                break;
+                            }
                         }
                     }
                 }
             }
             Err(e) => {
-                tracing::warn!("Failed to poll Saved Messages: {}", e);
+                tracing::warn!("Failed to poll {}: {}", source, e);
+            }
+        }
+    }
+}
+
+/// Periodically checks whether the bio was changed outside the bot.
+async fn watch_external_bio(bot: Arc<TelegramBot>, interval: Duration) {
+    loop {
+        // Detected changes are logged by the bot
+        if let Err(e) = bot.check_external_bio_change().await {
+            debug!("Failed to check the current bio: {}", e);
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Periodically marks the account offline again while offline mode is on.
+async fn keep_offline(bot: Arc<TelegramBot>) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(OFFLINE_REFRESH_SECS)).await;
+        if let Err(e) = bot.keep_offline().await {
+            debug!("Failed to refresh offline status: {}", e);
+        }
+    }
+}
+
+/// Starts the local JSON control socket in the background.
+#[cfg(unix)]
+fn spawn_control_socket(
+    path: std::path::PathBuf,
+    handler: Arc<CommandHandler>,
+    bot: Arc<TelegramBot>,
+    scheduler_tx: mpsc::Sender<SchedulerMessage>,
+) {
+    tokio::spawn(async move {
+        if let Err(e) =
+            description_user_bot::commands::serve_control_socket(&path, handler, bot, scheduler_tx)
+                .await
+        {
+            tracing::error!("Control socket {} stopped: {}", path.display(), e);
+        }
+    });
+}
+
+/// Control sockets are Unix-only.
+#[cfg(not(unix))]
+fn spawn_control_socket(
+    _path: std::path::PathBuf,
+    _handler: Arc<CommandHandler>,
+    _bot: Arc<TelegramBot>,
+    _scheduler_tx: mpsc::Sender<SchedulerMessage>,
+) {
+    tracing::warn!("CONTROL_SOCKET is only supported on Unix, ignoring");
+}
+
+/// Checks that the state file is being kept up to date.
+///
+/// Returns an error (exit code 1) if the state is stale or files are missing.
+fn check_health(paths: &BotPaths) -> Result<()> {
+    let config = DescriptionConfig::load_from_file(&paths.descriptions)
+        .context("Failed to load descriptions configuration")?;
+
+    // Only check the session when Telegram credentials are configured
+    if TelegramConfig::from_env().is_ok()
+        && !paths.session.exists()
+        && !session_crypto::encrypted_path(&paths.session).exists()
+    {
+        anyhow::bail!(
+            "Unhealthy: session file not found: {}",
+            paths.session.display()
+        );
+    }
+
+    if !BotSettings::from_env_with_defaults().persist_state {
+        anyhow::bail!("Unhealthy: --health needs state.json, but PERSIST_STATE is off");
+    }
+
+    let state_path = paths.state.as_path();
+    let saved_at = std::fs::metadata(state_path)
+        .and_then(|m| m.modified())
+        .context("Unhealthy: state file not found")?;
+    let state = PersistentState::load(state_path);
+
+    let shortest = config
+        .active_descriptions()
+        .iter()
+        .map(|d| d.duration_secs)
+        .min()
+        .unwrap_or(0);
+    let grace = shortest + HEALTH_MARGIN_SECS;
+
+    let unix = |time: SystemTime| {
+        time.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    };
+
+    if !state.is_fresh(unix(SystemTime::now()), unix(saved_at), grace) {
+        anyhow::bail!("Unhealthy: state not updated within {grace} seconds");
+    }
+
+    println!("✓ Healthy");
+    Ok(())
+}
+
+/// Reads the settings from the environment, with the changes saved by
+/// `config set` applied on top.
+fn load_settings(paths: &BotPaths) -> BotSettings {
+    let mut settings = BotSettings::from_env_with_defaults();
+    match SettingsOverrides::load(&paths.settings) {
+        Ok(overrides) => {
+            for e in overrides.apply(&mut settings) {
+                tracing::warn!(
+                    "Ignoring saved setting from {}: {}",
+                    paths.settings.display(),
+                    e
+                );
             }
         }
+        Err(e) => tracing::warn!("Could not read {}: {}", paths.settings.display(), e),
     }
+    settings
+}
+
+/// Prints the configuration the bot would run with.
+fn print_config(paths: &BotPaths) -> Result<()> {
+    let mut tg_config = TelegramConfig::from_env()
+        .context("Failed to load Telegram configuration from environment")?;
+    tg_config.session_path.clone_from(&paths.session);
+
+    let bot_settings = load_settings(paths);
+
+    let description_count = match DescriptionConfig::load_from_file(&paths.descriptions) {
+        Ok(mut config) => {
+            if let Some(profile) = &bot_settings.profile
+                && let Err(e) = config.switch_profile(profile)
+            {
+                tracing::warn!("{}", e);
+            }
+            Some(config.len())
+        }
+        Err(e) => {
+            tracing::warn!("Could not load descriptions: {}", e);
+            None
+        }
+    };
+
+    let effective = EffectiveConfig::new(&tg_config, paths, &bot_settings, description_count);
+    println!("{}", effective.to_json()?);
+    Ok(())
+}
+
+/// Prints the updates the rotation would make over the next `hours`,
+/// starting from a fresh state.
+fn simulate_rotation(path: &Path, hours: u64) -> Result<()> {
+    let bot_settings = BotSettings::from_env_with_defaults();
+    let mut config = DescriptionConfig::load_from_file(path)
+        .context("Failed to load descriptions configuration")?;
+    if let Some(profile) = &bot_settings.profile {
+        config
+            .switch_profile(profile)
+            .context("Failed to select profile from DESCRIPTIONS_PROFILE")?;
+    }
+
+    let tz = bot_settings.timezone;
+    let start = SystemClock.now_unix();
+    let updates = simulate(&config, &bot_settings, start, hours.saturating_mul(3600));
+
+    println!("Simulated rotation for the next {hours}h ({tz}):");
+    for update in &updates {
+        let at = i64::try_from(update.at_unix)
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .unwrap_or_default()
+            .with_timezone(&tz);
+        println!(
+            "  {} [{}] for {}s",
+            at.format("%a %H:%M"),
+            update.description_id,
+            update.duration_secs
+        );
+    }
+    println!("{} updates", updates.len());
+    Ok(())
+}
+
+/// Writes a copy of the descriptions ready for another account.
+fn clone_config(source: &Path, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        anyhow::bail!("{} already exists, not overwriting it", dest.display());
+    }
+
+    let config = DescriptionConfig::load_from_file(source)
+        .context("Failed to load descriptions configuration")?;
+    config
+        .for_new_account()
+        .save_to_file(dest)
+        .with_context(|| format!("Failed to write {}", dest.display()))?;
+
+    println!(
+        "✓ Copied {} to {} for a new account",
+        source.display(),
+        dest.display()
+    );
+    println!(
+        "  Edit it, then run with: description_bot --config {}",
+        dest.display()
+    );
+    Ok(())
+}
+
+/// Prompts for the API credentials and saves them in the OS keyring.
+fn store_credentials() -> Result<()> {
+    let store = credentials::default_store();
+
+    let api_id: i32 = Input::new()
+        .with_prompt("API ID (from https://my.telegram.org)")
+        .interact_text()?;
+    let api_hash = Password::new().with_prompt("API hash").interact()?;
+
+    store
+        .set("TG_API_ID", &api_id.to_string())
+        .and_then(|()| store.set("TG_API_HASH", api_hash.trim()))
+        .context("Failed to save credentials in the keyring")?;
+
+    println!(
+        "✓ Saved TG_API_ID and TG_API_HASH in the keyring (service \"{}\")",
+        credentials::KEYRING_SERVICE
+    );
+    println!("  They are used whenever the variables are not set in the environment or .env.");
+    Ok(())
+}
+
+/// Reads a path from a non-empty environment variable.
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
 }
 
 /// Initializes the logging subsystem.
@@ -287,49 +977,191 @@ fn generate_example_config() -> Result<()> {
     Ok(())
 }
 
+/// Explains how to create the missing descriptions file and, when run
+/// from a terminal, offers to write the example there.
+///
+/// Returns the original error unless the example was written.
+fn offer_example_config(path: &Path, err: ValidationError, can_prompt: bool) -> Result<()> {
+    if let Some(help) = err.first_run_help() {
+        eprintln!("{help}\n");
+    }
+
+    let create = can_prompt
+        && std::io::stdin().is_terminal()
+        && Confirm::new()
+            .with_prompt(format!("Create {} from the example now?", path.display()))
+            .default(false)
+            .interact()?;
+    if !create {
+        return Err(err.into());
+    }
+
+    DescriptionConfig::example().save_to_file(path)?;
+    println!("✓ Example descriptions written to {}", path.display());
+    println!("Edit them, then start the bot again.");
+    Ok(())
+}
+
+/// Maximum number of login codes requested before giving up.
+const MAX_LOGIN_CODE_REQUESTS: u32 = 3;
+
+/// Maximum number of attempts to type a single login code.
+const MAX_LOGIN_CODE_ATTEMPTS: u32 = 3;
+
 /// Handles Telegram authentication.
-async fn authenticate(bot: &TelegramBot, config: &TelegramConfig) -> Result<()> {
+///
+/// An expired login code triggers a new code request and a mistyped code
+/// re-prompts (or waits for a new code in the file), both without dropping
+/// the connection. A code from `TG_LOGIN_CODE` is tried once. Without a
+/// terminal, the phone (`TG_PHONE`) and the code must come from elsewhere.
+async fn authenticate(
+    bot: &TelegramBot,
+    config: &TelegramConfig,
+    code_source: &LoginCodeSource,
+) -> Result<()> {
     info!("Authentication required");
 
-    let phone: String = Input::new()
-        .with_prompt("Enter your phone number (with country code)")
-        .interact_text()?;
+    let phone = if let Some(phone) = std::env::var("TG_PHONE")
+        .ok()
+        .as_deref()
+        .and_then(parse_code)
+    {
+        phone
+    } else {
+        ensure_terminal("the phone number (set TG_PHONE)")?;
+        Input::new()
+            .with_prompt("Enter your phone number (with country code)")
+            .interact_text()?
+    };
+    if code_source.needs_terminal() {
+        ensure_terminal("the login code (use --code-file or TG_LOGIN_CODE)")?;
+    }
 
-    let token = bot
-        .request_login_code(&phone, &config.api_hash)
-        .await
-        .context("Failed to request login code")?;
+    let timeout = Duration::from_secs(
+        std::env::var("TG_LOGIN_CODE_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(LOGIN_CODE_TIMEOUT_SECS),
+    );
+
+    // The last code tried, so a code file is not read twice
+    let mut used_code: Option<String> = None;
+    for _ in 0..MAX_LOGIN_CODE_REQUESTS {
+        let requested_at = SystemTime::now();
+        let token = bot
+            .request_login_code(&phone, &config.api_hash)
+            .await
+            .context("Failed to request login code")?;
+
+        info!("Login code sent to your Telegram app");
+
+        for attempt in 1..=MAX_LOGIN_CODE_ATTEMPTS {
+            let code =
+                read_login_code(code_source, used_code.as_deref(), requested_at, timeout).await?;
+
+            match bot.sign_in(&token, &code).await {
+                Ok(()) => {
+                    info!("Successfully signed in!");
+                    return Ok(());
+                }
+                Err(TelegramError::PasswordRequired(password_token)) => {
+                    return authenticate_2fa(bot, password_token).await;
+                }
+                Err(TelegramError::LoginCodeInvalid)
+                    if attempt < MAX_LOGIN_CODE_ATTEMPTS && code_source.can_retry() =>
+                {
+                    tracing::warn!(
+                        "Invalid login code, please try again ({}/{})",
+                        attempt,
+                        MAX_LOGIN_CODE_ATTEMPTS
+                    );
+                    used_code = Some(code);
+                }
+                Err(TelegramError::LoginCodeExpired) if code_source.can_retry() => {
+                    used_code = Some(code);
+                    tracing::warn!("Login code expired, requesting a new one...");
+                    break;
+                }
+                Err(e) => return Err(e).context("Authentication failed"),
+            }
+        }
+    }
 
-    info!("Login code sent to your Telegram app");
+    Err(TelegramError::SignInFailed(format!(
+        "no valid login code after {MAX_LOGIN_CODE_REQUESTS} requests"
+    )))
+    .context("Authentication failed")
+}
 
-    let code: String = Input::new()
-        .with_prompt("Enter the login code")
-        .interact_text()?;
+/// Fails if there is no terminal to ask for `what`.
+fn ensure_terminal(what: &str) -> Result<()> {
+    if std::io::stdin().is_terminal() {
+        Ok(())
+    } else {
+        anyhow::bail!("No terminal to enter {what}")
+    }
+}
 
-    match bot.sign_in(&token, &code).await {
-        Ok(()) => {
-            info!("Successfully signed in!");
-            Ok(())
+/// Gets a login code from its source, waiting for the code file if needed.
+async fn read_login_code(
+    source: &LoginCodeSource,
+    used: Option<&str>,
+    requested_at: SystemTime,
+    timeout: Duration,
+) -> Result<String> {
+    match source {
+        LoginCodeSource::File(path) => {
+            info!("Waiting for the login code in {}", path.display());
+            wait_for_code_file(path, used, requested_at, timeout)
+                .await
+                .with_context(|| {
+                    format!(
+                        "No new login code in {} after {}s",
+                        path.display(),
+                        timeout.as_secs()
+                    )
+                })
         }
-        Err(TelegramError::PasswordRequired(password_token)) => {
-            info!("Two-factor authentication is enabled");
+        LoginCodeSource::Env(code) => Ok(code.clone()),
+        LoginCodeSource::Prompt => Ok(Input::new()
+            .with_prompt("Enter the login code")
+            .interact_text()?),
+    }
+}
+
+/// Completes sign in with the 2FA password.
+async fn authenticate_2fa(bot: &TelegramBot, password_token: PasswordToken) -> Result<()> {
+    info!("Two-factor authentication is enabled");
 
-            let hint = password_token.hint().unwrap_or("no hint");
-            info!("Password hint: {}", hint);
+    let hint = password_token.hint().unwrap_or("no hint");
+    info!("Password hint: {}", hint);
 
-            let password: String = Password::new()
+    let source = PasswordSource::select(
+        env_path("TG_PASSWORD_FILE"),
+        std::env::var("TG_PASSWORD").ok().as_deref(),
+    );
+    let password = match source {
+        PasswordSource::File(path) => {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            parse_password(&content)
+                .with_context(|| format!("No 2FA password in {}", path.display()))?
+        }
+        PasswordSource::Env(password) => password,
+        PasswordSource::Prompt => {
+            ensure_terminal("the 2FA password (set TG_PASSWORD_FILE or TG_PASSWORD)")?;
+            Password::new()
                 .with_prompt("Enter your 2FA password")
-                .interact()?;
+                .interact()?
+        }
+    };
 
-            bot.check_password(password_token, &password)
-                .await
-                .context("2FA authentication failed")?;
+    bot.check_password(password_token, &password)
+        .await
+        .context("2FA authentication failed")?;
 
-            info!("Successfully signed in with 2FA!");
-            Ok(())
-        }
-        Err(e) => Err(e).context("Authentication failed"),
-    }
+    info!("Successfully signed in with 2FA!");
+    Ok(())
 }
 
 /// Handles QR code authentication.
@@ -337,59 +1169,76 @@ async fn authenticate_qr(bot: &TelegramBot, config: &TelegramConfig) -> Result<(
     info!("QR code authentication");
 
     let mut last_token: Option<Vec<u8>> = None;
+    // Result from another DC that must be handled before polling again
+    let mut pending: Option<QrAuthResult> = None;
+    let mut migrations = MigrationCounter::default();
 
     loop {
-        match bot
-            .export_login_token(config.api_id, &config.api_hash)
-            .await?
-        {
-            QrAuthResult::Token { token, expires } => {
-                // Always clear and redraw when token changes
-                if last_token.as_ref() != Some(&token) {
-                    clear_screen();
-                    println!("Scan QR code in Telegram:");
-                    println!("Settings → Devices → Link Desktop Device\n");
-                    display_qr_code(&token);
-
-                    #[allow(clippy::cast_possible_truncation)]
-                    let now = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs() as i32; // Safe until 2038
-                    let remaining = expires - now;
-                    println!("\nExpires in {remaining} seconds...");
-
-                    last_token = Some(token);
-                }
-
-                // Poll every 2 seconds
-                tokio::time::sleep(Duration::from_secs(2)).await;
+        let result = match pending.take() {
+            Some(result) => result,
+            None => {
+                bot.export_login_token(config.api_id, &config.api_hash)
+                    .await?
             }
-            QrAuthResult::Success { user_id, username } => {
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let now = i64::try_from(now).unwrap_or(i64::MAX);
+
+        match qr_auth_step(result, last_token.as_deref(), now) {
+            QrAction::ShowToken { token, expires_in } => {
                 clear_screen();
-                let name = username.unwrap_or_else(|| format!("id:{user_id}"));
-                info!("Successfully authenticated as @{}", name);
-                println!("✓ Successfully authenticated as @{name}");
-                return Ok(());
+                println!("Scan QR code in Telegram:");
+                println!("Settings → Devices → Link Desktop Device\n");
+                display_qr_code(&token);
+                println!();
+                print_qr_countdown(expires_in);
+
+                last_token = Some(token);
             }
-            QrAuthResult::PasswordRequired => {
+            QrAction::UpdateCountdown { expires_in } => print_qr_countdown(expires_in),
+            QrAction::Migrate { dc_id, token } => {
+                let attempt = migrations.record()?;
+                info!(
+                    "Account lives on DC {}, importing login token there ({}/{})",
+                    dc_id, attempt, MAX_QR_MIGRATIONS
+                );
+                pending = Some(bot.import_login_token(dc_id, token).await?);
+                continue;
+            }
+            QrAction::WaitForConfirmation => {
                 // 2FA is enabled - user needs to confirm on their phone
                 // After scanning QR, Telegram will ask for 2FA password on the phone
                 println!(
-                    "\n2FA is enabled. Please confirm login on your phone and enter 2FA password there."
+                    "\n\n2FA is enabled. Please confirm login on your phone and enter 2FA password there."
                 );
                 println!("Waiting for confirmation...\n");
-                tokio::time::sleep(Duration::from_secs(3)).await;
+                tokio::time::sleep(jittered(QR_CONFIRM_INTERVAL_MS, time_seed())).await;
                 // Continue polling - success will come after phone confirmation
+                continue;
             }
-            QrAuthResult::MigrateTo { dc_id } => {
-                info!("Need to migrate to DC {}, retrying...", dc_id);
-                tokio::time::sleep(Duration::from_secs(1)).await;
+            QrAction::Done { user_id, username } => {
+                clear_screen();
+                let name = username.unwrap_or_else(|| format!("id:{user_id}"));
+                info!("Successfully authenticated as @{}", name);
+                println!("✓ Successfully authenticated as @{name}");
+                return Ok(());
             }
         }
+
+        tokio::time::sleep(jittered(QR_POLL_INTERVAL_MS, time_seed())).await;
     }
 }
 
+/// Rewrites the QR expiry countdown line in place.
+fn print_qr_countdown(expires_in: i64) {
+    print!("\rExpires in {expires_in} seconds...   ");
+    let _ = std::io::stdout().flush();
+}
+
 /// Clears the terminal screen using crossterm for WSL compatibility.
 fn clear_screen() {
     use crossterm::{
@@ -432,13 +1281,3 @@ fn display_qr_code(token: &[u8]) {
         }
     }
 }
-
-/// Truncates a string for logging.
-#[allow(dead_code)]
-fn truncate_log(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_owned()
-    } else {
-        format!("{}...", s.chars().take(max_len).collect::<String>())
-    }
-}
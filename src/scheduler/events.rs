@@ -0,0 +1,175 @@
+//! In-process notifications about what the scheduler does.
+//!
+//! Integrations (a GUI, a logger) call [`DescriptionScheduler::subscribe`]
+//! instead of polling the state. Events go through a bounded broadcast
+//! channel: a receiver that falls behind loses the oldest events and
+//! carries on with the newest.
+//!
+//! [`DescriptionScheduler::subscribe`]: super::DescriptionScheduler::subscribe
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// How many events a slow subscriber may fall behind before losing some.
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Something the scheduler did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SchedulerEvent {
+    /// The bio was changed.
+    Updated {
+        /// ID of the applied description (`custom` for a `set` text).
+        id: String,
+        /// Text sent to Telegram.
+        text: String,
+    },
+    /// The bio read back after an update differs from the text sent
+    /// (with `VERIFY_UPDATES`).
+    NotApplied {
+        /// ID of the applied description.
+        id: String,
+        /// Text sent to Telegram.
+        sent: String,
+        /// Bio Telegram shows instead.
+        actual: String,
+    },
+    /// Rotation was paused (by a command or after repeated failures).
+    Paused,
+    /// Rotation was resumed.
+    Resumed,
+    /// Telegram asked to wait before the next update.
+    FloodWait {
+        /// Seconds to wait.
+        secs: u32,
+    },
+    /// A bio update failed.
+    Error {
+        /// Error description.
+        msg: String,
+    },
+}
+
+/// Sending side of the event channel, owned by the scheduler.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<SchedulerEvent>,
+}
+
+impl EventBus {
+    /// Creates a bus keeping up to `capacity` undelivered events.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Sends an event to all current subscribers (none is fine).
+    pub fn emit(&self, event: SchedulerEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Returns a receiver for events emitted from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> EventReceiver {
+        EventReceiver {
+            inner: self.sender.subscribe(),
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EVENT_CHANNEL_CAPACITY)
+    }
+}
+
+/// Receiving side of the event channel.
+#[derive(Debug)]
+pub struct EventReceiver {
+    inner: broadcast::Receiver<SchedulerEvent>,
+}
+
+impl EventReceiver {
+    /// Waits for the next event.
+    ///
+    /// Events missed because the receiver lagged are skipped with a
+    /// warning. Returns `None` once the scheduler is gone.
+    pub async fn recv(&mut self) -> Option<SchedulerEvent> {
+        loop {
+            match self.inner.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!(
+                        "Scheduler event subscriber lagged, {} events dropped",
+                        missed
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Returns the event for a change of the paused flag, if it changed.
+#[must_use]
+pub const fn pause_transition(was_paused: bool, is_paused: bool) -> Option<SchedulerEvent> {
+    match (was_paused, is_paused) {
+        (false, true) => Some(SchedulerEvent::Paused),
+        (true, false) => Some(SchedulerEvent::Resumed),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn updated(id: &str) -> SchedulerEvent {
+        SchedulerEvent::Updated {
+            id: id.to_owned(),
+            text: format!("text {id}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_receives_update() {
+        let bus = EventBus::default();
+        // Emitting without subscribers is not an error
+        bus.emit(updated("early"));
+
+        let mut events = bus.subscribe();
+        bus.emit(updated("1"));
+        bus.emit(SchedulerEvent::FloodWait { secs: 30 });
+
+        assert_eq!(events.recv().await, Some(updated("1")));
+        assert_eq!(
+            events.recv().await,
+            Some(SchedulerEvent::FloodWait { secs: 30 })
+        );
+
+        drop(bus);
+        assert_eq!(events.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_subscriber_continues_with_newest() {
+        let bus = EventBus::new(2);
+        let mut events = bus.subscribe();
+        for id in ["1", "2", "3", "4"] {
+            bus.emit(updated(id));
+        }
+
+        assert_eq!(events.recv().await, Some(updated("3")));
+        assert_eq!(events.recv().await, Some(updated("4")));
+    }
+
+    #[test]
+    fn test_pause_transition() {
+        assert_eq!(pause_transition(false, true), Some(SchedulerEvent::Paused));
+        assert_eq!(pause_transition(true, false), Some(SchedulerEvent::Resumed));
+        assert_eq!(pause_transition(true, true), None);
+        assert_eq!(pause_transition(false, false), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! The inputs and outcome of the next rotation decision, for debugging.
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use super::{SchedulerState, decide_next, is_update_due};
+use crate::config::DescriptionConfig;
+
+/// Why the scheduler would (or would not) change the bio on its next tick.
+///
+/// The `debug` command formats it for chat and returns it as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent decision inputs
+pub struct NextExplanation {
+    /// Index of the current description (0-based).
+    pub current_index: usize,
+
+    /// Whether rotation is paused.
+    pub is_paused: bool,
+
+    /// Whether the pause keeps the bio from changing (`pause auto` still
+    /// lets a `set` text through).
+    pub is_held: bool,
+
+    /// Whether the current description's deadline has passed (or none is set).
+    pub is_expired: bool,
+
+    /// Whether a deadline is set.
+    pub has_deadline: bool,
+
+    /// Whether a `set` text is waiting to be applied.
+    pub has_custom: bool,
+
+    /// Whether an update is due (deadline, schedule window or weekday).
+    pub is_due: bool,
+
+    /// Whether the index would advance, if there is anything to show.
+    pub should_advance: Option<bool>,
+
+    /// Index that would be applied (unchanged for a custom text).
+    pub next_index: Option<usize>,
+
+    /// ID that would be applied (`"custom"` for a `set` text).
+    pub next_id: Option<String>,
+}
+
+impl NextExplanation {
+    /// Explains the decision for `state` and `config` at local time `now`.
+    #[must_use]
+    pub fn capture(state: &SchedulerState, config: &DescriptionConfig, now: NaiveDateTime) -> Self {
+        let next = decide_next(state, config, now);
+        Self {
+            current_index: state.current_index,
+            is_paused: state.is_paused,
+            is_held: state.holds_updates(),
+            is_expired: state.is_expired(),
+            has_deadline: state.has_deadline(),
+            has_custom: state.custom_description.is_some(),
+            is_due: is_update_due(state, config, now),
+            should_advance: next.as_ref().map(|n| n.should_advance),
+            next_index: next.as_ref().map(|n| n.index),
+            next_id: next.map(|n| n.description_id),
+        }
+    }
+
+    /// Whether the next tick would change the bio.
+    #[must_use]
+    pub const fn would_update(&self) -> bool {
+        !self.is_held && self.is_due && self.next_index.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Local time on Wednesday, 2024-05-01.
+    fn noon() -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_explain_without_deadline() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.current_index = 1;
+
+        let explanation = NextExplanation::capture(&state, &config, noon());
+        assert_eq!(
+            explanation,
+            NextExplanation {
+                current_index: 1,
+                is_paused: false,
+                is_held: false,
+                is_expired: true,
+                has_deadline: false,
+                has_custom: false,
+                is_due: true,
+                should_advance: Some(false),
+                next_index: Some(1),
+                next_id: Some("working".to_owned()),
+            }
+        );
+        assert!(explanation.would_update());
+    }
+
+    #[test]
+    fn test_explain_with_running_deadline() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.set_deadline(3600);
+
+        let explanation = NextExplanation::capture(&state, &config, noon());
+        assert!(explanation.has_deadline);
+        assert!(!explanation.is_expired);
+        assert!(!explanation.is_due);
+        // Once due, the rotation moves on to the next description
+        assert_eq!(explanation.should_advance, Some(true));
+        assert_eq!(explanation.next_index, Some(1));
+        assert!(!explanation.would_update());
+    }
+
+    #[test]
+    fn test_explain_custom_text() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.set_deadline(3600);
+        state.set_custom("Lunch".to_owned(), 900);
+        state.is_paused = true;
+
+        let explanation = NextExplanation::capture(&state, &config, noon());
+        assert!(explanation.has_custom);
+        assert_eq!(explanation.next_id.as_deref(), Some("custom"));
+        assert_eq!(explanation.next_index, Some(0));
+        assert_eq!(explanation.should_advance, Some(false));
+        assert!(!explanation.would_update());
+    }
+}
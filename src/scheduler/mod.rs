@@ -3,8 +3,32 @@
 //! Manages the automatic rotation of profile descriptions
 //! according to configured durations.
 
+mod clock;
+mod events;
+mod explain;
+mod presence;
+mod render;
 mod runner;
+mod simulate;
+mod snapshot;
 mod state;
+mod stats;
+mod store;
 
-pub use runner::{DescriptionScheduler, SchedulerMessage};
-pub use state::{PersistentState, SchedulerState};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use events::{EVENT_CHANNEL_CAPACITY, EventBus, EventReceiver, SchedulerEvent};
+pub use explain::NextExplanation;
+pub use presence::{PRESENCE_MAX_DEFER_SECS, PRESENCE_RECENT_SECS, PresenceGate};
+pub use render::{RenderedBio, render};
+pub use runner::{
+    DescriptionScheduler, NextUpdate, SchedulerMessage, decide_next, initial_state, is_update_due,
+    startup_delay,
+};
+pub use simulate::{SIMULATION_STEP_SECS, SimulatedUpdate, simulate};
+pub use snapshot::StatusSnapshot;
+pub use state::{
+    CLOCK_JUMP_FACTOR, HEALTH_MARGIN_SECS, HEARTBEAT_SECS, LastError, PauseMode, PersistentState,
+    QueuedText, SchedulerState,
+};
+pub use stats::{DescriptionStats, DisplayStats};
+pub use store::{FileStateStore, NullStateStore, ReadOnlyStateStore, StateStore, state_store};
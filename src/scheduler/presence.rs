@@ -0,0 +1,121 @@
+//! Presence-aware deferral of bio updates.
+//!
+//! With `SUPPRESS_WHILE_ONLINE`, a regular rotation is held back while the
+//! account was online recently, so the bio does not change in the middle
+//! of a conversation. An update is never deferred for longer than
+//! [`PRESENCE_MAX_DEFER_SECS`].
+
+/// Seconds after being seen online during which the account counts as active.
+pub const PRESENCE_RECENT_SECS: u64 = 60;
+
+/// Longest time a due update may be deferred, in seconds.
+pub const PRESENCE_MAX_DEFER_SECS: u64 = 900;
+
+/// Minimum seconds between two presence checks against Telegram.
+const PRESENCE_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Decides whether a due update should wait for the user to go idle.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PresenceGate {
+    /// Unix time the account was last seen online.
+    last_online: Option<u64>,
+
+    /// Unix time of the last presence check.
+    last_checked: Option<u64>,
+
+    /// Unix time the current update was first deferred.
+    deferred_since: Option<u64>,
+}
+
+impl PresenceGate {
+    /// Creates a gate with no presence information.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            last_online: None,
+            last_checked: None,
+            deferred_since: None,
+        }
+    }
+
+    /// Checks if presence should be fetched again (throttled).
+    #[must_use]
+    pub fn check_due(&self, now: u64) -> bool {
+        self.last_checked
+            .is_none_or(|at| now.saturating_sub(at) >= PRESENCE_CHECK_INTERVAL_SECS)
+    }
+
+    /// Records the result of a presence check.
+    pub fn record(&mut self, online: bool, now: u64) {
+        self.last_checked = Some(now);
+        if online {
+            self.last_online = Some(now);
+        }
+    }
+
+    /// Checks if a due update should be deferred at `now`.
+    ///
+    /// Defers while the account was online within [`PRESENCE_RECENT_SECS`],
+    /// but not for longer than [`PRESENCE_MAX_DEFER_SECS`] in total.
+    pub fn should_defer(&mut self, now: u64) -> bool {
+        let recently_active = self
+            .last_online
+            .is_some_and(|at| now.saturating_sub(at) < PRESENCE_RECENT_SECS);
+
+        if !recently_active {
+            self.deferred_since = None;
+            return false;
+        }
+
+        let since = *self.deferred_since.get_or_insert(now);
+        if now.saturating_sub(since) >= PRESENCE_MAX_DEFER_SECS {
+            self.deferred_since = None;
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defer_while_recently_active() {
+        let mut gate = PresenceGate::new();
+        assert!(!gate.should_defer(1_000)); // Never seen online
+
+        gate.record(true, 1_000);
+        assert!(gate.should_defer(1_010));
+        assert!(gate.should_defer(1_000 + PRESENCE_RECENT_SECS - 1));
+
+        // Idle long enough: update goes through
+        assert!(!gate.should_defer(1_000 + PRESENCE_RECENT_SECS));
+    }
+
+    #[test]
+    fn test_defer_is_bounded() {
+        let mut gate = PresenceGate::new();
+        let start = 10_000;
+        gate.record(true, start);
+        assert!(gate.should_defer(start));
+
+        // Online the whole time
+        let late = start + PRESENCE_MAX_DEFER_SECS;
+        gate.record(true, late);
+        assert!(!gate.should_defer(late));
+
+        // The next due update may be deferred again
+        assert!(gate.should_defer(late + 1));
+    }
+
+    #[test]
+    fn test_check_is_throttled() {
+        let mut gate = PresenceGate::new();
+        assert!(gate.check_due(1_000));
+
+        gate.record(false, 1_000);
+        assert!(!gate.check_due(1_010));
+        assert!(gate.check_due(1_000 + PRESENCE_CHECK_INTERVAL_SECS));
+    }
+}
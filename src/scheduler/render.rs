@@ -0,0 +1,148 @@
+//! The bio a description turns into when it is applied.
+//!
+//! The scheduler runs every text through [`render_text`] before sending it,
+//! and the `render` command previews the result without touching the
+//! account.
+
+use serde::Serialize;
+
+use crate::config::{
+    Description, DescriptionConfig, OverlongPolicy, normalize_bio, truncate_to_limit, utf16_len,
+};
+
+/// The final bio text for a description, as it would be sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderedBio {
+    /// ID of the description (`"custom"`/`"queued"` for other texts).
+    pub id: String,
+
+    /// Text that is sent to Telegram.
+    pub text: String,
+
+    /// Length of `text` in UTF-16 units, as Telegram counts it.
+    pub utf16_len: usize,
+
+    /// Bio length limit of the account.
+    pub max_len: usize,
+
+    /// Length before truncation, if the text was cut to fit.
+    pub truncated_from: Option<usize>,
+
+    /// Whether an emoji from the description's pool was added; it is
+    /// picked at random on each update.
+    pub decorated: bool,
+}
+
+impl RenderedBio {
+    /// Checks if Telegram would reject the text as too long.
+    #[must_use]
+    pub const fn is_overlong(&self) -> bool {
+        self.utf16_len > self.max_len
+    }
+}
+
+/// Renders the description with ID `id` in the active profile.
+///
+/// `seed` picks the emoji from the pool, so the same seed gives the same
+/// text. Returns `None` if there is no such description.
+#[must_use]
+pub fn render(
+    config: &DescriptionConfig,
+    id: &str,
+    on_overlong: OverlongPolicy,
+    seed: u64,
+) -> Option<RenderedBio> {
+    let desc = config.active_descriptions().iter().find(|d| d.id == id)?;
+    Some(render_text(
+        id,
+        &desc.text,
+        Some(desc),
+        config.max_bio_length(),
+        on_overlong,
+        seed,
+    ))
+}
+
+/// Turns `text` into the bio that is sent: normalized, decorated with an
+/// emoji from `desc`'s pool and, if the policy says so, truncated to
+/// `max_len`.
+pub(super) fn render_text(
+    id: &str,
+    text: &str,
+    desc: Option<&Description>,
+    max_len: usize,
+    on_overlong: OverlongPolicy,
+    seed: u64,
+) -> RenderedBio {
+    // Measure the text as Telegram will store it
+    let text = normalize_bio(text);
+    let decorated = desc.is_some_and(|d| !d.emoji_pool.is_empty());
+    let mut text = desc.map_or_else(|| text.to_owned(), |d| d.decorate(text, seed));
+
+    let mut truncated_from = None;
+    if on_overlong == OverlongPolicy::Truncate && utf16_len(&text) > max_len {
+        truncated_from = Some(utf16_len(&text));
+        text = truncate_to_limit(&text, max_len);
+    }
+
+    RenderedBio {
+        id: id.to_owned(),
+        utf16_len: utf16_len(&text),
+        text,
+        max_len,
+        truncated_from,
+        decorated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Decoration, MAX_BIO_LENGTH_FREE};
+
+    fn config_with(desc: Description) -> DescriptionConfig {
+        let mut config = DescriptionConfig::example();
+        config.active_descriptions_mut().push(desc);
+        config
+    }
+
+    #[test]
+    fn test_render_decorated_and_truncated() {
+        let desc = Description::builder()
+            .id("long")
+            .text(format!("{}   ", "a".repeat(80)))
+            .duration_secs(60)
+            .emoji_pool(["🌙"], Decoration::Suffix)
+            .build()
+            .unwrap();
+        let config = config_with(desc);
+
+        let rendered = render(&config, "long", OverlongPolicy::Truncate, 7).unwrap();
+        // Trailing spaces dropped, then " 🌙" added: 80 + 1 + 2 units
+        assert_eq!(rendered.truncated_from, Some(83));
+        assert_eq!(rendered.utf16_len, MAX_BIO_LENGTH_FREE);
+        assert_eq!(rendered.text, format!("{}…", "a".repeat(69)));
+        assert!(rendered.decorated);
+        assert!(!rendered.is_overlong());
+
+        // Rejecting instead sends the full text, which Telegram refuses
+        let rendered = render(&config, "long", OverlongPolicy::Reject, 7).unwrap();
+        assert_eq!(rendered.text, format!("{} 🌙", "a".repeat(80)));
+        assert_eq!(rendered.truncated_from, None);
+        assert!(rendered.is_overlong());
+    }
+
+    #[test]
+    fn test_render_plain_text_unchanged() {
+        let config = DescriptionConfig::example();
+        let rendered = render(&config, "working", OverlongPolicy::Truncate, 0).unwrap();
+        assert_eq!(rendered.text, "💻 Currently working...");
+        assert_eq!(rendered.utf16_len, utf16_len("💻 Currently working..."));
+        assert!(!rendered.decorated);
+
+        assert_eq!(
+            render(&config, "missing", OverlongPolicy::Truncate, 0),
+            None
+        );
+    }
+}
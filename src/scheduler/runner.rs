@@ -1,29 +1,51 @@
 //! Description scheduler runner.
 //!
 //! The scheduler follows a simple state machine:
-//! 1. Check if expired (deadline passed or no deadline)
-//! 2. If expired and not paused:
+//! 1. Check if expired (deadline passed, no deadline, or a schedule window
+//!    opened/closed). A deadline left far ahead by the clock being set back
+//!    is cleared first, re-applying the current description; one left far
+//!    behind by a jump forward counts as expired
+//! 2. If expired, not paused and outside quiet hours:
 //!    - If custom description is set → use it, then clear it
+//!    - Else if a schedule window is active → use its description
 //!    - Else if has deadline (regular expiration) → advance to next
 //!    - Else (no deadline, e.g. after goto/skip) → use current index
-//! 3. Apply the description via API
+//!
+//!    Descriptions limited to other weekdays are skipped throughout
+//! 3. With `suppress_while_online`, hold back a regular rotation while the
+//!    account is online (bounded), then apply the description via API
 //! 4. On success → set new deadline and save state
 //!
+//! All time reads go through the state's [`Clock`], so tests can drive the
+//! scheduler with a `MockClock`.
+//!
 //! Commands modify state and SAVE immediately:
 //! - goto/skip: set index + clear deadline + save
 //! - pause/resume: set flag + save
 //! - set: set custom description + clear deadline + save
 
-use std::sync::Arc;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLockReadGuard};
 use std::time::Duration;
 
-use tokio::sync::{RwLock, mpsc};
-use tokio::time::interval;
+use chrono::{Datelike, NaiveDateTime};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio::time::{Instant, interval_at};
 use tracing::{debug, error, info, warn};
 
-use super::SchedulerState;
-use crate::config::DescriptionConfig;
+use super::events::pause_transition;
+use super::render::render_text;
+use super::{
+    Clock, EventBus, EventReceiver, HEARTBEAT_SECS, NextExplanation, PersistentState, PresenceGate,
+    SchedulerEvent, SchedulerState, StateStore, StatusSnapshot,
+};
+use crate::config::{
+    BotSettings, DEFAULT_CUSTOM_DURATION_SECS, DescriptionConfig, OverlongPolicy, ResumePolicy,
+    SharedSettings, normalize_bio,
+};
 use crate::telegram::{TelegramBot, TelegramError};
+use crate::util::truncate;
 
 /// Messages that can be sent to the scheduler.
 #[derive(Debug, Clone)]
@@ -45,11 +67,29 @@ pub struct DescriptionScheduler {
     /// Scheduler state.
     state: Arc<RwLock<SchedulerState>>,
 
-    /// Path to save persistent state.
-    state_path: String,
+    /// Where persistent state is saved.
+    state_store: Arc<dyn StateStore>,
 
     /// Check interval for state changes.
     check_interval: Duration,
+
+    /// Bot settings (overlong policy, etc.).
+    settings: SharedSettings,
+
+    /// Defers rotation while the account is online.
+    presence: Mutex<PresenceGate>,
+
+    /// Notifications for subscribers.
+    events: EventBus,
+
+    /// Paused flag seen on the previous tick, for `Paused`/`Resumed` events.
+    was_paused: AtomicBool,
+
+    /// Whether the empty configuration was already reported.
+    warned_empty: AtomicBool,
+
+    /// When the state was last saved as a heartbeat.
+    last_heartbeat_unix: AtomicU64,
 }
 
 impl DescriptionScheduler {
@@ -59,17 +99,41 @@ impl DescriptionScheduler {
         bot: Arc<TelegramBot>,
         config: Arc<RwLock<DescriptionConfig>>,
         state: Arc<RwLock<SchedulerState>>,
-        state_path: String,
+        state_store: Arc<dyn StateStore>,
     ) -> Self {
         Self {
             bot,
             config,
             state,
-            state_path,
+            state_store,
             check_interval: Duration::from_secs(1),
+            settings: SharedSettings::default(),
+            presence: Mutex::new(PresenceGate::new()),
+            events: EventBus::default(),
+            was_paused: AtomicBool::new(false),
+            warned_empty: AtomicBool::new(false),
+            last_heartbeat_unix: AtomicU64::new(0),
         }
     }
 
+    /// Sets the bot settings used by the scheduler.
+    #[must_use]
+    pub fn with_settings(self, settings: BotSettings) -> Self {
+        self.with_shared_settings(Arc::new(std::sync::RwLock::new(settings)))
+    }
+
+    /// Sets bot settings shared with other components.
+    #[must_use]
+    pub fn with_shared_settings(mut self, settings: SharedSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Returns the current bot settings.
+    fn settings(&self) -> RwLockReadGuard<'_, BotSettings> {
+        self.settings.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Sets the check interval for state changes.
     #[must_use]
     pub const fn with_check_interval(mut self, interval: Duration) -> Self {
@@ -77,11 +141,32 @@ impl DescriptionScheduler {
         self
     }
 
+    /// Returns a receiver for scheduler events (updates, pauses, errors).
+    #[must_use]
+    pub fn subscribe(&self) -> EventReceiver {
+        self.events.subscribe()
+    }
+
     /// Runs the scheduler loop.
     pub async fn run(&self, mut rx: mpsc::Receiver<SchedulerMessage>) {
         info!("Description scheduler started");
+        self.was_paused
+            .store(self.state.read().await.is_paused, Ordering::Relaxed);
 
-        let mut check_timer = interval(self.check_interval);
+        // Spread the first updates of many instances started together;
+        // commands are still handled meanwhile
+        let jitter_secs = self.settings().startup_jitter_secs;
+        let delay = startup_delay(
+            jitter_secs,
+            RandomState::new().hash_one(self.state.read().await.clock().now_unix()),
+        );
+        if jitter_secs > 0 {
+            info!(
+                "Delaying the first update check by {}s (startup jitter)",
+                delay.as_secs()
+            );
+        }
+        let mut check_timer = interval_at(Instant::now() + delay, self.check_interval);
 
         loop {
             tokio::select! {
@@ -106,105 +191,332 @@ impl DescriptionScheduler {
 
     /// Single tick of the scheduler.
     async fn tick(&self) {
+        self.update_outcome(self.run_once().await).await;
+
+        let is_paused = self.state.read().await.is_paused;
+        let was_paused = self.was_paused.swap(is_paused, Ordering::Relaxed);
+        if let Some(event) = pause_transition(was_paused, is_paused) {
+            self.events.emit(event);
+        }
+        if needs_heartbeat(&*self.state.read().await) {
+            self.heartbeat().await;
+        }
+    }
+
+    /// Saves the state every [`HEARTBEAT_SECS`] while nothing else does
+    /// (see [`needs_heartbeat`]), so `--health` sees the scheduler alive.
+    async fn heartbeat(&self) {
+        let mut state = self.state.write().await;
+        let now = state.now_unix();
+        let last = self.last_heartbeat_unix.load(Ordering::Relaxed);
+        if now.saturating_sub(last) < HEARTBEAT_SECS {
+            return;
+        }
+
+        self.last_heartbeat_unix.store(now, Ordering::Relaxed);
+        if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+            warn!("Failed to save state for the health check: {}", e);
+        }
+    }
+
+    /// Records the result of an update attempt.
+    async fn update_outcome(&self, result: Result<Option<String>, TelegramError>) {
+        if let Some(message) = last_error_message(&result) {
+            self.state.write().await.record_error(message);
+        }
+
+        match result {
+            Ok(Some(_)) => self.state.write().await.record_success(),
+            // A rejected description was already reported and is skipped from now on
+            Ok(None) | Err(TelegramError::BioTooLong) => {}
+            Err(TelegramError::RateLimited(seconds)) => {
+                debug!("Rate limited, {} seconds remaining", seconds);
+                // Don't modify state - scheduler will retry on next tick
+            }
+            Err(TelegramError::FloodWait(seconds)) => {
+                warn!("Flood wait from Telegram: {} seconds", seconds);
+                self.events
+                    .emit(SchedulerEvent::FloodWait { secs: seconds });
+                // Don't modify state - will retry later
+            }
+            Err(e) => {
+                self.events
+                    .emit(SchedulerEvent::Error { msg: e.to_string() });
+                let mut state = self.state.write().await;
+                if state.record_failure(self.settings().failure_threshold) {
+                    error!(
+                        "Failed to update bio {} times in a row, pausing rotation \
+                         (last error: {}). Use 'resume' to try again.",
+                        state.consecutive_failures(),
+                        e
+                    );
+                    if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+                        warn!("Failed to save state: {}", e);
+                    }
+                } else {
+                    error!("Failed to update bio: {}", e);
+                }
+                // Otherwise don't modify state - will retry on next tick
+            }
+        }
+    }
+
+    /// Returns the current status of the rotation.
+    pub async fn snapshot(&self) -> StatusSnapshot {
+        let state = self.state.read().await;
+        let config = self.config.read().await;
+        StatusSnapshot::capture(&state, &config, &self.settings())
+    }
+
+    /// Explains what the next tick would do, from the same inputs
+    /// [`Self::run_once`] decides on (quiet hours aside).
+    pub async fn explain_next(&self) -> NextExplanation {
+        let state = self.state.read().await;
+        let config = self.config.read().await;
+        let now = state
+            .clock()
+            .now_utc()
+            .with_timezone(&self.settings().timezone)
+            .naive_local();
+        NextExplanation::capture(&state, &config, now)
+    }
+
+    /// Applies the username and emoji status that come with a description.
+    ///
+    /// The bio is what matters, so failures here are only logged.
+    async fn apply_extras(&self, next: &NextUpdate) {
+        if let Some(username) = &next.username
+            && let Err(e) = self.bot.update_username(username).await
+        {
+            warn!(
+                "{}",
+                username_failure_message(&next.description_id, username, &e)
+            );
+        }
+
+        if let Some(document_id) = next.emoji_status_id
+            && let Err(e) = self.bot.update_emoji_status(document_id).await
+        {
+            warn!(
+                "Failed to set emoji status for [{}]: {}",
+                next.description_id, e
+            );
+        }
+    }
+
+    /// Applies the next description if an update is due.
+    ///
+    /// Returns the ID of the applied description, or `None` if nothing was
+    /// due (paused, quiet hours, deadline not reached, or no descriptions
+    /// configured).
+    ///
+    /// # Errors
+    ///
+    /// Returns the Telegram error if the bio update failed. State is left
+    /// untouched.
+    pub async fn run_once(&self) -> Result<Option<String>, TelegramError> {
+        let clock = self.clock().await;
+        let now = clock
+            .now_utc()
+            .with_timezone(&self.settings().timezone)
+            .naive_local();
+
+        // Keep the current bio during quiet hours; the next due update
+        // (only one) is applied once they end. Meanwhile the expired
+        // deadline makes `tick` save heartbeats for `--health`
+        if self.settings().is_quiet_at(clock.now_utc()) {
+            return Ok(None);
+        }
+
+        // Don't trust a deadline set before a system clock jump
+        {
+            let mut state = self.state.write().await;
+            let config = self.config.read().await;
+            let fallback_secs = config
+                .active_descriptions()
+                .iter()
+                .map(|d| d.duration_secs)
+                .max()
+                .unwrap_or(0);
+            if !state.is_paused && state.resync_clock(fallback_secs) {
+                warn!("System clock jumped, resynchronizing the rotation deadline");
+            }
+        }
+
         // Step 1: Quick check if we should even try
         {
             let state = self.state.read().await;
-            if state.is_paused || !state.is_expired() {
-                return;
+            let config = self.config.read().await;
+            if !wants_update(&state, &config, now) {
+                return Ok(None);
             }
         }
 
         // Step 2: Determine what to update (READ ONLY - don't modify state yet)
-        let (text, duration_secs, description_id, should_advance, has_custom) = {
+        let next = {
             let state = self.state.read().await;
             let config = self.config.read().await;
 
             // Re-check under lock
-            if state.is_paused || !state.is_expired() {
-                return;
+            if !wants_update(&state, &config, now) {
+                return Ok(None);
             }
 
+            if first_empty_report(&self.warned_empty, config.is_empty()) {
+                warn!("No descriptions configured, waiting for 'add' or 'reload'");
+            }
             if config.is_empty() {
-                warn!("No descriptions configured");
-                return;
+                return Ok(None);
             }
 
-            // Figure out what we'll update (without modifying state)
-            if let Some(ref custom) = state.custom_description {
-                // Custom description
-                (custom.clone(), 3600u64, "custom".to_owned(), false, true)
-            } else {
-                // Regular rotation
-                let should_advance = state.has_deadline();
-                let next_index = if should_advance {
-                    (state.current_index + 1) % config.len()
-                } else {
-                    state.current_index
-                };
-
-                let desc = config.get(next_index).or_else(|| config.get(0));
-                let Some(desc) = desc else {
-                    error!("No description available");
-                    return;
-                };
-
-                (
-                    desc.text.clone(),
-                    desc.duration_secs,
-                    desc.id.clone(),
-                    should_advance,
-                    false,
-                )
-            }
+            let Some(mut next) = decide_next(&state, &config, now) else {
+                debug!("No eligible description available");
+                return Ok(None);
+            };
+
+            log_decision(&config, &next, now);
+
+            prepare_text(
+                &mut next,
+                &config,
+                self.settings().on_overlong,
+                clock.now_unix(),
+            );
+            next
         };
 
+        // Commands and custom texts are applied right away
+        if (next.should_advance || next.is_queued)
+            && self.defer_for_presence(clock.now_unix()).await
+        {
+            return Ok(None);
+        }
+
         // Step 3: Make API call (no locks held)
         debug!(
             "Updating bio to [{}]: \"{}\"",
-            description_id,
-            truncate(&text, 30)
+            next.description_id,
+            truncate(&next.text, 30)
         );
 
-        match self.bot.update_bio(&text).await {
-            Ok(()) => {
-                // Step 4: On SUCCESS, modify state and save
-                let mut state = self.state.write().await;
-                let config = self.config.read().await;
+        if let Err(e) = self.bot.update_bio(&next.text).await {
+            if matches!(e, TelegramError::BioTooLong) {
+                self.reject(&next).await;
+            }
+            return Err(e);
+        }
 
-                // Apply the changes we decided on
-                if has_custom {
-                    state.custom_description = None;
-                } else if should_advance {
-                    state.advance(config.len());
-                }
+        self.apply_extras(&next).await;
 
-                state.set_deadline(duration_secs);
+        // Step 4: On SUCCESS, modify state and save
+        let mut state = self.state.write().await;
 
-                // Save state to disk
-                if let Err(e) = state.to_persistent().save(&self.state_path) {
-                    warn!("Failed to save state: {}", e);
-                }
+        // Apply the changes we decided on
+        if next.is_queued {
+            state.queue.pop_front();
+        } else if next.is_custom {
+            state.clear_custom();
+        } else {
+            state.current_index = next.index;
+        }
+
+        state.set_deadline(next.duration_secs);
+        state.record_shown((!next.is_custom).then_some(next.description_id.as_str()));
 
-                info!(
-                    "Bio updated to [{}], next update in {} seconds",
-                    description_id, duration_secs
-                );
+        // Save state to disk
+        if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+            warn!("Failed to save state: {}", e);
+        }
+
+        info!(
+            "Bio updated to [{}], next update in {} seconds",
+            next.description_id, next.duration_secs
+        );
+        if self.settings().verify_updates {
+            self.verify_update(&next.description_id, &next.text).await;
+        }
+        self.events.emit(SchedulerEvent::Updated {
+            id: next.description_id.clone(),
+            text: next.text,
+        });
+
+        Ok(Some(next.description_id))
+    }
+
+    /// Reads the bio back and warns if Telegram did not store the text sent.
+    ///
+    /// The state has already moved on; a mismatch is only reported.
+    async fn verify_update(&self, id: &str, sent: &str) {
+        match self.bot.get_current_bio().await {
+            Ok(actual) => {
+                if let Some(event) = bio_mismatch(id, sent, &actual) {
+                    warn!(
+                        "Bio update to [{}] did not take effect, the profile shows \"{}\"",
+                        id,
+                        truncate(&actual, 30)
+                    );
+                    self.events.emit(event);
+                }
             }
-            Err(TelegramError::RateLimited(seconds)) => {
-                debug!("Rate limited, {} seconds remaining", seconds);
-                // Don't modify state - scheduler will retry on next tick
+            Err(e) => debug!("Failed to verify the bio update: {}", e),
+        }
+    }
+
+    /// Checks if a regular rotation should wait because the account is
+    /// online (with `suppress_while_online`).
+    async fn defer_for_presence(&self, now: u64) -> bool {
+        if !self.settings().suppress_while_online {
+            return false;
+        }
+
+        let mut gate = self.presence.lock().await;
+
+        if gate.check_due(now) {
+            match self.bot.self_is_online().await {
+                Ok(online) => gate.record(online, now),
+                Err(e) => {
+                    debug!("Failed to check presence: {}", e);
+                    gate.record(false, now);
+                }
             }
-            Err(TelegramError::FloodWait(seconds)) => {
-                warn!("Flood wait from Telegram: {} seconds", seconds);
-                // Don't modify state - will retry later
+        }
+
+        let defer = gate.should_defer(now);
+        if defer {
+            debug!("Account is online, deferring the bio update");
+        }
+        defer
+    }
+
+    /// Stops retrying a description the server refused as too long.
+    async fn reject(&self, next: &NextUpdate) {
+        let mut state = self.state.write().await;
+
+        if next.is_custom {
+            if next.is_queued {
+                warn!("Telegram rejected the queued text as too long, discarding it");
+                state.queue.pop_front();
+            } else {
+                warn!("Telegram rejected the custom description as too long, discarding it");
+                state.clear_custom();
             }
-            Err(e) => {
-                error!("Failed to update bio: {}", e);
-                // Don't modify state - will retry on next tick
+            if let Err(e) = state.save(&*self.state_store, &self.settings()) {
+                warn!("Failed to save state: {}", e);
             }
+        } else {
+            warn!(
+                "Telegram rejected [{}] as too long, skipping it in rotation",
+                next.description_id
+            );
+            state.mark_rejected(&next.description_id);
         }
     }
 
+    /// Returns the time source, shared with the scheduler state.
+    async fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(self.state.read().await.clock())
+    }
+
     /// Gets a reference to the scheduler state.
     #[must_use]
     pub fn state(&self) -> &Arc<RwLock<SchedulerState>> {
@@ -218,12 +530,380 @@ impl DescriptionScheduler {
     }
 }
 
-/// Truncates a string for display.
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_owned()
+/// The update the scheduler would apply next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NextUpdate {
+    /// Bio text to set.
+    pub text: String,
+    /// How long the text stays before the next rotation.
+    pub duration_secs: u64,
+    /// ID of the description (`"custom"` for a `set` text, `"queued"` for a
+    /// queued one).
+    pub description_id: String,
+    /// Index to store as current once the update succeeds
+    /// (unchanged for a custom description).
+    pub index: usize,
+    /// Emoji status to apply with the bio (only set for Premium accounts).
+    pub emoji_status_id: Option<i64>,
+    /// Username to apply with the bio.
+    pub username: Option<String>,
+    /// Whether the index should advance once the update succeeds.
+    pub should_advance: bool,
+    /// Whether this is a custom text rather than a configured description
+    /// (a `set` text or the front of the queue).
+    pub is_custom: bool,
+    /// Whether this is the front of the queue, removed once it is shown.
+    pub is_queued: bool,
+}
+
+/// Decides which description should be applied next.
+///
+/// This is the pure decision logic of the scheduler:
+/// - A pending custom description always wins
+/// - With a deadline set, the oldest queued text comes next
+/// - A description whose schedule window contains `now` is pinned
+/// - With a deadline set (regular expiration) → the next unscheduled index,
+///   unless the current description is sticky (after a custom or queued
+///   text it comes back)
+/// - Without a deadline (first run, after goto/skip) → the current index,
+///   or the next unscheduled one if it is scheduled
+///
+/// Descriptions rejected by Telegram, not active on today's weekday or
+/// without the tag chosen with `only` are skipped. When nothing else is
+/// eligible, the configured fallback is shown. Without one, if every
+/// description is scheduled and no window is active, the current one is
+/// kept, and if none is active today, the first usable one is used. Pause
+/// and expiry are not checked here. Returns `None` if there is nothing to
+/// show.
+#[must_use]
+pub fn decide_next(
+    state: &SchedulerState,
+    config: &DescriptionConfig,
+    now: NaiveDateTime,
+) -> Option<NextUpdate> {
+    if let Some(ref custom) = state.custom_description {
+        return Some(NextUpdate {
+            text: custom.clone(),
+            duration_secs: state
+                .custom_duration_secs
+                .unwrap_or(DEFAULT_CUSTOM_DURATION_SECS),
+            description_id: "custom".to_owned(),
+            index: state.current_index,
+            emoji_status_id: None,
+            username: None,
+            should_advance: false,
+            is_custom: true,
+            is_queued: false,
+        });
+    }
+
+    // Queued texts take their turn as the current description expires
+    if state.has_deadline()
+        && let Some(queued) = state.queue.front()
+    {
+        return Some(NextUpdate {
+            text: queued.text.clone(),
+            duration_secs: queued.duration_secs,
+            description_id: "queued".to_owned(),
+            index: state.current_index,
+            emoji_status_id: None,
+            username: None,
+            should_advance: false,
+            is_custom: true,
+            is_queued: true,
+        });
+    }
+
+    if config.is_empty() {
+        return None;
+    }
+
+    let should_advance =
+        state.has_deadline() && !config.get(state.current_index).is_some_and(|d| d.sticky);
+    let fallback = config.fallback_index();
+    let index = pinned_index(state, config, now).or_else(|| {
+        let len = config.len();
+        let usable = |i: usize| config.get(i).is_some_and(|d| !state.is_rejected(&d.id));
+        let today = |i: usize| {
+            usable(i)
+                && Some(i) != fallback
+                && config
+                    .get(i)
+                    .is_some_and(|d| d.active_on(now.weekday()) && state.in_tag(d))
+        };
+        let first = usize::from(should_advance);
+
+        (first..first + len)
+            .map(|offset| (state.current_index + offset) % len)
+            .find(|&i| today(i) && config.get(i).is_some_and(|d| d.schedule.is_none()))
+            .or_else(|| fallback.filter(|&i| usable(i)))
+            .or_else(|| {
+                (0..len)
+                    .map(|offset| (state.current_index + offset) % len)
+                    .find(|&i| today(i))
+            })
+            .or_else(|| (0..len).find(|&i| usable(i)))
+    })?;
+
+    let desc = config.get(index)?;
+
+    Some(NextUpdate {
+        text: desc.text.clone(),
+        duration_secs: desc.duration_secs,
+        description_id: desc.id.clone(),
+        index,
+        emoji_status_id: desc.emoji_status_id.filter(|_| config.is_premium),
+        username: desc.username.clone(),
+        should_advance,
+        is_custom: false,
+        is_queued: false,
+    })
+}
+
+/// Returns how long the scheduler waits before its first tick: a duration
+/// in `[0, jitter_secs]` seconds picked by `seed`.
+///
+/// Only the first check moves; a deadline still pending afterwards is kept.
+#[must_use]
+pub const fn startup_delay(jitter_secs: u64, seed: u64) -> Duration {
+    match seed.checked_rem(jitter_secs.saturating_add(1)) {
+        Some(secs) => Duration::from_secs(secs),
+        None => Duration::ZERO,
+    }
+}
+
+/// Builds the scheduler state for a new run.
+///
+/// Persisted state is resumed according to `resume_policy`, unless it was
+/// saved for another account than `account_id` (e.g. after the session path
+/// changed), in which case the run starts fresh. On a fresh start with
+/// `random_start`, the first description is picked from the regular
+/// rotation using `seed`.
+#[must_use]
+pub fn initial_state(
+    persistent: Option<&PersistentState>,
+    account_id: Option<i64>,
+    config: &DescriptionConfig,
+    resume_policy: ResumePolicy,
+    random_start: bool,
+    seed: u64,
+) -> SchedulerState {
+    match persistent {
+        Some(persistent) if persistent.belongs_to(account_id) => {
+            let mut state = SchedulerState::from_persistent(persistent, resume_policy);
+            state.account_id = account_id.or(persistent.account_id);
+            return state;
+        }
+        Some(persistent) => warn!(
+            "State was saved for account {} but the session is account {}; starting fresh",
+            persistent.account_id.unwrap_or_default(),
+            account_id.unwrap_or_default()
+        ),
+        None => {}
+    }
+
+    let mut state = SchedulerState::new();
+    state.account_id = account_id;
+    if random_start {
+        let fallback = config.fallback_index();
+        let rotating: Vec<usize> = config
+            .active_descriptions()
+            .iter()
+            .enumerate()
+            .filter(|&(i, d)| d.schedule.is_none() && Some(i) != fallback)
+            .map(|(i, _)| i)
+            .collect();
+
+        // The remainder is below `rotating.len()`, so it fits in usize
+        #[allow(clippy::cast_possible_truncation)]
+        let pick = seed.checked_rem(rotating.len() as u64).unwrap_or(0) as usize;
+        if let Some(&index) = rotating.get(pick) {
+            state.current_index = index;
+        }
+    }
+    state
+}
+
+/// Checks if the scheduler should apply a new description now.
+///
+/// Besides the regular deadline, an update is due as soon as a schedule
+/// window opens for another description, the current description's window
+/// closes, its weekday ends or it lacks the tag chosen with `only`, or a
+/// description becomes eligible while the fallback is shown.
+#[must_use]
+pub fn is_update_due(
+    state: &SchedulerState,
+    config: &DescriptionConfig,
+    now: NaiveDateTime,
+) -> bool {
+    if holds_sticky(state, config) {
+        return false;
+    }
+
+    if state.is_expired() {
+        return true;
+    }
+
+    if let Some(index) = pinned_index(state, config, now) {
+        return index != state.current_index;
+    }
+
+    let fallback = config.fallback_index();
+    let current = state.current_index;
+    let in_rotation = config
+        .active_descriptions()
+        .iter()
+        .enumerate()
+        .any(|(i, d)| {
+            Some(i) != fallback
+                && d.schedule.is_none()
+                && d.active_on(now.weekday())
+                && state.in_tag(d)
+                && !state.is_rejected(&d.id)
+        });
+    let to_fallback = fallback
+        .is_some_and(|i| i != current && config.get(i).is_some_and(|d| !state.is_rejected(&d.id)));
+
+    config.get(current).is_some_and(|d| {
+        Some(current) == fallback
+            || !d.active_on(now.weekday())
+            || !state.in_tag(d)
+            || d.schedule.is_some_and(|w| !w.contains(now.time()))
+    }) && (in_rotation || to_fallback)
+}
+
+/// Checks if a sticky description is on display. It stays past its
+/// deadline, schedule windows and weekdays until `skip` or `goto` clears
+/// the deadline (a `set` text does too).
+fn holds_sticky(state: &SchedulerState, config: &DescriptionConfig) -> bool {
+    state.has_deadline()
+        && state.custom_description.is_none()
+        && config
+            .get(state.current_index)
+            .is_some_and(|d| d.sticky && state.stats.current_id() == Some(d.id.as_str()))
+}
+
+/// Checks if the state file needs heartbeat saves to look fresh: the
+/// scheduler is paused, or a tick left an expired deadline in place (a
+/// sticky description, quiet hours, an update that keeps failing).
+fn needs_heartbeat(state: &SchedulerState) -> bool {
+    state.is_paused || state.is_expired()
+}
+
+/// Checks if a tick should change the bio now: an update is due and no
+/// pause holds it (a `pause auto` still lets a pending `set` text through).
+fn wants_update(state: &SchedulerState, config: &DescriptionConfig, now: NaiveDateTime) -> bool {
+    !state.holds_updates() && is_update_due(state, config, now)
+}
+
+/// Returns the message `last-error` keeps for a failed update, `None` for
+/// a success or the bot's own rate limit (an update that merely waits).
+fn last_error_message(result: &Result<Option<String>, TelegramError>) -> Option<String> {
+    match result {
+        Ok(_) | Err(TelegramError::RateLimited(_)) => None,
+        Err(e) => Some(e.to_string()),
+    }
+}
+
+/// Returns the event reporting that the bio read back after applying `id`
+/// differs from the text sent, compared as Telegram stores them.
+fn bio_mismatch(id: &str, sent: &str, actual: &str) -> Option<SchedulerEvent> {
+    (normalize_bio(sent) != normalize_bio(actual)).then(|| SchedulerEvent::NotApplied {
+        id: id.to_owned(),
+        sent: sent.to_owned(),
+        actual: actual.to_owned(),
+    })
+}
+
+/// Logs the fallbacks taken when deciding on `next`.
+fn log_decision(config: &DescriptionConfig, next: &NextUpdate, now: NaiveDateTime) {
+    let Some(desc) = config.get(next.index).filter(|_| !next.is_custom) else {
+        return;
+    };
+
+    if config.fallback_index() == Some(next.index) {
+        debug!(
+            "No description is eligible, showing the fallback [{}]",
+            next.description_id
+        );
+    } else if !desc.active_on(now.weekday()) {
+        warn!(
+            "No description is active on {}, falling back to [{}]",
+            now.weekday(),
+            next.description_id
+        );
+    }
+
+    if next.emoji_status_id.is_none() && desc.emoji_status_id.is_some() {
+        debug!(
+            "Skipping emoji status for [{}]: account is not Premium",
+            next.description_id
+        );
+    }
+}
+
+/// Turns the chosen text into the bio that is sent (see [`render_text`]),
+/// with a fresh random emoji from the description's pool each time.
+fn prepare_text(
+    next: &mut NextUpdate,
+    config: &DescriptionConfig,
+    on_overlong: OverlongPolicy,
+    now_unix: u64,
+) {
+    let desc = config.get(next.index).filter(|_| !next.is_custom);
+    let seed = RandomState::new().hash_one(now_unix);
+    let rendered = render_text(
+        &next.description_id,
+        &next.text,
+        desc,
+        config.max_bio_length(),
+        on_overlong,
+        seed,
+    );
+
+    if let Some(len) = rendered.truncated_from {
+        info!(
+            "Truncating [{}] from {} to {} UTF-16 units",
+            next.description_id, len, rendered.max_len
+        );
+    }
+    next.text = rendered.text;
+}
+
+/// Returns the index pinned by an active schedule window, unless Telegram
+/// rejected that description.
+fn pinned_index(
+    state: &SchedulerState,
+    config: &DescriptionConfig,
+    now: NaiveDateTime,
+) -> Option<usize> {
+    config
+        .scheduled_index_at(now)
+        .filter(|&i| config.get(i).is_some_and(|d| !state.is_rejected(&d.id)))
+}
+
+/// Returns true only on the first call after the configuration became
+/// empty, so the scheduler reports it once instead of every tick.
+fn first_empty_report(warned: &AtomicBool, is_empty: bool) -> bool {
+    if is_empty {
+        !warned.swap(true, Ordering::Relaxed)
     } else {
-        format!("{}...", s.chars().take(max_len).collect::<String>())
+        warned.store(false, Ordering::Relaxed);
+        false
+    }
+}
+
+/// Describes why the username of a description was not applied; the bio
+/// update goes ahead either way.
+fn username_failure_message(id: &str, username: &str, err: &TelegramError) -> String {
+    match err {
+        TelegramError::UsernameOccupied => {
+            format!("Skipping username @{username} for [{id}]: it is taken by someone else")
+        }
+        TelegramError::UsernameInvalid => {
+            format!("Skipping username @{username} for [{id}]: Telegram rejected it as invalid")
+        }
+        e => format!("Failed to set username @{username} for [{id}]: {e}"),
     }
 }
 
@@ -234,3 +914,665 @@ impl std::fmt::Debug for DescriptionScheduler {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TimeWindow;
+    use crate::scheduler::{HEALTH_MARGIN_SECS, MockClock, PauseMode};
+    use chrono::Weekday;
+
+    /// Local time on Wednesday, 2024-05-01.
+    fn at(h: u32, m: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decide_next_without_deadline_uses_current() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.current_index = 1;
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "working");
+        assert_eq!(next.duration_secs, 7200);
+        assert!(!next.should_advance);
+        assert!(!next.is_custom);
+    }
+
+    #[test]
+    fn test_startup_delay_within_jitter() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new().with_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        state.set_deadline(3600);
+
+        for seed in [0, 1, 59, 60, 61, 12_345, u64::MAX] {
+            let delay = startup_delay(60, seed);
+            assert!(delay.as_secs() <= 60, "{delay:?}");
+        }
+        assert_eq!(startup_delay(60, 61), Duration::ZERO);
+        assert_eq!(startup_delay(0, 12_345), Duration::ZERO);
+
+        // The pending deadline still holds once the delay has passed
+        clock.advance(startup_delay(60, 45).as_secs());
+        assert!(!is_update_due(&state, &config, at(12, 0)));
+        clock.advance(3600);
+        assert!(is_update_due(&state, &config, at(12, 0)));
+    }
+
+    #[test]
+    fn test_initial_state_random_start() {
+        let config = DescriptionConfig::example();
+
+        // Fresh start without random_start begins at the first description
+        assert_eq!(
+            initial_state(None, None, &config, ResumePolicy::KeepDeadline, false, 2).current_index,
+            0
+        );
+
+        // The seed picks the starting description
+        assert_eq!(
+            initial_state(None, None, &config, ResumePolicy::KeepDeadline, true, 2).current_index,
+            2
+        );
+        assert_eq!(
+            initial_state(None, None, &config, ResumePolicy::KeepDeadline, true, 4).current_index,
+            1
+        );
+
+        // Persisted state wins over random_start
+        let persistent = PersistentState {
+            current_index: 1,
+            expires_at_unix: Some(123),
+            ..Default::default()
+        };
+        let state = initial_state(
+            Some(&persistent),
+            None,
+            &config,
+            ResumePolicy::KeepDeadline,
+            true,
+            2,
+        );
+        assert_eq!(state.current_index, 1);
+        assert!(state.has_deadline());
+    }
+
+    #[test]
+    fn test_initial_state_resets_for_other_account() {
+        let config = DescriptionConfig::example();
+        let persistent = PersistentState {
+            current_index: 2,
+            expires_at_unix: Some(123),
+            custom_description: Some("Old account's bio".to_owned()),
+            account_id: Some(111),
+            ..Default::default()
+        };
+
+        // Same account: resumed
+        let state = initial_state(
+            Some(&persistent),
+            Some(111),
+            &config,
+            ResumePolicy::KeepDeadline,
+            false,
+            0,
+        );
+        assert_eq!(state.current_index, 2);
+
+        // Another account: fresh state, recorded for the new account
+        let state = initial_state(
+            Some(&persistent),
+            Some(222),
+            &config,
+            ResumePolicy::KeepDeadline,
+            false,
+            0,
+        );
+        assert_eq!(state.current_index, 0);
+        assert!(state.custom_description.is_none());
+        assert!(!state.has_deadline());
+        assert_eq!(state.to_persistent().account_id, Some(222));
+
+        // Older state without an account is adopted
+        let legacy = PersistentState {
+            account_id: None,
+            ..persistent
+        };
+        let state = initial_state(
+            Some(&legacy),
+            Some(222),
+            &config,
+            ResumePolicy::KeepDeadline,
+            false,
+            0,
+        );
+        assert_eq!(state.current_index, 2);
+        assert_eq!(state.account_id, Some(222));
+    }
+
+    #[test]
+    fn test_initial_state_skips_scheduled() {
+        let mut config = DescriptionConfig::example();
+        for desc in &mut config.descriptions[..2] {
+            desc.schedule = Some(TimeWindow::new(at(9, 0).time(), at(10, 0).time()));
+        }
+
+        for seed in 0..5 {
+            assert_eq!(
+                initial_state(None, None, &config, ResumePolicy::KeepDeadline, true, seed)
+                    .current_index,
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test_decide_next_with_deadline_advances() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.current_index = 2;
+        state.set_deadline(0);
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "morning"); // Wraps around
+        assert!(next.should_advance);
+    }
+
+    #[test]
+    fn test_sticky_description_stays_until_skipped() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[1].sticky = true;
+        let mut state = SchedulerState::new();
+        let apply = |state: &mut SchedulerState, next: &NextUpdate| {
+            if next.is_custom {
+                state.clear_custom();
+            } else {
+                state.current_index = next.index;
+            }
+            state.set_deadline(0);
+            state.record_shown((!next.is_custom).then_some(next.description_id.as_str()));
+        };
+
+        // "morning" expires, the rotation reaches the sticky description
+        state.set_deadline(0);
+        state.record_shown(Some("morning"));
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "working");
+        apply(&mut state, &next);
+
+        // The deadline passed, but the sticky description is kept
+        assert!(state.is_expired());
+        assert!(!is_update_due(&state, &config, at(12, 0)));
+        assert!(!is_update_due(&state, &config, at(23, 0)));
+
+        // A set text goes through, then the sticky description comes back
+        state.set_custom("Lunch".to_owned(), 600);
+        state.clear_deadline();
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        apply(&mut state, &next);
+        assert!(is_update_due(&state, &config, at(12, 0)));
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "working");
+        assert!(!next.should_advance);
+        apply(&mut state, &next);
+        assert!(!is_update_due(&state, &config, at(12, 0)));
+
+        // Past its deadline, the heartbeat keeps `--health` passing
+        assert!(needs_heartbeat(&state));
+        let later = state.now_unix() + 86_400;
+        assert!(
+            state
+                .to_persistent()
+                .is_fresh(later, later - HEARTBEAT_SECS, 60)
+        );
+
+        // skip moves on
+        state.advance(config.len());
+        state.clear_deadline();
+        assert!(is_update_due(&state, &config, at(12, 0)));
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+    }
+
+    #[test]
+    fn test_queue_consumed_in_order_then_rotation_resumes() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+        state.enqueue("First".to_owned(), 600);
+        state.enqueue("Second".to_owned(), 900);
+
+        let mut shown = Vec::new();
+        for _ in 0..4 {
+            let next = decide_next(&state, &config, at(12, 0)).unwrap();
+            // Applied the way run_once does
+            if next.is_queued {
+                state.queue.pop_front();
+            } else {
+                state.current_index = next.index;
+            }
+            state.set_deadline(0);
+            shown.push((next.description_id, next.text, next.duration_secs));
+        }
+
+        let ids: Vec<&str> = shown.iter().map(|(id, ..)| id.as_str()).collect();
+        assert_eq!(ids, vec!["queued", "queued", "working", "evening"]);
+        assert_eq!(shown[0].1, "First");
+        assert_eq!(shown[0].2, 600);
+        assert_eq!(shown[1].1, "Second");
+        assert!(state.queue.is_empty());
+    }
+
+    #[test]
+    fn test_queue_waits_for_current_description() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.enqueue("Later".to_owned(), 600);
+
+        // Without a deadline (first run, goto) the chosen description goes first
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "morning");
+
+        // A pending set text still wins
+        state.set_deadline(0);
+        state.set_custom("Now".to_owned(), 600);
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert!(!next.is_queued);
+        assert_eq!(next.text, "Now");
+    }
+
+    #[tokio::test]
+    async fn test_bio_mismatch_warns_but_state_advances() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+
+        // Applied the way run_once does, before the bio is read back
+        state.current_index = next.index;
+        state.set_deadline(next.duration_secs);
+
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let fetched = "Old bio";
+        if let Some(event) = bio_mismatch(&next.description_id, &next.text, fetched) {
+            events.emit(event);
+        }
+
+        assert_eq!(
+            rx.recv().await,
+            Some(SchedulerEvent::NotApplied {
+                id: "working".to_owned(),
+                sent: next.text.clone(),
+                actual: "Old bio".to_owned(),
+            })
+        );
+        assert_eq!(state.current_index, 1);
+        assert!(!state.is_expired());
+
+        // Telegram trims trailing whitespace, which is not a mismatch
+        assert_eq!(bio_mismatch("working", "Busy  ", "Busy"), None);
+    }
+
+    #[test]
+    fn test_failed_update_recorded_until_success() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock);
+
+        // Recorded the way update_outcome does
+        let record = |state: &mut SchedulerState, result| {
+            if let Some(message) = last_error_message(&result) {
+                state.record_error(message);
+            }
+            if matches!(result, Ok(Some(_))) {
+                state.record_success();
+            }
+        };
+
+        record(&mut state, Err(TelegramError::RateLimited(3)));
+        assert_eq!(state.last_error(), None);
+
+        record(&mut state, Err(TelegramError::FloodWait(420)));
+        let error = state.last_error().unwrap();
+        assert_eq!(error.message, "Flood wait required: 420 seconds");
+        assert_eq!(error.at_unix, 1_000_000);
+
+        // Nothing applied (e.g. deferred): the error stays
+        record(&mut state, Ok(None));
+        assert!(state.last_error().is_some());
+
+        record(&mut state, Ok(Some("morning".to_owned())));
+        assert_eq!(state.last_error(), None);
+    }
+
+    #[test]
+    fn test_decide_next_prefers_custom() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.custom_description = Some("Custom bio".to_owned());
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.text, "Custom bio");
+        assert_eq!(next.duration_secs, DEFAULT_CUSTOM_DURATION_SECS);
+        assert!(next.is_custom);
+        assert!(!next.should_advance);
+
+        state.set_custom("Back at 5".to_owned(), 900);
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.duration_secs, 900);
+    }
+
+    #[test]
+    fn test_empty_config_is_reported_once() {
+        let warned = AtomicBool::new(false);
+        assert!(first_empty_report(&warned, true));
+        for _ in 0..5 {
+            assert!(!first_empty_report(&warned, true));
+        }
+
+        // Reported again after descriptions were added and removed
+        assert!(!first_empty_report(&warned, false));
+        assert!(first_empty_report(&warned, true));
+    }
+
+    #[test]
+    fn test_decide_next_empty_config() {
+        let config = DescriptionConfig::default();
+        let state = SchedulerState::new();
+        assert!(decide_next(&state, &config, at(12, 0)).is_none());
+    }
+
+    fn scheduled_config() -> DescriptionConfig {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[2].schedule = Some(TimeWindow::new(at(19, 0).time(), at(23, 0).time()));
+        config
+    }
+
+    #[test]
+    fn test_decide_next_pins_scheduled_description() {
+        let config = scheduled_config();
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+
+        let next = decide_next(&state, &config, at(20, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+        assert_eq!(next.index, 2);
+    }
+
+    #[test]
+    fn test_decide_next_rotation_skips_scheduled() {
+        let config = scheduled_config();
+        let mut state = SchedulerState::new();
+        state.current_index = 1;
+        state.set_deadline(0);
+
+        // "evening" is outside its window, so rotation wraps to "morning"
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "morning");
+        assert_eq!(next.index, 0);
+    }
+
+    #[test]
+    fn test_update_due_on_window_change() {
+        let config = scheduled_config();
+        let mut state = SchedulerState::new();
+        state.set_deadline(3600);
+        assert!(!is_update_due(&state, &config, at(12, 0)));
+
+        // Window opens
+        assert!(is_update_due(&state, &config, at(19, 0)));
+
+        // Window closes while its description is shown
+        state.current_index = 2;
+        state.set_deadline(3600);
+        assert!(!is_update_due(&state, &config, at(22, 0)));
+        assert!(is_update_due(&state, &config, at(23, 0)));
+    }
+
+    #[test]
+    fn test_decide_next_skips_rejected() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+        state.mark_rejected("working");
+
+        // Index 0 expired, "working" (1) is skipped
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+        assert_eq!(next.index, 2);
+
+        state.mark_rejected("morning");
+        state.mark_rejected("evening");
+        assert!(decide_next(&state, &config, at(12, 0)).is_none());
+    }
+
+    #[test]
+    fn test_decide_next_carries_username() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].username = Some("alex_at_work".to_owned());
+        let state = SchedulerState::new();
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.username.as_deref(), Some("alex_at_work"));
+    }
+
+    #[test]
+    fn test_username_rejection_is_skipped() {
+        let occupied = username_failure_message("work", "alex", &TelegramError::UsernameOccupied);
+        assert!(occupied.starts_with("Skipping username @alex for [work]"));
+        assert!(occupied.contains("taken"));
+
+        let invalid = username_failure_message("work", "alex", &TelegramError::UsernameInvalid);
+        assert!(invalid.contains("invalid"));
+
+        let other = username_failure_message(
+            "work",
+            "alex",
+            &TelegramError::Connection("timed out".to_owned()),
+        );
+        assert!(other.starts_with("Failed to set username"));
+    }
+
+    #[test]
+    fn test_decide_next_emoji_status_requires_premium() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].emoji_status_id = Some(42);
+        let state = SchedulerState::new();
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "morning");
+        assert!(next.emoji_status_id.is_none());
+
+        config.is_premium = true;
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.emoji_status_id, Some(42));
+    }
+
+    #[test]
+    fn test_decide_next_skips_other_weekdays() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[1].weekdays = vec![Weekday::Sat, Weekday::Sun];
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+
+        // Wednesday: "working" is weekend-only
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+
+        // Saturday: it is in rotation again
+        let saturday = at(12, 0) + chrono::Duration::days(3);
+        let next = decide_next(&state, &config, saturday).unwrap();
+        assert_eq!(next.description_id, "working");
+    }
+
+    #[test]
+    fn test_decide_next_falls_back_when_nothing_active_today() {
+        let mut config = DescriptionConfig::example();
+        for desc in &mut config.descriptions {
+            desc.weekdays = vec![Weekday::Sun];
+        }
+        let mut state = SchedulerState::new();
+        state.current_index = 1;
+        state.set_deadline(0);
+
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "morning");
+    }
+
+    /// The example config with "evening" as the weekend-only fallback.
+    fn fallback_config() -> DescriptionConfig {
+        let mut config = DescriptionConfig::example();
+        config.fallback_id = Some("evening".to_owned());
+        config.descriptions[2].weekdays = vec![Weekday::Sat];
+        config
+    }
+
+    #[test]
+    fn test_decide_next_uses_fallback_when_nothing_eligible() {
+        let mut config = fallback_config();
+        config.descriptions[0].weekdays = vec![Weekday::Sun];
+        config.descriptions[1].schedule = Some(TimeWindow::new(at(6, 0).time(), at(7, 0).time()));
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+
+        // Wednesday noon: "morning" is Sunday-only, "working" is outside its window
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+        assert_eq!(next.index, 2);
+
+        // The window pins "working" over the fallback
+        let next = decide_next(&state, &config, at(6, 30)).unwrap();
+        assert_eq!(next.description_id, "working");
+    }
+
+    #[test]
+    fn test_fallback_only_when_nothing_else_eligible() {
+        let config = fallback_config();
+        let mut state = SchedulerState::new();
+        state.set_deadline(0);
+
+        // The fallback is never part of the rotation
+        for current in 0..config.len() {
+            state.current_index = current;
+            let next = decide_next(&state, &config, at(12, 0)).unwrap();
+            assert_ne!(next.description_id, "evening");
+        }
+
+        // Once the rotation is rejected, it is all that is left
+        state.mark_rejected("morning");
+        state.mark_rejected("working");
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert_eq!(next.description_id, "evening");
+    }
+
+    #[test]
+    fn test_update_due_switches_to_and_from_fallback() {
+        let thursday = at(0, 30) + chrono::Duration::days(1);
+        let mut state = SchedulerState::new();
+        state.set_deadline(3600);
+
+        // The shown description's day ends and only the fallback is left
+        let mut config = fallback_config();
+        config.descriptions[0].weekdays = vec![Weekday::Wed];
+        config.descriptions[1].weekdays = vec![Weekday::Wed];
+        assert!(!is_update_due(&state, &config, at(23, 0)));
+        assert!(is_update_due(&state, &config, thursday));
+
+        // While the fallback is up, waiting for a description's day
+        config.descriptions[0].weekdays = vec![Weekday::Thu];
+        config.descriptions[1].weekdays = vec![Weekday::Thu];
+        state.current_index = 2;
+        assert!(!is_update_due(&state, &config, at(23, 0)));
+        assert!(is_update_due(&state, &config, thursday));
+    }
+
+    #[test]
+    fn test_pause_all_holds_custom_text() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.pause(PauseMode::All);
+        assert!(!wants_update(&state, &config, at(12, 0)));
+
+        state.set_custom("Lunch".to_owned(), 900);
+        state.clear_deadline();
+        assert!(!wants_update(&state, &config, at(12, 0)));
+
+        state.resume();
+        assert!(wants_update(&state, &config, at(12, 0)));
+    }
+
+    #[test]
+    fn test_pause_auto_applies_only_custom_text() {
+        let config = DescriptionConfig::example();
+        let mut state = SchedulerState::new();
+        state.pause(PauseMode::Auto);
+
+        // The rotation stays put, even past the deadline
+        assert!(!wants_update(&state, &config, at(12, 0)));
+
+        state.set_custom("Lunch".to_owned(), 900);
+        state.clear_deadline();
+        assert!(wants_update(&state, &config, at(12, 0)));
+        let next = decide_next(&state, &config, at(12, 0)).unwrap();
+        assert!(next.is_custom);
+
+        // Once applied, the expired custom text is not followed by rotation
+        state.clear_custom();
+        state.set_deadline(0);
+        assert!(!wants_update(&state, &config, at(12, 0)));
+    }
+
+    #[test]
+    fn test_update_due_follows_mock_clock() {
+        let config = DescriptionConfig::example();
+        let clock = Arc::new(crate::scheduler::MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        state.set_deadline(3600);
+
+        assert!(!is_update_due(&state, &config, at(12, 0)));
+        clock.advance(3600);
+        assert!(is_update_due(&state, &config, at(12, 0)));
+    }
+
+    #[test]
+    fn test_state_stays_fresh_through_quiet_hours() {
+        let settings = BotSettings {
+            quiet_hours: Some(TimeWindow::new(at(1, 0).time(), at(7, 0).time())),
+            ..Default::default()
+        };
+        let night = 20 * 86_400 + 2 * 3600; // 02:00 UTC
+        let clock = Arc::new(MockClock::new(night));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        state.set_deadline(600);
+
+        // The deadline passes at 02:10, nothing is applied until 07:00
+        clock.advance(3 * 3600);
+        assert!(settings.is_quiet_at(clock.now_utc()));
+        assert!(needs_heartbeat(&state));
+
+        let now = clock.now_unix();
+        let persistent = state.to_persistent();
+        assert!(persistent.is_fresh(now, now - HEARTBEAT_SECS, HEALTH_MARGIN_SECS));
+        assert!(!persistent.is_fresh(now, night, HEALTH_MARGIN_SECS)); // No heartbeat
+    }
+
+    #[test]
+    fn test_update_due_when_weekday_ends() {
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].weekdays = vec![Weekday::Wed];
+        let mut state = SchedulerState::new();
+        state.set_deadline(3600);
+
+        assert!(!is_update_due(&state, &config, at(23, 0)));
+        assert!(is_update_due(
+            &state,
+            &config,
+            at(0, 30) + chrono::Duration::days(1)
+        ));
+    }
+}
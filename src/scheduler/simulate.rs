@@ -0,0 +1,143 @@
+//! Offline replay of the rotation.
+//!
+//! Runs the scheduler's decision logic against a [`MockClock`], without
+//! connecting to Telegram, to show which descriptions a configuration
+//! would put up and when.
+
+use std::sync::Arc;
+
+use super::{Clock, MockClock, SchedulerState, decide_next, is_update_due};
+use crate::config::{BotSettings, DescriptionConfig};
+
+/// Resolution of the simulated clock between deadlines.
+///
+/// Schedule windows and quiet hours are set in whole minutes, so checking
+/// every minute catches each of their boundaries.
+pub const SIMULATION_STEP_SECS: u64 = 60;
+
+/// One bio update of a simulated rotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedUpdate {
+    /// Unix timestamp the update would be applied at.
+    pub at_unix: u64,
+    /// ID of the applied description.
+    pub description_id: String,
+    /// How long the description would stay.
+    pub duration_secs: u64,
+}
+
+/// Simulates `duration_secs` of rotation starting at `start_unix`, from a
+/// fresh state.
+///
+/// Each update is assumed to succeed. Quiet hours, weekdays and schedule
+/// windows are honored; the rate limit and presence checks are not.
+#[must_use]
+pub fn simulate(
+    config: &DescriptionConfig,
+    settings: &BotSettings,
+    start_unix: u64,
+    duration_secs: u64,
+) -> Vec<SimulatedUpdate> {
+    let clock = Arc::new(MockClock::new(start_unix));
+    let mut state = SchedulerState::new().with_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+    let end_unix = start_unix.saturating_add(duration_secs);
+    let mut updates = Vec::new();
+
+    while clock.now_unix() < end_unix {
+        let now_unix = clock.now_unix();
+        let now_utc = clock.now_utc();
+        let now = now_utc.with_timezone(&settings.timezone).naive_local();
+
+        if !settings.is_quiet_at(now_utc)
+            && is_update_due(&state, config, now)
+            && let Some(next) = decide_next(&state, config, now)
+        {
+            state.current_index = next.index;
+            state.set_deadline(next.duration_secs);
+            state.record_shown(Some(next.description_id.as_str()));
+            updates.push(SimulatedUpdate {
+                at_unix: now_unix,
+                description_id: next.description_id,
+                duration_secs: next.duration_secs,
+            });
+        }
+
+        // Jump to the deadline or the next minute, whichever comes first
+        let to_minute = SIMULATION_STEP_SECS - now_unix % SIMULATION_STEP_SECS;
+        let step = state
+            .time_remaining_at(now_unix)
+            .map_or(to_minute, |left| left.as_secs().clamp(1, to_minute));
+        clock.advance(step);
+    }
+
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Description, TimeWindow};
+
+    fn desc(id: &str, duration_secs: u64) -> Description {
+        Description::builder()
+            .id(id)
+            .text(id)
+            .duration_secs(duration_secs)
+            .build()
+            .unwrap()
+    }
+
+    fn ids(updates: &[SimulatedUpdate]) -> Vec<(u64, &str)> {
+        updates
+            .iter()
+            .map(|u| (u.at_unix, u.description_id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_simulate_known_sequence() {
+        let mut lunch = desc("lunch", 600);
+        lunch.schedule = Some("02:00-02:30".parse::<TimeWindow>().unwrap());
+        let config = DescriptionConfig {
+            descriptions: vec![desc("a", 3600), desc("b", 1800), lunch],
+            ..DescriptionConfig::default()
+        };
+
+        // 1970-01-01 00:00 UTC
+        let updates = simulate(&config, &BotSettings::default(), 0, 4 * 3600);
+
+        assert_eq!(
+            ids(&updates),
+            vec![
+                (0, "a"),
+                (3600, "b"),
+                (5400, "a"),
+                // Pinned for the whole window, re-applied as it expires
+                (7200, "lunch"),
+                (7800, "lunch"),
+                (8400, "lunch"),
+                (9000, "a"),
+                (12600, "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simulate_waits_out_quiet_hours() {
+        let config = DescriptionConfig {
+            descriptions: vec![desc("a", 600), desc("b", 600)],
+            ..DescriptionConfig::default()
+        };
+        let settings = BotSettings {
+            quiet_hours: Some("00:05-00:30".parse().unwrap()),
+            ..BotSettings::default()
+        };
+
+        let updates = simulate(&config, &settings, 0, 3600);
+
+        assert_eq!(
+            ids(&updates),
+            vec![(0, "a"), (1800, "b"), (2400, "a"), (3000, "b")]
+        );
+    }
+}
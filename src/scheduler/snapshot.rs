@@ -0,0 +1,158 @@
+//! Point-in-time view of the rotation for status output.
+
+use serde::Serialize;
+
+use super::{PauseMode, SchedulerState};
+use crate::config::{BotSettings, DescriptionConfig};
+
+/// What the rotation is doing right now.
+///
+/// The `status` command formats it for chat and the control interface
+/// returns it as JSON, so the field names are part of that API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent status flags
+pub struct StatusSnapshot {
+    /// Index of the current description (0-based).
+    pub index: usize,
+
+    /// ID of the current description.
+    pub id: Option<String>,
+
+    /// Number of descriptions in the active profile.
+    pub count: usize,
+
+    /// Whether rotation is paused.
+    pub paused: bool,
+
+    /// What the pause stops.
+    pub pause_mode: PauseMode,
+
+    /// Bio updates that failed in a row.
+    pub failures: u32,
+
+    /// Whether the pause came from repeated failures.
+    pub paused_by_failures: bool,
+
+    /// Whether quiet hours are in effect.
+    pub quiet_hours: bool,
+
+    /// Seconds until the next rotation, if a deadline is set.
+    pub remaining_secs: Option<u64>,
+
+    /// Duration the current description was applied with.
+    pub duration_secs: Option<u64>,
+
+    /// Configured duration of the current description after the minimum
+    /// update interval is applied.
+    pub effective_duration_secs: Option<u64>,
+
+    /// Whether a `set` text waits to be applied or is shown.
+    pub custom_pending: bool,
+
+    /// Whether the account is treated as Premium.
+    pub premium: bool,
+
+    /// Name of the active profile.
+    pub profile: String,
+
+    /// IDs Telegram rejected as too long.
+    pub rejected: Vec<String>,
+}
+
+impl StatusSnapshot {
+    /// Captures the status at the time of `state`'s clock.
+    #[must_use]
+    pub fn capture(
+        state: &SchedulerState,
+        config: &DescriptionConfig,
+        settings: &BotSettings,
+    ) -> Self {
+        let current = config.get(state.current_index);
+
+        Self {
+            index: state.current_index,
+            id: current.map(|d| d.id.clone()),
+            count: config.len(),
+            paused: state.is_paused,
+            pause_mode: state.pause_mode,
+            failures: state.consecutive_failures(),
+            paused_by_failures: state.is_paused_by_failures(),
+            quiet_hours: settings.is_quiet_at(state.clock().now_utc()),
+            remaining_secs: state
+                .time_remaining_at(state.now_unix())
+                .map(|r| r.as_secs()),
+            duration_secs: state.current_duration().map(|d| d.as_secs()),
+            effective_duration_secs: current
+                .map(|d| settings.effective_duration_secs(d.duration_secs)),
+            custom_pending: state.custom_description.is_some(),
+            premium: config.is_premium,
+            profile: config.active_profile_name().to_owned(),
+            rejected: state.rejected_ids().map(str::to_owned).collect(),
+        }
+    }
+
+    /// Checks if the minimum update interval overrides the configured
+    /// duration of the current description.
+    #[must_use]
+    pub fn duration_floored(&self, config: &DescriptionConfig) -> bool {
+        let configured = config.get(self.index).map(|d| d.duration_secs);
+        configured != self.effective_duration_secs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::scheduler::MockClock;
+
+    #[test]
+    fn test_snapshot_matches_state_and_config() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        state.current_index = 1;
+        state.set_deadline(7200);
+        state.mark_rejected("evening");
+        clock.advance(600);
+
+        let mut config = DescriptionConfig::example();
+        config.is_premium = true;
+        let settings = BotSettings::default();
+
+        let snapshot = StatusSnapshot::capture(&state, &config, &settings);
+        assert_eq!(snapshot.index, 1);
+        assert_eq!(snapshot.id.as_deref(), Some("working"));
+        assert_eq!(snapshot.count, 3);
+        assert!(!snapshot.paused);
+        assert_eq!(snapshot.remaining_secs, Some(6600));
+        assert_eq!(snapshot.duration_secs, Some(7200));
+        assert_eq!(snapshot.effective_duration_secs, Some(7200));
+        assert!(!snapshot.duration_floored(&config));
+        assert!(snapshot.premium);
+        assert_eq!(snapshot.profile, "default");
+        assert_eq!(snapshot.rejected, ["evening"]);
+    }
+
+    #[test]
+    fn test_snapshot_pause_and_floor() {
+        let mut state = SchedulerState::new();
+        state.is_paused = true;
+        state.custom_description = Some("Back soon".to_owned());
+
+        let mut config = DescriptionConfig::example();
+        config.descriptions[0].duration_secs = 10;
+        let settings = BotSettings {
+            min_update_interval_secs: 60,
+            ..BotSettings::default()
+        };
+
+        let snapshot = StatusSnapshot::capture(&state, &config, &settings);
+        assert!(snapshot.paused);
+        assert!(!snapshot.paused_by_failures);
+        assert!(snapshot.custom_pending);
+        assert_eq!(snapshot.remaining_secs, None);
+        assert_eq!(snapshot.effective_duration_secs, Some(60));
+        assert!(snapshot.duration_floored(&config));
+    }
+}
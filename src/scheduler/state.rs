@@ -4,54 +4,200 @@
 //! - Store "deadline" (Unix timestamp when current description expires)
 //! - On each tick, check if current time >= deadline
 //! - No Instant gymnastics, no race conditions with timing
+//! - Time is read from an injectable [`Clock`]
 
+use std::collections::{BTreeSet, VecDeque};
+use std::io::{Read, Write};
 use std::path::Path;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-/// Gets current Unix timestamp in seconds.
-fn now_unix() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use super::{Clock, DisplayStats, StateStore, SystemClock};
+use crate::config::{BotSettings, Description, ResumePolicy};
+use crate::util::retry_io;
+
+/// How many description durations a deadline may be away from the current
+/// time before it is treated as a clock jump.
+pub const CLOCK_JUMP_FACTOR: u64 = 4;
+
+/// Extra seconds a health check allows on top of the shortest duration.
+pub const HEALTH_MARGIN_SECS: u64 = 60;
+
+/// How often a scheduler that is paused or holding an expired deadline
+/// rewrites the state file, so a health check can tell it from a dead one.
+pub const HEARTBEAT_SECS: u64 = HEALTH_MARGIN_SECS / 2;
+
+/// First bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// What a pause stops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PauseMode {
+    /// Every bio update, including texts from `set` (`pause` or `pause all`).
+    #[default]
+    All,
+    /// Only the automatic rotation; texts from `set` are still applied
+    /// (`pause auto`).
+    Auto,
+}
+
+impl PauseMode {
+    /// Parses `all` or `auto`.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "all" => Some(Self::All),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // Signature required by serde
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::fmt::Display for PauseMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// A one-shot text waiting in the queue filled by `queue`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedText {
+    /// Bio text to show.
+    pub text: String,
+    /// How long the text stays, in seconds.
+    pub duration_secs: u64,
+}
+
+/// The last failed bio update, reported by `last-error`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LastError {
+    /// Error message.
+    pub message: String,
+    /// Unix timestamp of the failure.
+    pub at_unix: u64,
 }
 
 /// Persistent state that survives restarts.
-/// This is stored as JSON in state.json.
+/// This is stored as JSON in state.json, gzipped if the file name ends in
+/// `.gz`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PersistentState {
     /// Current description index.
     pub current_index: usize,
     /// Whether rotation is paused.
     pub is_paused: bool,
+    /// What the pause stops.
+    #[serde(default, skip_serializing_if = "PauseMode::is_default")]
+    pub pause_mode: PauseMode,
     /// Unix timestamp when current description expires (deadline).
     /// None means "needs immediate update".
     pub expires_at_unix: Option<u64>,
     /// Pending custom description (survives restarts).
     pub custom_description: Option<String>,
+    /// How long the pending custom description stays, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_duration_secs: Option<u64>,
+    /// One-shot texts shown before the rotation continues, oldest first.
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    pub queue: VecDeque<QueuedText>,
+    /// Per-description display statistics.
+    #[serde(default)]
+    pub stats: DisplayStats,
+    /// Telegram user ID of the account this state belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<i64>,
+    /// Update interval chosen with `rate-limit`, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_secs: Option<u64>,
+    /// Tag the rotation is restricted to with `only`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_tag: Option<String>,
 }
 
 impl PersistentState {
     /// Loads state from a JSON file, returns default if not found.
     pub fn load(path: impl AsRef<Path>) -> Self {
-        std::fs::read_to_string(path)
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_default()
+        Self::try_load(path).unwrap_or_default()
     }
 
-    /// Saves state to a JSON file.
+    /// Loads state from a JSON file, returns `None` if missing or unreadable.
+    ///
+    /// Gzipped files are recognized by their content, whatever the name.
+    pub fn try_load(path: impl AsRef<Path>) -> Option<Self> {
+        let bytes = std::fs::read(path).ok()?;
+        if bytes.starts_with(&GZIP_MAGIC) {
+            let mut json = Vec::new();
+            GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut json)
+                .ok()?;
+            serde_json::from_slice(&json).ok()
+        } else {
+            serde_json::from_slice(&bytes).ok()
+        }
+    }
+
+    /// Saves state to a JSON file, gzipped if the path ends in `.gz`.
     pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            serde_json::to_writer(&mut encoder, self)?;
+            encoder.flush()?;
+            return std::fs::write(path, encoder.finish()?);
+        }
+
         let json = serde_json::to_string_pretty(self)?;
         std::fs::write(path, json)
     }
+
+    /// Checks if this state may be resumed by the account `account_id`.
+    ///
+    /// State without a recorded account (older files) and an unknown
+    /// current account are accepted.
+    #[must_use]
+    pub fn belongs_to(&self, account_id: Option<i64>) -> bool {
+        match (self.account_id, account_id) {
+            (Some(saved), Some(current)) => saved == current,
+            _ => true,
+        }
+    }
+
+    /// Checks if the state looks maintained by a live scheduler.
+    ///
+    /// A running scheduler replaces the deadline shortly after it passes, so
+    /// the state is stale once both the deadline and the last save are more
+    /// than `grace_secs` in the past. A deadline left in place on purpose (a
+    /// sticky description, quiet hours) is covered by the saves every
+    /// [`HEARTBEAT_SECS`]. A paused scheduler keeps no moving deadline, so
+    /// paused state is judged by the last save alone.
+    #[must_use]
+    pub const fn is_fresh(&self, now_unix: u64, saved_at_unix: u64, grace_secs: u64) -> bool {
+        let reference = match self.expires_at_unix {
+            Some(deadline) if !self.is_paused && deadline > saved_at_unix => deadline,
+            _ => saved_at_unix,
+        };
+        now_unix <= reference.saturating_add(grace_secs)
+    }
 }
 
 /// Runtime state of the description scheduler.
 /// Simple and straightforward - deadline based timing.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SchedulerState {
     /// Current description index in the list.
     pub current_index: usize,
@@ -59,16 +205,82 @@ pub struct SchedulerState {
     /// Whether rotation is paused.
     pub is_paused: bool,
 
+    /// What the pause stops (meaningful while `is_paused`).
+    pub pause_mode: PauseMode,
+
     /// Custom description to use instead of the configured one.
     /// Set by "set" command, consumed on next update.
     pub custom_description: Option<String>,
 
+    /// How long the custom description stays (`None`: the default).
+    pub custom_duration_secs: Option<u64>,
+
+    /// One-shot texts added with `queue`, oldest first.
+    /// Each is shown once when the current description expires.
+    pub queue: VecDeque<QueuedText>,
+
     /// Unix timestamp when current description expires.
     /// None = needs immediate update (first run or after goto/skip).
     expires_at_unix: Option<u64>,
 
     /// Duration of current description (for status display).
     current_duration_secs: Option<u64>,
+
+    /// IDs of descriptions Telegram rejected as too long.
+    /// Skipped by rotation until edited or reloaded.
+    rejected_ids: BTreeSet<String>,
+
+    /// Per-description display statistics.
+    pub stats: DisplayStats,
+
+    /// Telegram user ID of the account the bot runs as.
+    pub account_id: Option<i64>,
+
+    /// Update interval chosen with `rate-limit` (`None`: from settings).
+    pub rate_limit_secs: Option<u64>,
+
+    /// Tag the rotation is restricted to (`None`: every description).
+    pub active_tag: Option<String>,
+
+    /// Bio updates that failed in a row (not persisted).
+    consecutive_failures: u32,
+
+    /// Whether rotation was paused by too many failures in a row.
+    paused_by_failures: bool,
+
+    /// Last failed bio update since the last success (not persisted).
+    last_error: Option<LastError>,
+
+    /// Successful bio updates since startup (not persisted).
+    session_updates: u64,
+
+    /// Source of the current time.
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for SchedulerState {
+    fn default() -> Self {
+        Self {
+            current_index: 0,
+            is_paused: false,
+            pause_mode: PauseMode::All,
+            custom_description: None,
+            custom_duration_secs: None,
+            queue: VecDeque::new(),
+            expires_at_unix: None,
+            current_duration_secs: None,
+            rejected_ids: BTreeSet::new(),
+            stats: DisplayStats::default(),
+            account_id: None,
+            rate_limit_secs: None,
+            active_tag: None,
+            consecutive_failures: 0,
+            paused_by_failures: false,
+            last_error: None,
+            session_updates: 0,
+            clock: Arc::new(SystemClock),
+        }
+    }
 }
 
 impl SchedulerState {
@@ -78,15 +290,53 @@ impl SchedulerState {
         Self::default()
     }
 
+    /// Replaces the time source (e.g. with a `MockClock` in tests).
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the time source used by this state.
+    #[must_use]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Returns the current Unix timestamp from the clock.
+    #[must_use]
+    pub fn now_unix(&self) -> u64 {
+        self.clock.now_unix()
+    }
+
     /// Creates state from persistent state loaded from disk.
+    ///
+    /// With [`ResumePolicy::RestartDuration`] the saved deadline is dropped,
+    /// so the current description is shown for its full duration again.
     #[must_use]
-    pub fn from_persistent(persistent: &PersistentState) -> Self {
+    pub fn from_persistent(persistent: &PersistentState, policy: ResumePolicy) -> Self {
         Self {
             current_index: persistent.current_index,
             is_paused: persistent.is_paused,
+            pause_mode: persistent.pause_mode,
             custom_description: persistent.custom_description.clone(),
-            expires_at_unix: persistent.expires_at_unix,
+            custom_duration_secs: persistent.custom_duration_secs,
+            queue: persistent.queue.clone(),
+            expires_at_unix: match policy {
+                ResumePolicy::KeepDeadline => persistent.expires_at_unix,
+                ResumePolicy::RestartDuration => None,
+            },
             current_duration_secs: None, // Recalculated on first update
+            rejected_ids: BTreeSet::new(),
+            stats: persistent.stats.clone(),
+            account_id: persistent.account_id,
+            rate_limit_secs: persistent.rate_limit_secs,
+            active_tag: persistent.active_tag.clone(),
+            consecutive_failures: 0,
+            paused_by_failures: false,
+            last_error: None,
+            session_updates: 0,
+            clock: Arc::new(SystemClock),
         }
     }
 
@@ -96,16 +346,65 @@ impl SchedulerState {
         PersistentState {
             current_index: self.current_index,
             is_paused: self.is_paused,
+            pause_mode: self.pause_mode,
             expires_at_unix: self.expires_at_unix,
             custom_description: self.custom_description.clone(),
+            custom_duration_secs: self.custom_duration_secs,
+            queue: self.queue.clone(),
+            stats: self.stats.clone(),
+            account_id: self.account_id,
+            rate_limit_secs: self.rate_limit_secs,
+            active_tag: self.active_tag.clone(),
+        }
+    }
+
+    /// Saves the state to `store`, retrying transient errors up to
+    /// `SAVE_ATTEMPTS` times.
+    ///
+    /// Statistics of descriptions not shown within `STATS_RETENTION_DAYS`
+    /// are pruned first, then the least recently shown ones beyond
+    /// `MAX_STATS_ENTRIES`.
+    pub fn save(&mut self, store: &dyn StateStore, settings: &BotSettings) -> std::io::Result<()> {
+        if let Some(days) = settings.stats_retention_days {
+            self.stats.prune(self.now_unix(), days);
+        }
+        if let Some(max) = settings.max_stats_entries {
+            self.stats.cap(max);
         }
+        let persistent = self.to_persistent();
+        retry_io(settings.save_attempts, || store.save(&persistent))
+    }
+
+    /// Records that a description was applied, for the statistics.
+    ///
+    /// `None` (a custom description) only stops the previous one's clock.
+    pub fn record_shown(&mut self, id: Option<&str>) {
+        let now = self.now_unix();
+        match id {
+            Some(id) => self.stats.record_shown(id, now),
+            None => self.stats.finish_current(now),
+        }
+    }
+
+    /// Clears the display statistics.
+    pub fn reset_stats(&mut self) {
+        self.stats.reset(self.now_unix());
     }
 
     /// Checks if the current description has expired (deadline passed).
     #[must_use]
     pub fn is_expired(&self) -> bool {
+        self.is_expired_at(self.now_unix())
+    }
+
+    /// Checks if the current description has expired at `now`.
+    ///
+    /// A deadline implausibly far in the future (the clock was set back) is
+    /// treated as expired so rotation does not freeze.
+    #[must_use]
+    pub fn is_expired_at(&self, now: u64) -> bool {
         match self.expires_at_unix {
-            Some(deadline) => now_unix() >= deadline,
+            Some(deadline) => now >= deadline || self.clock_jumped_at(now, None),
             None => true, // No deadline = needs update
         }
     }
@@ -119,15 +418,60 @@ impl SchedulerState {
     /// Returns the time remaining until expiration.
     #[must_use]
     pub fn time_remaining(&self) -> Option<Duration> {
+        self.time_remaining_at(self.now_unix())
+    }
+
+    /// Returns the time remaining until expiration at `now`.
+    ///
+    /// Zero if the deadline is implausibly far in the future (clock jump).
+    #[must_use]
+    pub fn time_remaining_at(&self, now: u64) -> Option<Duration> {
         let deadline = self.expires_at_unix?;
-        let now = now_unix();
-        if now >= deadline {
+        if now >= deadline || self.clock_jumped_at(now, None) {
             Some(Duration::ZERO)
         } else {
             Some(Duration::from_secs(deadline - now))
         }
     }
 
+    /// Handles a system clock jump since the deadline was set.
+    ///
+    /// After a jump back the deadline is cleared, which makes the next tick
+    /// re-apply the current description. After a jump forward the deadline
+    /// is kept: it has passed, so the next tick advances as on expiry.
+    /// `fallback_duration_secs` bounds the check when the current duration
+    /// is unknown (after a restart). Returns `true` if the clock jumped.
+    pub fn resync_clock(&mut self, fallback_duration_secs: u64) -> bool {
+        self.resync_clock_at(self.now_unix(), fallback_duration_secs)
+    }
+
+    /// Like [`Self::resync_clock`], at an explicit time.
+    pub fn resync_clock_at(&mut self, now: u64, fallback_duration_secs: u64) -> bool {
+        if !self.clock_jumped_at(now, Some(fallback_duration_secs)) {
+            return false;
+        }
+        if self.expires_at_unix.is_some_and(|deadline| deadline > now) {
+            self.clear_deadline();
+        }
+        true
+    }
+
+    /// Checks if the deadline is more than [`CLOCK_JUMP_FACTOR`] durations
+    /// away from `now` in either direction.
+    ///
+    /// A regular deadline is at most one duration ahead and only a few
+    /// ticks behind, so a larger gap means the wall clock jumped.
+    fn clock_jumped_at(&self, now: u64, fallback_duration_secs: Option<u64>) -> bool {
+        let (Some(deadline), Some(duration)) = (
+            self.expires_at_unix,
+            self.current_duration_secs.or(fallback_duration_secs),
+        ) else {
+            return false;
+        };
+
+        deadline.abs_diff(now) > duration.max(1).saturating_mul(CLOCK_JUMP_FACTOR)
+    }
+
     /// Returns the total duration of current description.
     #[must_use]
     pub fn current_duration(&self) -> Option<Duration> {
@@ -145,11 +489,27 @@ impl SchedulerState {
     /// Sets the deadline for current description.
     /// Call this AFTER successful bio update.
     pub fn set_deadline(&mut self, duration_secs: u64) {
-        let now = now_unix();
-        self.expires_at_unix = Some(now + duration_secs);
+        let now = self.now_unix();
+        self.expires_at_unix = Some(now.saturating_add(duration_secs));
         self.current_duration_secs = Some(duration_secs);
     }
 
+    /// Pushes the deadline out by `secs`, keeping the current description.
+    ///
+    /// A deadline that already passed is extended from now. The duration
+    /// grows too, so the longer wait is not mistaken for a clock jump.
+    /// Returns `false` and changes nothing if there is no deadline.
+    pub fn extend_deadline(&mut self, secs: u64) -> bool {
+        let Some(deadline) = self.expires_at_unix else {
+            return false;
+        };
+        self.expires_at_unix = Some(deadline.max(self.now_unix()).saturating_add(secs));
+        self.current_duration_secs = self
+            .current_duration_secs
+            .map(|duration| duration.saturating_add(secs));
+        true
+    }
+
     /// Clears the deadline (triggers immediate update on next tick).
     /// Used by goto/skip commands.
     pub fn clear_deadline(&mut self) {
@@ -163,20 +523,319 @@ impl SchedulerState {
         self.clear_deadline();
     }
 
+    /// Sets a custom description to show for `duration_secs`.
+    pub fn set_custom(&mut self, text: String, duration_secs: u64) {
+        self.custom_description = Some(text);
+        self.custom_duration_secs = Some(duration_secs);
+    }
+
     /// Clears the custom description.
     pub fn clear_custom(&mut self) {
         self.custom_description = None;
+        self.custom_duration_secs = None;
+    }
+
+    /// Adds a one-shot text to the end of the queue.
+    pub fn enqueue(&mut self, text: String, duration_secs: u64) {
+        self.queue.push_back(QueuedText {
+            text,
+            duration_secs,
+        });
+    }
+
+    /// Marks a description as rejected by Telegram.
+    pub fn mark_rejected(&mut self, id: &str) {
+        self.rejected_ids.insert(id.to_owned());
+    }
+
+    /// Checks if a description was rejected by Telegram.
+    #[must_use]
+    pub fn is_rejected(&self, id: &str) -> bool {
+        self.rejected_ids.contains(id)
+    }
+
+    /// Returns the IDs of rejected descriptions in sorted order.
+    pub fn rejected_ids(&self) -> impl Iterator<Item = &str> {
+        self.rejected_ids.iter().map(String::as_str)
+    }
+
+    /// Gives a description another chance (e.g. after it was edited).
+    pub fn unmark_rejected(&mut self, id: &str) {
+        self.rejected_ids.remove(id);
+    }
+
+    /// Records a failed bio update and pauses rotation once `threshold`
+    /// updates failed in a row (`0` never pauses).
+    ///
+    /// Returns `true` if this failure paused the rotation.
+    pub fn record_failure(&mut self, threshold: u32) -> bool {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+
+        if threshold == 0 || self.consecutive_failures < threshold || self.is_paused {
+            return false;
+        }
+        self.pause(PauseMode::All);
+        self.paused_by_failures = true;
+        true
+    }
+
+    /// Records a successful bio update, resetting the failure count and
+    /// the last error.
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.last_error = None;
+        self.session_updates = self.session_updates.saturating_add(1);
+    }
+
+    /// Returns how many bio updates succeeded since startup.
+    #[must_use]
+    pub const fn session_updates(&self) -> u64 {
+        self.session_updates
+    }
+
+    /// Records why a bio update failed, for `last-error`.
+    pub fn record_error(&mut self, message: String) {
+        self.last_error = Some(LastError {
+            message,
+            at_unix: self.now_unix(),
+        });
+    }
+
+    /// Returns the last failed bio update since the last success.
+    #[must_use]
+    pub const fn last_error(&self) -> Option<&LastError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns how many bio updates failed in a row.
+    #[must_use]
+    pub const fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Checks if rotation is paused because of repeated failures.
+    #[must_use]
+    pub const fn is_paused_by_failures(&self) -> bool {
+        self.is_paused && self.paused_by_failures
+    }
+
+    /// Resumes rotation, giving a tripped failure breaker a fresh start.
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+        self.pause_mode = PauseMode::All;
+        self.paused_by_failures = false;
+        self.consecutive_failures = 0;
     }
 
-    /// Resets the scheduler state to initial values.
+    /// Pauses updates; `mode` says whether `set` texts still go through.
+    pub fn pause(&mut self, mode: PauseMode) {
+        self.is_paused = true;
+        self.pause_mode = mode;
+    }
+
+    /// Checks if a pause keeps the bio from changing now: always under
+    /// [`PauseMode::All`], and under [`PauseMode::Auto`] unless a `set`
+    /// text is waiting.
+    #[must_use]
+    pub const fn holds_updates(&self) -> bool {
+        self.is_paused
+            && !(matches!(self.pause_mode, PauseMode::Auto) && self.custom_description.is_some())
+    }
+
+    /// Checks if `desc` is in the rotation under the active tag.
+    #[must_use]
+    pub fn in_tag(&self, desc: &Description) -> bool {
+        self.active_tag
+            .as_deref()
+            .is_none_or(|tag| desc.has_tag(tag))
+    }
+
+    /// Forgets all rejected descriptions (e.g. after a reload).
+    pub fn clear_rejected(&mut self) {
+        self.rejected_ids.clear();
+    }
+
+    /// Resets the scheduler state to initial values, keeping the clock,
+    /// the account and the chosen rate limit.
     pub fn reset(&mut self) {
-        *self = Self::default();
+        let account_id = self.account_id;
+        let rate_limit_secs = self.rate_limit_secs;
+        *self = Self::default().with_clock(Arc::clone(&self.clock));
+        self.account_id = account_id;
+        self.rate_limit_secs = rate_limit_secs;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scheduler::MockClock;
+
+    #[test]
+    fn test_failure_breaker_trips_at_threshold() {
+        let mut state = SchedulerState::new();
+        assert!(!state.record_failure(3));
+        assert!(!state.record_failure(3));
+        assert!(!state.is_paused);
+
+        assert!(state.record_failure(3));
+        assert!(state.is_paused_by_failures());
+
+        // Already paused: reported only once
+        assert!(!state.record_failure(3));
+
+        state.resume();
+        assert!(!state.is_paused);
+        assert_eq!(state.consecutive_failures(), 0);
+    }
+
+    #[test]
+    fn test_failure_breaker_resets_after_success() {
+        let mut state = SchedulerState::new();
+        state.record_failure(3);
+        state.record_failure(3);
+        state.record_success();
+
+        assert!(!state.record_failure(3));
+        assert!(!state.record_failure(3));
+        assert!(!state.is_paused);
+
+        // Disabled with a zero threshold
+        let mut state = SchedulerState::new();
+        for _ in 0..100 {
+            assert!(!state.record_failure(0));
+        }
+    }
+
+    #[test]
+    fn test_expiry_at_exact_deadline() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        state.set_deadline(300);
+
+        clock.advance(299);
+        assert!(!state.is_expired());
+        assert_eq!(state.time_remaining(), Some(Duration::from_secs(1)));
+
+        clock.advance(1);
+        assert!(state.is_expired());
+        assert_eq!(state.time_remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_extend_deadline() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        assert!(!state.extend_deadline(600));
+        assert!(!state.has_deadline());
+
+        state.set_deadline(60);
+        clock.advance(20);
+        assert!(state.extend_deadline(3600));
+        assert_eq!(state.time_remaining(), Some(Duration::from_secs(40 + 3600)));
+        // Far beyond the original duration, but not a clock jump
+        assert!(!state.resync_clock(60));
+
+        clock.advance(4000);
+        assert!(state.extend_deadline(30));
+        assert_eq!(state.time_remaining(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_huge_deadline_saturates() {
+        let clock = Arc::new(MockClock::new(1_000_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+
+        state.set_deadline(u64::MAX - 10);
+        assert_eq!(state.to_persistent().expires_at_unix, Some(u64::MAX));
+        // Wrapping around would have put the deadline in the past
+        assert!(!state.is_expired());
+    }
+
+    #[test]
+    fn test_reset_keeps_clock() {
+        let clock = Arc::new(MockClock::new(5_000));
+        let mut state = SchedulerState::new().with_clock(clock.clone());
+        state.reset();
+
+        state.set_deadline(10);
+        clock.advance(10);
+        assert!(state.is_expired());
+    }
+
+    #[test]
+    fn test_backward_clock_jump() {
+        let mut state = SchedulerState::new();
+        state.expires_at_unix = Some(1_000_000 + 500);
+        state.current_duration_secs = Some(500);
+
+        // Regular countdown
+        assert!(!state.is_expired_at(1_000_000));
+        assert_eq!(
+            state.time_remaining_at(1_000_000),
+            Some(Duration::from_secs(500))
+        );
+
+        // Clock set back by a day: deadline is a day away
+        let now = 1_000_000 - 86_400;
+        assert!(state.is_expired_at(now));
+        assert_eq!(state.time_remaining_at(now), Some(Duration::ZERO));
+        assert!(state.resync_clock_at(now, 500));
+        assert!(!state.has_deadline());
+    }
+
+    #[test]
+    fn test_forward_clock_jump() {
+        let mut state = SchedulerState::new();
+        state.expires_at_unix = Some(1_000_000);
+        state.current_duration_secs = Some(500);
+
+        // Deadline just passed: regular expiry, no resync
+        assert!(!state.resync_clock_at(1_000_005, 500));
+        assert!(state.has_deadline());
+
+        // Clock jumped ahead by a day: the description expired, rotation
+        // advances instead of re-applying it
+        let now = 1_000_000 + 86_400;
+        assert!(state.resync_clock_at(now, 500));
+        assert!(state.has_deadline());
+        assert!(state.is_expired_at(now));
+    }
+
+    #[test]
+    fn test_clock_jump_after_restart_uses_fallback() {
+        let mut state = SchedulerState::from_persistent(
+            &PersistentState {
+                expires_at_unix: Some(100_000),
+                ..Default::default()
+            },
+            ResumePolicy::KeepDeadline,
+        );
+
+        // Duration unknown: no detection without a fallback
+        assert!(!state.is_expired_at(10_000));
+        assert!(!state.resync_clock_at(99_000, 600));
+        assert!(state.resync_clock_at(10_000, 600));
+    }
+
+    #[test]
+    fn test_resume_policy_with_deadline_mid_interval() {
+        // A one-hour description with 50 minutes left
+        let persistent = PersistentState {
+            current_index: 1,
+            expires_at_unix: Some(1_003_000),
+            ..Default::default()
+        };
+
+        let kept = SchedulerState::from_persistent(&persistent, ResumePolicy::KeepDeadline);
+        assert_eq!(kept.expires_at_unix, Some(1_003_000));
+        assert!(!kept.is_expired_at(1_000_000));
+
+        let restarted = SchedulerState::from_persistent(&persistent, ResumePolicy::RestartDuration);
+        assert_eq!(restarted.current_index, 1);
+        assert!(!restarted.has_deadline());
+        assert!(restarted.is_expired_at(1_000_000));
+    }
 
     #[test]
     fn test_default_state() {
@@ -235,20 +894,168 @@ mod tests {
         assert!(!state.has_deadline()); // Deadline cleared
     }
 
+    #[test]
+    fn test_is_fresh_deadline() {
+        let state = PersistentState {
+            expires_at_unix: Some(1_000),
+            ..Default::default()
+        };
+        assert!(state.is_fresh(900, 0, 120)); // Deadline not reached
+        assert!(state.is_fresh(1_120, 0, 120)); // Within grace
+        assert!(!state.is_fresh(1_121, 0, 120)); // Stale
+    }
+
+    #[test]
+    fn test_is_fresh_without_deadline_uses_save_time() {
+        let state = PersistentState::default();
+        assert!(state.is_fresh(1_050, 1_000, 120));
+        assert!(!state.is_fresh(2_000, 1_000, 120));
+    }
+
+    #[test]
+    fn test_is_fresh_paused() {
+        let state = PersistentState {
+            is_paused: true,
+            expires_at_unix: Some(1_000),
+            ..Default::default()
+        };
+        // The frozen deadline is ignored, the heartbeat save counts
+        assert!(state.is_fresh(1_000_000, 999_950, 120));
+        assert!(!state.is_fresh(1_000_000, 0, 120));
+    }
+
+    #[test]
+    fn test_is_fresh_expired_deadline_uses_save_time() {
+        // A sticky description keeps its deadline long after it passed
+        let state = PersistentState {
+            expires_at_unix: Some(1_000),
+            ..Default::default()
+        };
+        assert!(state.is_fresh(1_000_000, 999_950, 120)); // Heartbeat save
+        assert!(!state.is_fresh(1_000_000, 900, 120));
+    }
+
     #[test]
     fn test_persistent_roundtrip() {
         let mut state = SchedulerState::new();
         state.current_index = 3;
         state.is_paused = true;
         state.custom_description = Some("test".to_owned());
+        state.enqueue("queued".to_owned(), 600);
         state.set_deadline(1000);
 
         let persistent = state.to_persistent();
-        let restored = SchedulerState::from_persistent(&persistent);
+        let restored = SchedulerState::from_persistent(&persistent, ResumePolicy::KeepDeadline);
 
         assert_eq!(restored.current_index, 3);
         assert!(restored.is_paused);
         assert_eq!(restored.custom_description, Some("test".to_owned()));
+        assert_eq!(restored.queue, state.queue);
         assert!(restored.has_deadline());
     }
+
+    #[test]
+    fn test_pause_mode_persists() {
+        let mut state = SchedulerState::new();
+        state.pause(PauseMode::Auto);
+
+        let json = serde_json::to_string(&state.to_persistent()).unwrap();
+        assert!(json.contains(r#""pause_mode":"auto""#), "{json}");
+        let persistent: PersistentState = serde_json::from_str(&json).unwrap();
+        let restored = SchedulerState::from_persistent(&persistent, ResumePolicy::KeepDeadline);
+        assert_eq!(restored.pause_mode, PauseMode::Auto);
+
+        // Older files without the field pause everything
+        let old: PersistentState =
+            serde_json::from_str(r#"{"current_index":0,"is_paused":true,"expires_at_unix":null,"custom_description":null}"#)
+                .unwrap();
+        assert_eq!(old.pause_mode, PauseMode::All);
+    }
+
+    #[test]
+    fn test_save_prunes_stale_stats() {
+        let path = std::env::temp_dir().join(format!(
+            "description_bot_state_test_{}_prune.json",
+            std::process::id()
+        ));
+        let mut state = SchedulerState::new();
+        state.stats.record_shown("ancient", 0);
+        state.record_shown(Some("fresh"));
+
+        let settings = BotSettings {
+            stats_retention_days: Some(30),
+            ..BotSettings::default()
+        };
+        state
+            .save(&super::super::FileStateStore::new(&path), &settings)
+            .unwrap();
+        assert!(state.stats.get("ancient").is_none());
+
+        let saved = PersistentState::load(&path);
+        assert!(saved.stats.get("ancient").is_none());
+        assert_eq!(saved.stats.get("fresh").unwrap().shown_count, 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_save_caps_stats_entries() {
+        let path = std::env::temp_dir().join(format!(
+            "description_bot_state_test_{}_cap.json",
+            std::process::id()
+        ));
+        let mut state = SchedulerState::new();
+        for (i, id) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            state.stats.record_shown(id, 1_000 + i as u64);
+        }
+        let settings = BotSettings {
+            max_stats_entries: Some(2),
+            ..BotSettings::default()
+        };
+
+        state
+            .save(&super::super::FileStateStore::new(&path), &settings)
+            .unwrap();
+
+        // Only the most recently shown are kept
+        let saved = PersistentState::load(&path);
+        let ids: Vec<&str> = saved.stats.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, ["c", "d"]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_gzipped_state_round_trip() {
+        let dir = std::env::temp_dir();
+        let gz = dir.join(format!(
+            "description_bot_state_test_{}.json.gz",
+            std::process::id()
+        ));
+        let mut state = PersistentState {
+            current_index: 4,
+            custom_description: Some("Lunch".to_owned()),
+            ..PersistentState::default()
+        };
+        state.stats.record_shown("a", 1_000);
+
+        state.save(&gz).unwrap();
+        assert!(std::fs::read(&gz).unwrap().starts_with(&GZIP_MAGIC));
+        let loaded = PersistentState::try_load(&gz).unwrap();
+        assert_eq!(loaded.current_index, 4);
+        assert_eq!(loaded.custom_description.as_deref(), Some("Lunch"));
+        assert_eq!(loaded.stats, state.stats);
+
+        // A plain file is still read, and a gzipped one under a plain name
+        let plain = dir.join(format!(
+            "description_bot_state_test_{}_plain.json",
+            std::process::id()
+        ));
+        state.save(&plain).unwrap();
+        assert_eq!(PersistentState::try_load(&plain).unwrap().current_index, 4);
+        std::fs::rename(&gz, &plain).unwrap();
+        assert_eq!(PersistentState::try_load(&plain).unwrap().current_index, 4);
+
+        let _ = std::fs::remove_file(&plain);
+    }
 }
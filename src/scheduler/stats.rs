@@ -0,0 +1,227 @@
+//! Per-description display statistics.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Display statistics of a single description.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DescriptionStats {
+    /// How many times the description was applied.
+    pub shown_count: u64,
+
+    /// Total seconds the description was on display (finished periods only).
+    pub total_secs: u64,
+
+    /// Unix timestamp when the description was last applied.
+    pub last_shown_unix: u64,
+}
+
+/// Display statistics keyed by description ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DisplayStats {
+    /// Statistics per description ID.
+    #[serde(default)]
+    entries: BTreeMap<String, DescriptionStats>,
+
+    /// Description currently on display and the Unix time it was applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current: Option<(String, u64)>,
+}
+
+impl DisplayStats {
+    /// Records that a description was applied at `now`.
+    ///
+    /// The display time of the previous description is credited first.
+    pub fn record_shown(&mut self, id: &str, now: u64) {
+        self.finish_current(now);
+
+        let entry = self.entries.entry(id.to_owned()).or_default();
+        entry.shown_count += 1;
+        entry.last_shown_unix = now;
+        self.current = Some((id.to_owned(), now));
+    }
+
+    /// Credits the display time of the current description and stops
+    /// tracking it (e.g. when a custom description replaces it).
+    pub fn finish_current(&mut self, now: u64) {
+        if let Some((id, since)) = self.current.take()
+            && let Some(entry) = self.entries.get_mut(&id)
+        {
+            entry.total_secs = entry.total_secs.saturating_add(now.saturating_sub(since));
+        }
+    }
+
+    /// Returns the ID of the description on display (`None` while a custom
+    /// text is shown).
+    #[must_use]
+    pub fn current_id(&self) -> Option<&str> {
+        self.current.as_ref().map(|(id, _)| id.as_str())
+    }
+
+    /// Returns the statistics of a description.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&DescriptionStats> {
+        self.entries.get(id)
+    }
+
+    /// Returns total display seconds of a description, including the
+    /// running period if it is currently shown.
+    #[must_use]
+    pub fn total_secs_at(&self, id: &str, now: u64) -> u64 {
+        let finished = self.entries.get(id).map_or(0, |e| e.total_secs);
+        let running = match &self.current {
+            Some((current, since)) if current == id => now.saturating_sub(*since),
+            _ => 0,
+        };
+        finished.saturating_add(running)
+    }
+
+    /// Iterates over all statistics in ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DescriptionStats)> {
+        self.entries.iter().map(|(id, stats)| (id.as_str(), stats))
+    }
+
+    /// Returns the number of descriptions with statistics.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if no statistics were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Clears all statistics.
+    ///
+    /// The description on display keeps being tracked from `now`.
+    pub fn reset(&mut self, now: u64) {
+        self.entries.clear();
+        if let Some((id, since)) = &mut self.current {
+            *since = now;
+            self.entries.insert(id.clone(), DescriptionStats::default());
+        }
+    }
+
+    /// Removes descriptions not shown within the last `retention_days`.
+    ///
+    /// The description on display is always kept. Returns the number of
+    /// removed entries.
+    pub fn prune(&mut self, now: u64, retention_days: u64) -> usize {
+        let cutoff = now.saturating_sub(retention_days.saturating_mul(86_400));
+        let current = self.current.as_ref().map(|(id, _)| id.as_str());
+        let before = self.entries.len();
+
+        self.entries
+            .retain(|id, stats| stats.last_shown_unix >= cutoff || Some(id.as_str()) == current);
+        before - self.entries.len()
+    }
+
+    /// Keeps only the `max` most recently shown descriptions.
+    ///
+    /// The description on display is always kept. Returns the number of
+    /// removed entries.
+    pub fn cap(&mut self, max: usize) -> usize {
+        if self.entries.len() <= max {
+            return 0;
+        }
+
+        let current = self.current.as_ref().map(|(id, _)| id.as_str());
+        let mut by_age: Vec<(u64, &str)> = self
+            .entries
+            .iter()
+            .filter(|(id, _)| Some(id.as_str()) != current)
+            .map(|(id, stats)| (stats.last_shown_unix, id.as_str()))
+            .collect();
+        by_age.sort_unstable();
+        let excess = self.entries.len() - max;
+        let stale: Vec<String> = by_age
+            .into_iter()
+            .take(excess)
+            .map(|(_, id)| id.to_owned())
+            .collect();
+
+        for id in &stale {
+            self.entries.remove(id);
+        }
+        stale.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_shown_credits_previous() {
+        let mut stats = DisplayStats::default();
+        stats.record_shown("a", 1_000);
+        stats.record_shown("b", 1_300);
+        stats.record_shown("a", 1_400);
+
+        let a = stats.get("a").unwrap();
+        assert_eq!(a.shown_count, 2);
+        assert_eq!(a.total_secs, 300);
+        assert_eq!(a.last_shown_unix, 1_400);
+        assert_eq!(stats.get("b").unwrap().total_secs, 100);
+
+        // Running period is included on demand
+        assert_eq!(stats.total_secs_at("a", 1_450), 350);
+
+        // A custom description stops the clock
+        stats.finish_current(1_500);
+        assert_eq!(stats.total_secs_at("a", 2_000), 400);
+    }
+
+    #[test]
+    fn test_reset_zeroes_all_counters() {
+        let mut stats = DisplayStats::default();
+        stats.record_shown("a", 1_000);
+        stats.record_shown("b", 1_300);
+
+        stats.reset(2_000);
+        assert!(stats.get("a").is_none());
+        let b = stats.get("b").unwrap();
+        assert_eq!(*b, DescriptionStats::default());
+
+        // The current description is counted from the reset on
+        assert_eq!(stats.total_secs_at("b", 2_060), 60);
+    }
+
+    #[test]
+    fn test_prune_old_entries() {
+        let day = 86_400;
+        let mut stats = DisplayStats::default();
+        stats.record_shown("old", 0);
+        stats.record_shown("recent", 9 * day);
+        stats.record_shown("current", 9 * day);
+
+        assert_eq!(stats.prune(10 * day, 7), 1);
+        assert!(stats.get("old").is_none());
+        assert!(stats.get("recent").is_some());
+
+        // The description on display survives even if it is old
+        assert_eq!(stats.prune(100 * day, 7), 1);
+        assert!(stats.get("current").is_some());
+    }
+
+    #[test]
+    fn test_cap_keeps_most_recent_and_current() {
+        let mut stats = DisplayStats::default();
+        stats.record_shown("a", 100);
+        stats.record_shown("b", 200);
+        stats.record_shown("c", 300);
+        stats.record_shown("a", 400);
+
+        // "a" is on display, "b" is the oldest of the rest
+        assert_eq!(stats.cap(2), 1);
+        assert!(stats.get("b").is_none());
+        assert_eq!(stats.len(), 2);
+
+        assert_eq!(stats.cap(5), 0);
+        assert_eq!(stats.cap(0), 1);
+        assert!(stats.get("a").is_some());
+    }
+}
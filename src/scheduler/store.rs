@@ -0,0 +1,228 @@
+//! Where the scheduler state is persisted.
+//!
+//! The runner and the command handler save through [`StateStore`], so an
+//! ephemeral run (`PERSIST_STATE=false`) can swap the file for
+//! [`NullStateStore`] without touching any call site, and an observer run
+//! (`--read-only`) for [`ReadOnlyStateStore`].
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tracing::warn;
+
+use super::PersistentState;
+
+/// Loads and saves the persistent scheduler state.
+pub trait StateStore: Send + Sync {
+    /// Returns the saved state, or `None` if there is none (or it is
+    /// unreadable).
+    fn load(&self) -> Option<PersistentState>;
+
+    /// Saves the state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the state could not be written.
+    fn save(&self, state: &PersistentState) -> std::io::Result<()>;
+
+    /// File backing the store, if any.
+    fn path(&self) -> Option<PathBuf>;
+
+    /// Points the store at another file; later loads and saves use it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store has no file that could be moved.
+    fn relocate(&self, _path: PathBuf) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "the state is not saved to a file",
+        ))
+    }
+}
+
+/// Stores the state as JSON in a file (`state.json`).
+#[derive(Debug)]
+pub struct FileStateStore {
+    path: RwLock<PathBuf>,
+}
+
+impl FileStateStore {
+    /// Creates a store backed by the file at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: RwLock::new(path.into()),
+        }
+    }
+
+    /// Returns the current file.
+    fn file(&self) -> PathBuf {
+        self.path
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn load(&self) -> Option<PersistentState> {
+        PersistentState::try_load(self.file())
+    }
+
+    fn save(&self, state: &PersistentState) -> std::io::Result<()> {
+        state.save(self.file())
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.file())
+    }
+
+    fn relocate(&self, path: PathBuf) -> std::io::Result<()> {
+        *self.path.write().unwrap_or_else(PoisonError::into_inner) = path;
+        Ok(())
+    }
+}
+
+/// Keeps nothing: loading finds no state and saving is a no-op.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullStateStore;
+
+impl StateStore for NullStateStore {
+    fn load(&self) -> Option<PersistentState> {
+        None
+    }
+
+    fn save(&self, _state: &PersistentState) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Reads the state file but never writes it (`--read-only`).
+///
+/// Saving is a no-op; the first attempt is logged as a warning.
+#[derive(Debug)]
+pub struct ReadOnlyStateStore {
+    file: FileStateStore,
+    warned: AtomicBool,
+}
+
+impl ReadOnlyStateStore {
+    /// Creates a store that loads the file at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: FileStateStore::new(path),
+            warned: AtomicBool::new(false),
+        }
+    }
+}
+
+impl StateStore for ReadOnlyStateStore {
+    fn load(&self) -> Option<PersistentState> {
+        self.file.load()
+    }
+
+    fn save(&self, _state: &PersistentState) -> std::io::Result<()> {
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Read-only mode, state changes are not saved to {}",
+                self.file.file().display()
+            );
+        }
+        Ok(())
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.file.path()
+    }
+}
+
+/// Returns the file store for `path`, or the null store if `persist` is
+/// off.
+#[must_use]
+pub fn state_store(path: impl Into<PathBuf>, persist: bool) -> Arc<dyn StateStore> {
+    if persist {
+        Arc::new(FileStateStore::new(path))
+    } else {
+        Arc::new(NullStateStore)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("description_bot_store_{name}.json"))
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let path = temp_path("file");
+        let store = state_store(&path, true);
+        let state = PersistentState {
+            current_index: 3,
+            ..PersistentState::default()
+        };
+
+        store.save(&state).unwrap();
+        assert_eq!(store.load().unwrap().current_index, 3);
+        assert_eq!(store.path(), Some(path.clone()));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_file_store_relocate() {
+        let old = temp_path("relocate_old");
+        let new = temp_path("relocate_new");
+        let store = FileStateStore::new(&old);
+
+        store.relocate(new.clone()).unwrap();
+        store.save(&PersistentState::default()).unwrap();
+        assert!(new.exists());
+        assert!(!old.exists());
+        assert_eq!(store.path(), Some(new.clone()));
+        assert!(NullStateStore.relocate(new.clone()).is_err());
+
+        let _ = std::fs::remove_file(&new);
+    }
+
+    #[test]
+    fn test_null_store_writes_nothing() {
+        let path = temp_path("null");
+        let _ = std::fs::remove_file(&path);
+        let store = state_store(&path, false);
+
+        store.save(&PersistentState::default()).unwrap();
+        assert!(!path.exists());
+        assert!(store.load().is_none());
+        assert!(store.path().is_none());
+    }
+
+    #[test]
+    fn test_read_only_store_loads_but_never_writes() {
+        let path = temp_path("read_only");
+        let saved = PersistentState {
+            current_index: 2,
+            ..PersistentState::default()
+        };
+        FileStateStore::new(&path).save(&saved).unwrap();
+        let store = ReadOnlyStateStore::new(&path);
+
+        let changed = PersistentState {
+            current_index: 5,
+            ..PersistentState::default()
+        };
+        store.save(&changed).unwrap();
+        assert_eq!(store.load().unwrap().current_index, 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,144 @@
+//! Detection of bio changes made outside the bot.
+//!
+//! The bio is polled periodically and compared with what the bot last set
+//! and what was last observed. A difference to both means someone changed
+//! the bio manually (e.g. from another device).
+
+use std::collections::VecDeque;
+
+use crate::config::normalize_bio;
+
+/// How many external changes are remembered.
+const MAX_CHANGES: usize = 20;
+
+/// A bio change that was not made by the bot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileChange {
+    /// Bio the bot expected (what it set last, or what was seen before).
+    pub expected: String,
+
+    /// Bio found on the profile.
+    pub actual: String,
+
+    /// Unix timestamp when the change was detected.
+    pub detected_at_unix: u64,
+}
+
+/// Compares polled bios against the bot's own updates.
+#[derive(Debug, Clone, Default)]
+pub struct BioWatcher {
+    /// Bio seen on the last poll.
+    last_seen: Option<String>,
+
+    /// Recently detected external changes, oldest first.
+    changes: VecDeque<ProfileChange>,
+}
+
+impl BioWatcher {
+    /// Creates a watcher without a baseline.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a polled bio and returns the change if it was external.
+    ///
+    /// `set_by_bot` is the bio the bot last applied (`None` if it has not
+    /// applied one yet). The first poll before any bot update only sets
+    /// the baseline.
+    pub fn observe(
+        &mut self,
+        actual: &str,
+        set_by_bot: Option<&str>,
+        now: u64,
+    ) -> Option<ProfileChange> {
+        let last_seen = self.last_seen.replace(actual.to_owned());
+
+        // Telegram trims what the bot sends, so compare the trimmed texts
+        let same = |bio: &str| normalize_bio(bio) == normalize_bio(actual);
+        if set_by_bot.is_some_and(same) || last_seen.as_deref().is_some_and(same) {
+            return None;
+        }
+
+        let expected = set_by_bot.map(str::to_owned).or(last_seen)?;
+        let change = ProfileChange {
+            expected,
+            actual: actual.to_owned(),
+            detected_at_unix: now,
+        };
+
+        self.changes.push_back(change.clone());
+        if self.changes.len() > MAX_CHANGES {
+            self.changes.pop_front();
+        }
+        Some(change)
+    }
+
+    /// Returns the recently detected external changes, oldest first.
+    pub fn changes(&self) -> impl Iterator<Item = &ProfileChange> {
+        self.changes.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_poll_sets_baseline() {
+        let mut watcher = BioWatcher::new();
+        assert!(watcher.observe("manual bio", None, 100).is_none());
+        assert!(watcher.observe("manual bio", None, 200).is_none());
+
+        // Changed between polls without the bot
+        let change = watcher.observe("edited", None, 300).unwrap();
+        assert_eq!(change.expected, "manual bio");
+        assert_eq!(change.actual, "edited");
+        assert_eq!(watcher.changes().count(), 1);
+    }
+
+    #[test]
+    fn test_bot_updates_are_not_external() {
+        let mut watcher = BioWatcher::new();
+        assert!(watcher.observe("A", Some("A"), 100).is_none());
+        assert!(watcher.observe("B", Some("B"), 200).is_none());
+        assert_eq!(watcher.changes().count(), 0);
+    }
+
+    #[test]
+    fn test_trimmed_server_echo_matches_bot_bio() {
+        let mut watcher = BioWatcher::new();
+        assert!(watcher.observe("Hello", Some("Hello  "), 100).is_none());
+        assert!(watcher.observe("Hello", Some("Hello\n"), 200).is_none());
+        assert_eq!(watcher.changes().count(), 0);
+    }
+
+    #[test]
+    fn test_divergence_from_bot_bio_reported_once() {
+        let mut watcher = BioWatcher::new();
+        watcher.observe("A", Some("A"), 100);
+
+        let change = watcher.observe("typed by hand", Some("A"), 200).unwrap();
+        assert_eq!(change.expected, "A");
+        assert_eq!(change.detected_at_unix, 200);
+
+        // Still diverged, but already reported
+        assert!(watcher.observe("typed by hand", Some("A"), 300).is_none());
+
+        // The bot takes over again
+        assert!(watcher.observe("B", Some("B"), 400).is_none());
+        assert_eq!(watcher.changes().count(), 1);
+    }
+
+    #[test]
+    fn test_changes_are_bounded() {
+        let mut watcher = BioWatcher::new();
+        watcher.observe("start", None, 0);
+        for i in 1..=30 {
+            watcher.observe(&format!("bio {i}"), None, i);
+        }
+
+        assert_eq!(watcher.changes().count(), MAX_CHANGES);
+        assert_eq!(watcher.changes().last().unwrap().actual, "bio 30");
+    }
+}
@@ -1,6 +1,8 @@
 //! Telegram client wrapper for profile management.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use grammers_client::client::{LoginToken, PasswordToken, UpdatesConfiguration};
@@ -16,8 +18,28 @@ use tracing::{debug, info, warn};
 /// Type alias for the updates receiver from `SenderPool`.
 pub type RawUpdatesReceiver = mpsc::UnboundedReceiver<UpdatesLike>;
 
-use super::RateLimiter;
-use crate::config::TelegramConfig;
+use super::{BioWatcher, DEFAULT_METHOD, ProfileChange, RateLimiter};
+use crate::config::{CommandSource, TelegramConfig, normalize_bio};
+use crate::util::truncate;
+
+/// Flood wait keys of the API methods the bot calls.
+const UPDATE_PROFILE: &str = "account.updateProfile";
+const UPDATE_EMOJI_STATUS: &str = "account.updateEmojiStatus";
+const UPDATE_USERNAME: &str = "account.updateUsername";
+const UPDATE_STATUS: &str = "account.updateStatus";
+const GET_USERS: &str = "users.getUsers";
+const GET_FULL_USER: &str = "users.getFullUser";
+const GET_APP_CONFIG: &str = "help.getAppConfig";
+const GET_DIALOGS: &str = "messages.getDialogs";
+
+/// Dialogs fetched per request while looking for a command channel.
+const DIALOGS_PAGE_SIZE: i32 = 100;
+
+/// Folders searched for a command channel: the main list and the archive.
+const DIALOG_FOLDERS: [Option<i32>; 2] = [None, Some(1)];
+
+/// App config keys holding the bio limits for free and premium accounts.
+const ABOUT_LIMIT_KEYS: (&str, &str) = ("about_length_limit_default", "about_length_limit_premium");
 
 /// Re-export types for external use.
 pub use grammers_client::client::{LoginToken as Token, PasswordToken as PwdToken};
@@ -31,6 +53,12 @@ pub enum TelegramError {
     #[error("Sign in failed: {0}")]
     SignInFailed(String),
 
+    #[error("Login code expired")]
+    LoginCodeExpired,
+
+    #[error("Invalid login code")]
+    LoginCodeInvalid,
+
     #[error("Password required for 2FA")]
     PasswordRequired(PasswordToken),
 
@@ -40,6 +68,15 @@ pub enum TelegramError {
     #[error("Failed to update profile: {0}")]
     ProfileUpdateFailed(String),
 
+    #[error("Bio rejected by Telegram as too long")]
+    BioTooLong,
+
+    #[error("Username is already taken")]
+    UsernameOccupied,
+
+    #[error("Username rejected by Telegram as invalid")]
+    UsernameInvalid,
+
     #[error("Flood wait required: {0} seconds")]
     FloodWait(u32),
 
@@ -54,20 +91,138 @@ pub enum TelegramError {
 
     #[error("Rate limited: {0} seconds remaining")]
     RateLimited(u32),
+
+    #[error("Cannot use the command channel: {0}")]
+    CommandChannel(String),
+
+    #[error("Signed in as a bot; this tool is for user accounts (sign in with a phone number)")]
+    BotAccount,
 }
 
 impl From<InvocationError> for TelegramError {
     fn from(err: InvocationError) -> Self {
-        let err_str = err.to_string();
+        classify_invocation_error(err.to_string())
+    }
+}
 
-        // Check for flood wait errors
-        if (err_str.contains("FLOOD_WAIT") || err_str.contains("flood"))
-            && let Some(seconds) = extract_flood_wait_seconds(&err_str)
-        {
-            return Self::FloodWait(seconds);
-        }
+/// Maps an invocation error message to a specific error where possible.
+fn classify_invocation_error(err_str: String) -> TelegramError {
+    // Check for flood wait errors
+    if (err_str.contains("FLOOD_WAIT") || err_str.contains("flood"))
+        && let Some(seconds) = extract_flood_wait_seconds(&err_str)
+    {
+        return TelegramError::FloodWait(seconds);
+    }
 
-        Self::Invocation(err_str)
+    // The server may count length differently than local validation
+    if err_str.contains("ABOUT_TOO_LONG") {
+        return TelegramError::BioTooLong;
+    }
+
+    if err_str.contains("USERNAME_OCCUPIED") || err_str.contains("USERNAME_PURCHASE_AVAILABLE") {
+        return TelegramError::UsernameOccupied;
+    }
+    if err_str.contains("USERNAME_INVALID") {
+        return TelegramError::UsernameInvalid;
+    }
+
+    TelegramError::Invocation(err_str)
+}
+
+/// Refuses to run as a bot: bots have no profile bio of their own to
+/// rotate, and premium detection does not apply to them.
+///
+/// # Errors
+///
+/// Returns [`TelegramError::BotAccount`] if `is_bot` is set.
+pub const fn ensure_user_account(is_bot: bool) -> Result<(), TelegramError> {
+    if is_bot {
+        Err(TelegramError::BotAccount)
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads the (free, premium) bio limits from the `help.getAppConfig` JSON.
+///
+/// Returns `None` unless both limits are present as positive whole numbers.
+#[must_use]
+fn bio_limits_from_app_config(config: &tl::enums::JsonValue) -> Option<(usize, usize)> {
+    let tl::enums::JsonValue::JsonObject(object) = config else {
+        return None;
+    };
+    let limit = |key: &str| {
+        object.value.iter().find_map(|entry| {
+            let tl::enums::JsonObjectValue::Value(entry) = entry;
+            match &entry.value {
+                tl::enums::JsonValue::JsonNumber(number) if entry.key == key => {
+                    let value = number.value;
+                    // Checked to be a small positive whole number first
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    (value.fract() == 0.0 && (1.0..=f64::from(u16::MAX)).contains(&value))
+                        .then_some(value as usize)
+                }
+                _ => None,
+            }
+        })
+    };
+    Some((limit(ABOUT_LIMIT_KEYS.0)?, limit(ABOUT_LIMIT_KEYS.1)?))
+}
+
+/// Builds an `account.updateProfile` request; `None` fields are not sent.
+fn profile_update_request(
+    first_name: Option<&str>,
+    last_name: Option<&str>,
+    about: Option<&str>,
+) -> tl::functions::account::UpdateProfile {
+    tl::functions::account::UpdateProfile {
+        first_name: first_name.map(str::to_owned),
+        last_name: last_name.map(str::to_owned),
+        about: about.map(str::to_owned),
+    }
+}
+
+/// Converts an `auth.LoginToken` response into a QR login result.
+fn login_token_result(
+    response: Result<tl::enums::auth::LoginToken, InvocationError>,
+) -> Result<QrAuthResult, TelegramError> {
+    match response {
+        Ok(tl::enums::auth::LoginToken::Token(token)) => {
+            debug!("Got login token, expires: {}", token.expires);
+            Ok(QrAuthResult::Token {
+                token: token.token,
+                expires: token.expires,
+            })
+        }
+        Ok(tl::enums::auth::LoginToken::MigrateTo(migrate)) => {
+            debug!("Need to migrate to DC {}", migrate.dc_id);
+            Ok(QrAuthResult::MigrateTo {
+                dc_id: migrate.dc_id,
+                token: migrate.token,
+            })
+        }
+        Ok(tl::enums::auth::LoginToken::Success(success)) => {
+            debug!("QR login successful!");
+            if let tl::enums::auth::Authorization::Authorization(auth) = success.authorization
+                && let tl::enums::User::User(user) = auth.user
+            {
+                return Ok(QrAuthResult::Success {
+                    user_id: user.id,
+                    username: user.username,
+                });
+            }
+            Ok(QrAuthResult::Success {
+                user_id: 0,
+                username: None,
+            })
+        }
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("SESSION_PASSWORD_NEEDED") {
+                return Ok(QrAuthResult::PasswordRequired);
+            }
+            Err(TelegramError::SignInFailed(err_str))
+        }
     }
 }
 
@@ -90,6 +245,42 @@ fn extract_flood_wait_seconds(err_msg: &str) -> Option<u32> {
     None
 }
 
+/// Kind of login code failure reported by Telegram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoginCodeError {
+    /// The code timed out; a new one must be requested.
+    Expired,
+    /// The code was mistyped; the user can try again.
+    Invalid,
+}
+
+/// Classifies a sign-in error message as an expired or invalid login code.
+fn classify_login_code_error(err_msg: &str) -> Option<LoginCodeError> {
+    let upper = err_msg.to_uppercase();
+
+    if upper.contains("PHONE_CODE_EXPIRED") {
+        Some(LoginCodeError::Expired)
+    } else if upper.contains("PHONE_CODE_INVALID")
+        || upper.contains("PHONE_CODE_EMPTY")
+        || upper.contains("INVALID CODE")
+    {
+        Some(LoginCodeError::Invalid)
+    } else {
+        None
+    }
+}
+
+/// A text message from Saved Messages or a command channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedMessage {
+    /// Message ID.
+    pub id: i32,
+    /// Message text.
+    pub text: String,
+    /// Unix time of the last edit, if the message was edited.
+    pub edit_date: Option<i32>,
+}
+
 /// Result of QR code authentication attempt.
 #[derive(Debug, Clone)]
 pub enum QrAuthResult {
@@ -104,6 +295,8 @@ pub enum QrAuthResult {
     MigrateTo {
         /// Target datacenter ID.
         dc_id: i32,
+        /// Token to import on the target DC.
+        token: Vec<u8>,
     },
     /// Authentication successful.
     Success {
@@ -138,7 +331,7 @@ pub struct TelegramBot {
     handle: sender::SenderPoolHandle,
 
     /// Rate limiter for API calls.
-    rate_limiter: RateLimiter,
+    rate_limiter: Arc<RateLimiter>,
 
     /// Current profile state.
     state: RwLock<ProfileState>,
@@ -146,6 +339,18 @@ pub struct TelegramBot {
     /// Cached user ID (set after first `get_me` call).
     cached_user_id: RwLock<Option<i64>>,
 
+    /// Detects bio changes made outside the bot.
+    bio_watcher: RwLock<BioWatcher>,
+
+    /// Access hashes of the channels commands are read from, by ID.
+    command_channels: RwLock<HashMap<i64, i64>>,
+
+    /// Whether the account should keep appearing offline.
+    offline_mode: AtomicBool,
+
+    /// Whether profile updates are only logged (`--read-only`).
+    read_only: AtomicBool,
+
     /// Background task running the sender pool.
     _pool_task: JoinHandle<()>,
 }
@@ -193,9 +398,13 @@ impl TelegramBot {
         let bot = Self {
             client,
             handle: handle.thin,
-            rate_limiter: RateLimiter::from_secs(rate_limit_secs),
+            rate_limiter: Arc::new(RateLimiter::from_secs(rate_limit_secs)),
             state: RwLock::new(ProfileState::default()),
             cached_user_id: RwLock::new(None),
+            bio_watcher: RwLock::new(BioWatcher::new()),
+            command_channels: RwLock::new(HashMap::new()),
+            offline_mode: AtomicBool::new(false),
+            read_only: AtomicBool::new(false),
             _pool_task: pool_task,
         };
 
@@ -268,10 +477,15 @@ impl TelegramBot {
                 debug!("2FA password required, hint: {:?}", password_token.hint());
                 Err(TelegramError::PasswordRequired(password_token))
             }
-            Err(SignInError::InvalidCode) => {
-                Err(TelegramError::SignInFailed("Invalid code".to_owned()))
+            Err(SignInError::InvalidCode) => Err(TelegramError::LoginCodeInvalid),
+            Err(e) => {
+                let err_str = e.to_string();
+                match classify_login_code_error(&err_str) {
+                    Some(LoginCodeError::Expired) => Err(TelegramError::LoginCodeExpired),
+                    Some(LoginCodeError::Invalid) => Err(TelegramError::LoginCodeInvalid),
+                    None => Err(TelegramError::SignInFailed(err_str)),
+                }
             }
-            Err(e) => Err(TelegramError::SignInFailed(e.to_string())),
         }
     }
 
@@ -318,43 +532,26 @@ impl TelegramBot {
             except_ids: vec![],
         };
 
-        match self.client.invoke(&request).await {
-            Ok(tl::enums::auth::LoginToken::Token(token)) => {
-                debug!("Got login token, expires: {}", token.expires);
-                Ok(QrAuthResult::Token {
-                    token: token.token,
-                    expires: token.expires,
-                })
-            }
-            Ok(tl::enums::auth::LoginToken::MigrateTo(migrate)) => {
-                debug!("Need to migrate to DC {}", migrate.dc_id);
-                Ok(QrAuthResult::MigrateTo {
-                    dc_id: migrate.dc_id,
-                })
-            }
-            Ok(tl::enums::auth::LoginToken::Success(success)) => {
-                debug!("QR login successful!");
-                if let tl::enums::auth::Authorization::Authorization(auth) = success.authorization
-                    && let tl::enums::User::User(user) = auth.user
-                {
-                    return Ok(QrAuthResult::Success {
-                        user_id: user.id,
-                        username: user.username,
-                    });
-                }
-                Ok(QrAuthResult::Success {
-                    user_id: 0,
-                    username: None,
-                })
-            }
-            Err(e) => {
-                let err_str = e.to_string();
-                if err_str.contains("SESSION_PASSWORD_NEEDED") {
-                    return Ok(QrAuthResult::PasswordRequired);
-                }
-                Err(TelegramError::SignInFailed(err_str))
-            }
-        }
+        login_token_result(self.client.invoke(&request).await)
+    }
+
+    /// Imports a login token on the datacenter the account lives in.
+    ///
+    /// Called after [`QrAuthResult::MigrateTo`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn import_login_token(
+        &self,
+        dc_id: i32,
+        token: Vec<u8>,
+    ) -> Result<QrAuthResult, TelegramError> {
+        debug!("Importing login token on DC {}...", dc_id);
+
+        let request = tl::functions::auth::ImportLoginToken { token };
+
+        login_token_result(self.client.invoke_in_dc(dc_id, &request).await)
     }
 
     /// Accepts a login token (called when QR code is scanned).
@@ -376,17 +573,28 @@ impl TelegramBot {
 
     /// Updates the user's profile bio/about text.
     ///
+    /// Trailing whitespace is dropped first (see [`normalize_bio`]), so the
+    /// recorded bio matches what Telegram echoes back.
+    ///
     /// # Errors
     ///
     /// Returns an error if the update fails or if rate limited.
     pub async fn update_bio(&self, bio: &str) -> Result<(), TelegramError> {
+        let bio = normalize_bio(bio);
+        if self.is_read_only() {
+            info!(
+                "Read-only mode, not updating bio to: \"{}\"",
+                truncate(bio, 30)
+            );
+            return Ok(());
+        }
         if !self.is_authorized().await? {
             return Err(TelegramError::NotAuthorized);
         }
 
         // Check rate limit without blocking - let caller decide when to retry
-        if !self.rate_limiter.is_allowed().await {
-            let remaining = self.rate_limiter.time_until_allowed().await;
+        let remaining = self.time_until_allowed().await;
+        if !remaining.is_zero() {
             let secs = u32::try_from(remaining.as_secs()).unwrap_or(u32::MAX);
             debug!("Rate limited, {} seconds remaining", secs);
             return Err(TelegramError::RateLimited(secs));
@@ -395,27 +603,159 @@ impl TelegramBot {
         // Mark as used before API call
         self.rate_limiter.mark_used().await;
 
-        info!("Updating bio to: \"{}\"", truncate_for_log(bio, 30));
+        info!("Updating bio to: \"{}\"", truncate(bio, 30));
+
+        self.invoke_update_profile(None, None, Some(bio)).await?;
+
+        let mut state = self.state.write().await;
+        state.current_bio = Some(bio.to_owned());
+        state.is_skipped = false;
+        debug!("Bio update API call succeeded");
+        Ok(())
+    }
+
+    /// Sets the custom emoji status (Telegram Premium only).
+    ///
+    /// Not counted against the bio rate limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or the API call fails.
+    pub async fn update_emoji_status(&self, document_id: i64) -> Result<(), TelegramError> {
+        if self.is_read_only() {
+            info!("Read-only mode, not setting emoji status {}", document_id);
+            return Ok(());
+        }
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        debug!("Updating emoji status to document {}", document_id);
 
-        let request = tl::functions::account::UpdateProfile {
-            first_name: None,
-            last_name: None,
-            about: Some(bio.to_owned()),
+        let request = tl::functions::account::UpdateEmojiStatus {
+            emoji_status: tl::enums::EmojiStatus::Status(tl::types::EmojiStatus {
+                document_id,
+                until: None,
+            }),
         };
 
-        match self.client.invoke(&request).await {
-            Ok(_user) => {
-                let mut state = self.state.write().await;
-                state.current_bio = Some(bio.to_owned());
-                state.is_skipped = false;
-                debug!("Bio update API call succeeded");
-                Ok(())
+        self.invoke_method(UPDATE_EMOJI_STATUS, &request).await?;
+        Ok(())
+    }
+
+    /// Sets the public @username (without the `@`).
+    ///
+    /// Not counted against the bio rate limit. Setting the username the
+    /// account already has is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelegramError::UsernameOccupied`] or
+    /// [`TelegramError::UsernameInvalid`] if Telegram refuses the username,
+    /// or another error if not authorized or the API call fails.
+    pub async fn update_username(&self, username: &str) -> Result<(), TelegramError> {
+        if self.is_read_only() {
+            info!("Read-only mode, not setting username @{}", username);
+            return Ok(());
+        }
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        debug!("Updating username to @{}", username);
+
+        let request = tl::functions::account::UpdateUsername {
+            username: username.to_owned(),
+        };
+        match self.invoke_method(UPDATE_USERNAME, &request).await {
+            Ok(_) => Ok(()),
+            Err(TelegramError::Invocation(e)) if e.contains("USERNAME_NOT_MODIFIED") => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Updates profile fields; `None` leaves a field unchanged.
+    ///
+    /// Only a bio change is counted against the bio rate limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized, rate limited, or the API call fails.
+    pub async fn update_profile(
+        &self,
+        first_name: Option<&str>,
+        last_name: Option<&str>,
+        about: Option<&str>,
+    ) -> Result<(), TelegramError> {
+        if self.is_read_only() {
+            info!(
+                "Read-only mode, not updating profile (first name: {:?}, last name: {:?})",
+                first_name, last_name
+            );
+            return Ok(());
+        }
+
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        // Only bio changes use up the bio rate limit; a name change alone
+        // is still held back by a flood wait on the method
+        if about.is_some() {
+            let remaining = self.time_until_allowed().await;
+            if !remaining.is_zero() {
+                let secs = u32::try_from(remaining.as_secs()).unwrap_or(u32::MAX);
+                return Err(TelegramError::RateLimited(secs));
             }
+            self.rate_limiter.mark_used().await;
+        }
+
+        info!(
+            "Updating profile (first name: {:?}, last name: {:?}, bio: {})",
+            first_name,
+            last_name,
+            about.is_some()
+        );
+
+        self.invoke_update_profile(first_name, last_name, about)
+            .await
+    }
+
+    /// Sends `account.updateProfile` and handles flood waits.
+    async fn invoke_update_profile(
+        &self,
+        first_name: Option<&str>,
+        last_name: Option<&str>,
+        about: Option<&str>,
+    ) -> Result<(), TelegramError> {
+        let request = profile_update_request(first_name, last_name, about);
+        self.invoke_method(UPDATE_PROFILE, &request).await?;
+        Ok(())
+    }
+
+    /// Sends `request` unless `method` is in a flood wait, and records a
+    /// flood wait Telegram imposes on it.
+    ///
+    /// Flood waits are kept per method, so a wait on profile updates does
+    /// not hold up e.g. the premium check.
+    async fn invoke_method<R: tl::RemoteCall>(
+        &self,
+        method: &'static str,
+        request: &R,
+    ) -> Result<R::Return, TelegramError> {
+        let remaining = self.rate_limiter.flood_wait_remaining(method).await;
+        if !remaining.is_zero() {
+            let secs = u32::try_from(remaining.as_secs().max(1)).unwrap_or(u32::MAX);
+            debug!("{} is in a flood wait, {} seconds remaining", method, secs);
+            return Err(TelegramError::RateLimited(secs));
+        }
+
+        match self.client.invoke(request).await {
+            Ok(result) => Ok(result),
             Err(e) => {
-                let err: TelegramError = e.into();
-                if let TelegramError::FloodWait(seconds) = &err {
-                    warn!("Flood wait triggered: {} seconds", seconds);
-                    self.rate_limiter.handle_flood_wait(*seconds).await;
+                let err = TelegramError::from(e);
+                if let TelegramError::FloodWait(seconds) = err {
+                    self.rate_limiter.handle_flood_wait(method, seconds).await;
                 }
                 Err(err)
             }
@@ -439,9 +779,28 @@ impl TelegramBot {
         state.is_skipped = true;
     }
 
-    /// Gets the time remaining until the next API call is allowed.
+    /// Gets the time remaining until the next profile update is allowed
+    /// (the minimum interval or a flood wait, whichever is longer).
     pub async fn time_until_allowed(&self) -> Duration {
-        self.rate_limiter.time_until_allowed().await
+        self.rate_limiter
+            .time_until_allowed()
+            .await
+            .max(self.rate_limiter.flood_wait_remaining(UPDATE_PROFILE).await)
+    }
+
+    /// Relaxes the minimum interval between profile updates to `interval`
+    /// for the next `window`.
+    pub async fn allow_burst(&self, window: Duration, interval: Duration) {
+        self.rate_limiter
+            .with_temporary_override(window, interval)
+            .await;
+    }
+
+    /// Returns the rate limiter guarding profile updates, e.g. for the
+    /// `rate-limit` command.
+    #[must_use]
+    pub fn rate_limiter(&self) -> Arc<RateLimiter> {
+        Arc::clone(&self.rate_limiter)
     }
 
     /// Returns a reference to the underlying client for advanced operations.
@@ -466,7 +825,7 @@ impl TelegramBot {
             id: vec![tl::enums::InputUser::UserSelf],
         };
 
-        match self.client.invoke(&request).await {
+        match self.invoke_method(GET_USERS, &request).await {
             Ok(users) => {
                 if let Some(tl::enums::User::User(user)) = users.first() {
                     let is_premium = user.premium;
@@ -479,11 +838,190 @@ impl TelegramBot {
             }
             Err(e) => {
                 warn!("Failed to check premium status: {}", e);
-                Err(e.into())
+                Err(e)
             }
         }
     }
 
+    /// Fetches the bio limits Telegram currently enforces, as (free, premium).
+    ///
+    /// Returns `None` if the app config doesn't list them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn bio_limits(&self) -> Result<Option<(usize, usize)>, TelegramError> {
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        let request = tl::functions::help::GetAppConfig { hash: 0 };
+        match self.invoke_method(GET_APP_CONFIG, &request).await? {
+            tl::enums::help::AppConfig::Config(app_config) => {
+                Ok(bio_limits_from_app_config(&app_config.config))
+            }
+            tl::enums::help::AppConfig::NotModified => Ok(None),
+        }
+    }
+
+    /// Checks if the signed-in account is a bot rather than a user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn is_bot_account(&self) -> Result<bool, TelegramError> {
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        let request = tl::functions::users::GetUsers {
+            id: vec![tl::enums::InputUser::UserSelf],
+        };
+
+        match self.invoke_method(GET_USERS, &request).await?.first() {
+            Some(tl::enums::User::User(user)) => Ok(user.bot),
+            _ => Err(TelegramError::Invocation(
+                "Could not get user info".to_owned(),
+            )),
+        }
+    }
+
+    /// Fetches the current bio from the profile (empty if none is set).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn get_current_bio(&self) -> Result<String, TelegramError> {
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        let request = tl::functions::users::GetFullUser {
+            id: tl::enums::InputUser::UserSelf,
+        };
+
+        let tl::enums::users::UserFull::Full(full) =
+            self.invoke_method(GET_FULL_USER, &request).await?;
+        let tl::enums::UserFull::Full(user) = full.full_user;
+        Ok(user.about.unwrap_or_default())
+    }
+
+    /// Polls the bio and records it if it was changed outside the bot.
+    ///
+    /// Returns the detected change, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bio could not be fetched.
+    pub async fn check_external_bio_change(&self) -> Result<Option<ProfileChange>, TelegramError> {
+        let before = self.state.read().await.current_bio.clone();
+        let bio = self.get_current_bio().await?;
+
+        // The bot updated the bio meanwhile; the fetched value may be stale
+        let set_by_bot = self.state.read().await.current_bio.clone();
+        if set_by_bot != before {
+            return Ok(None);
+        }
+
+        let now = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or(0);
+        let change = self
+            .bio_watcher
+            .write()
+            .await
+            .observe(&bio, set_by_bot.as_deref(), now);
+
+        if let Some(change) = &change {
+            warn!(
+                "External bio change detected: expected \"{}\", found \"{}\"",
+                truncate(&change.expected, 30),
+                truncate(&change.actual, 30)
+            );
+        }
+        Ok(change)
+    }
+
+    /// Returns the recently detected bio changes not made by the bot,
+    /// oldest first.
+    pub async fn recent_profile_changes(&self) -> Vec<ProfileChange> {
+        self.bio_watcher.read().await.changes().cloned().collect()
+    }
+
+    /// Checks if the account is currently shown as online (e.g. the user
+    /// has a Telegram app open).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn self_is_online(&self) -> Result<bool, TelegramError> {
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        let request = tl::functions::users::GetUsers {
+            id: vec![tl::enums::InputUser::UserSelf],
+        };
+
+        let users = self.invoke_method(GET_USERS, &request).await?;
+        Ok(matches!(
+            users.first(),
+            Some(tl::enums::User::User(user))
+                if matches!(user.status, Some(tl::enums::UserStatus::Online(_)))
+        ))
+    }
+
+    /// Sets the account's online status and remembers it for
+    /// [`keep_offline`](Self::keep_offline).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn set_offline(&self, offline: bool) -> Result<(), TelegramError> {
+        if !self.is_authorized().await? {
+            return Err(TelegramError::NotAuthorized);
+        }
+
+        debug!(
+            "Setting account status to {}",
+            if offline { "offline" } else { "online" }
+        );
+
+        let request = tl::functions::account::UpdateStatus { offline };
+        self.invoke_method(UPDATE_STATUS, &request).await?;
+        self.offline_mode.store(offline, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Checks if the account is kept offline.
+    #[must_use]
+    pub fn is_offline_mode(&self) -> bool {
+        self.offline_mode.load(Ordering::Relaxed)
+    }
+
+    /// Turns read-only mode on or off: profile updates are then logged
+    /// and reported as successful without reaching Telegram.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    /// Checks if profile updates are only logged.
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Marks the account offline again if offline mode is on, since sending
+    /// replies shows it as online.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or API call fails.
+    pub async fn keep_offline(&self) -> Result<(), TelegramError> {
+        if self.is_offline_mode() {
+            self.set_offline(true).await?;
+        }
+        Ok(())
+    }
+
     /// Gets the cached user ID, fetching it from Telegram if not cached.
     ///
     /// # Errors
@@ -515,17 +1053,11 @@ impl TelegramBot {
             id: vec![tl::enums::InputUser::UserSelf],
         };
 
-        match self.client.invoke(&request).await {
-            Ok(users) => {
-                if let Some(tl::enums::User::User(user)) = users.first() {
-                    Ok((user.id, user.username.clone()))
-                } else {
-                    Err(TelegramError::Invocation(
-                        "Could not get user info".to_owned(),
-                    ))
-                }
-            }
-            Err(e) => Err(e.into()),
+        match self.invoke_method(GET_USERS, &request).await?.first() {
+            Some(tl::enums::User::User(user)) => Ok((user.id, user.username.clone())),
+            _ => Err(TelegramError::Invocation(
+                "Could not get user info".to_owned(),
+            )),
         }
     }
 
@@ -535,9 +1067,22 @@ impl TelegramBot {
     ///
     /// Returns an error if the message could not be sent.
     pub async fn send_to_saved_messages(&self, text: &str) -> Result<(), TelegramError> {
-        debug!("Sending message to Saved Messages");
+        self.send_to_source(CommandSource::SavedMessages, text)
+            .await
+    }
 
-        let user_id = self.get_user_id().await?;
+    /// Sends a message to a command source, e.g. a reply to a command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message could not be sent, or the source is
+    /// a channel not opened with [`Self::open_command_channel`].
+    pub async fn send_to_source(
+        &self,
+        source: CommandSource,
+        text: &str,
+    ) -> Result<(), TelegramError> {
+        debug!("Sending message to {}", source);
 
         let request = tl::functions::messages::SendMessage {
             no_webpage: true,
@@ -549,10 +1094,7 @@ impl TelegramBot {
             invert_media: false,
             allow_paid_floodskip: false,
             allow_paid_stars: None,
-            peer: tl::enums::InputPeer::User(tl::types::InputPeerUser {
-                user_id,
-                access_hash: 0, // Self doesn't need access hash
-            }),
+            peer: self.source_peer(source).await?,
             reply_to: None,
             message: text.to_owned(),
             random_id: rand_i64(),
@@ -566,31 +1108,38 @@ impl TelegramBot {
             suggested_post: None,
         };
 
-        self.client
-            .invoke(&request)
+        // Messages have no bucket of their own
+        self.invoke_method(DEFAULT_METHOD, &request)
             .await
             .map(|_| ())
-            .map_err(|e| TelegramError::Invocation(e.to_string()))
     }
 
-    /// Gets recent messages from Saved Messages.
-    ///
-    /// Returns a list of (`message_id`, text) tuples for recent text messages.
+    /// Gets recent text messages from Saved Messages, newest first.
     ///
     /// # Errors
     ///
     /// Returns an error if not authorized or API call fails.
-    pub async fn get_saved_messages(
+    pub async fn get_saved_messages(&self, limit: i32) -> Result<Vec<SavedMessage>, TelegramError> {
+        self.get_source_messages(CommandSource::SavedMessages, limit)
+            .await
+    }
+
+    /// Gets recent text messages from a command source, newest first.
+    ///
+    /// Only the account's own messages are returned from a channel, so
+    /// other members cannot run commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized, the API call fails or the
+    /// source is a channel not opened with [`Self::open_command_channel`].
+    pub async fn get_source_messages(
         &self,
+        source: CommandSource,
         limit: i32,
-    ) -> Result<Vec<(i32, String)>, TelegramError> {
-        let user_id = self.get_user_id().await?;
-
+    ) -> Result<Vec<SavedMessage>, TelegramError> {
         let request = tl::functions::messages::GetHistory {
-            peer: tl::enums::InputPeer::User(tl::types::InputPeerUser {
-                user_id,
-                access_hash: 0,
-            }),
+            peer: self.source_peer(source).await?,
             offset_id: 0,
             offset_date: 0,
             add_offset: 0,
@@ -600,18 +1149,125 @@ impl TelegramBot {
             hash: 0,
         };
 
-        match self.client.invoke(&request).await {
+        let own_only = matches!(source, CommandSource::Channel(_));
+        match self.invoke_method(DEFAULT_METHOD, &request).await {
             Ok(tl::enums::messages::Messages::Messages(msgs)) => {
-                Ok(extract_text_messages(&msgs.messages))
+                Ok(extract_text_messages(&msgs.messages, own_only))
             }
             Ok(tl::enums::messages::Messages::Slice(msgs)) => {
-                Ok(extract_text_messages(&msgs.messages))
+                Ok(extract_text_messages(&msgs.messages, own_only))
             }
             Ok(tl::enums::messages::Messages::ChannelMessages(msgs)) => {
-                Ok(extract_text_messages(&msgs.messages))
+                Ok(extract_text_messages(&msgs.messages, own_only))
             }
             Ok(tl::enums::messages::Messages::NotModified(_)) => Ok(vec![]),
-            Err(e) => Err(TelegramError::Invocation(e.to_string())),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Looks up a channel used for commands and checks that the account
+    /// can post there, so replies can be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the channel cannot be found or the account may
+    /// not post in it.
+    pub async fn open_command_channel(&self, channel_id: i64) -> Result<(), TelegramError> {
+        let channel = self.find_dialog_channel(channel_id).await?.ok_or_else(|| {
+            TelegramError::CommandChannel(format!(
+                "channel {channel_id} is not among the account's chats"
+            ))
+        })?;
+
+        if !can_post(&channel) {
+            return Err(TelegramError::CommandChannel(format!(
+                "the account cannot post in '{}'",
+                channel.title
+            )));
+        }
+
+        info!("Accepting commands from channel '{}'", channel.title);
+        self.command_channels
+            .write()
+            .await
+            .insert(channel_id, channel.access_hash.unwrap_or(0));
+        Ok(())
+    }
+
+    /// Walks the account's dialogs (including the archive) for a channel,
+    /// which also yields the access hash needed to talk to it.
+    async fn find_dialog_channel(
+        &self,
+        channel_id: i64,
+    ) -> Result<Option<tl::types::Channel>, TelegramError> {
+        for folder_id in DIALOG_FOLDERS {
+            let mut offset = DialogsOffset::default();
+            loop {
+                let request = tl::functions::messages::GetDialogs {
+                    exclude_pinned: false,
+                    folder_id,
+                    offset_date: offset.date,
+                    offset_id: offset.id,
+                    offset_peer: offset.peer.clone(),
+                    limit: DIALOGS_PAGE_SIZE,
+                    hash: 0,
+                };
+                let (page, last_page) = match self.invoke_method(GET_DIALOGS, &request).await? {
+                    tl::enums::messages::Dialogs::Dialogs(d) => (
+                        DialogsPage::new(d.dialogs, d.messages, d.chats, d.users),
+                        true,
+                    ),
+                    tl::enums::messages::Dialogs::Slice(d) => {
+                        let last = d.dialogs.len() < DIALOGS_PAGE_SIZE as usize;
+                        (
+                            DialogsPage::new(d.dialogs, d.messages, d.chats, d.users),
+                            last,
+                        )
+                    }
+                    tl::enums::messages::Dialogs::NotModified(_) => break,
+                };
+
+                if let Some(channel) = page.channel(channel_id) {
+                    return Ok(Some(channel));
+                }
+                match page.next_offset() {
+                    Some(next) if !last_page => offset = next,
+                    _ => break,
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the chat to read commands from and send replies to.
+    async fn source_peer(
+        &self,
+        source: CommandSource,
+    ) -> Result<tl::enums::InputPeer, TelegramError> {
+        match source {
+            CommandSource::SavedMessages => {
+                Ok(tl::enums::InputPeer::User(tl::types::InputPeerUser {
+                    user_id: self.get_user_id().await?,
+                    access_hash: 0, // Self doesn't need access hash
+                }))
+            }
+            CommandSource::Channel(channel_id) => {
+                let access_hash = self
+                    .command_channels
+                    .read()
+                    .await
+                    .get(&channel_id)
+                    .copied()
+                    .ok_or_else(|| {
+                        TelegramError::CommandChannel(format!(
+                            "channel {channel_id} was not opened"
+                        ))
+                    })?;
+                Ok(tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+                    channel_id,
+                    access_hash,
+                }))
+            }
         }
     }
 
@@ -622,15 +1278,158 @@ impl TelegramBot {
     }
 }
 
-/// Extracts text messages from a list of TL messages.
-fn extract_text_messages(messages: &[tl::enums::Message]) -> Vec<(i32, String)> {
+/// Position in the dialog list to continue from (`messages.getDialogs`).
+#[derive(Debug, Clone)]
+struct DialogsOffset {
+    date: i32,
+    id: i32,
+    peer: tl::enums::InputPeer,
+}
+
+impl Default for DialogsOffset {
+    fn default() -> Self {
+        Self {
+            date: 0,
+            id: 0,
+            peer: tl::enums::InputPeer::Empty,
+        }
+    }
+}
+
+/// One page of `messages.getDialogs`.
+struct DialogsPage {
+    dialogs: Vec<tl::enums::Dialog>,
+    messages: Vec<tl::enums::Message>,
+    chats: Vec<tl::enums::Chat>,
+    users: Vec<tl::enums::User>,
+}
+
+impl DialogsPage {
+    const fn new(
+        dialogs: Vec<tl::enums::Dialog>,
+        messages: Vec<tl::enums::Message>,
+        chats: Vec<tl::enums::Chat>,
+        users: Vec<tl::enums::User>,
+    ) -> Self {
+        Self {
+            dialogs,
+            messages,
+            chats,
+            users,
+        }
+    }
+
+    /// Returns the channel with `channel_id` if it is on this page.
+    fn channel(&self, channel_id: i64) -> Option<tl::types::Channel> {
+        self.chats.iter().find_map(|chat| match chat {
+            tl::enums::Chat::Channel(channel) if channel.id == channel_id => Some(channel.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns where the next page starts: after the last dialog, keyed by
+    /// the date of its top message and its peer.
+    fn next_offset(&self) -> Option<DialogsOffset> {
+        let dialog = self.dialogs.iter().rev().find_map(|dialog| match dialog {
+            tl::enums::Dialog::Dialog(dialog) => Some(dialog),
+            tl::enums::Dialog::Folder(_) => None,
+        })?;
+        let date = self.messages.iter().find_map(|message| {
+            let (id, date, peer) = match message {
+                tl::enums::Message::Message(m) => (m.id, m.date, &m.peer_id),
+                tl::enums::Message::Service(m) => (m.id, m.date, &m.peer_id),
+                tl::enums::Message::Empty(_) => return None,
+            };
+            (id == dialog.top_message && same_peer(peer, &dialog.peer)).then_some(date)
+        })?;
+
+        Some(DialogsOffset {
+            date,
+            id: dialog.top_message,
+            peer: self.input_peer(&dialog.peer)?,
+        })
+    }
+
+    /// Builds the input peer for `peer` from the users and chats on the page.
+    fn input_peer(&self, peer: &tl::enums::Peer) -> Option<tl::enums::InputPeer> {
+        match peer {
+            tl::enums::Peer::User(p) => self.users.iter().find_map(|user| match user {
+                tl::enums::User::User(u) if u.id == p.user_id => {
+                    Some(tl::enums::InputPeer::User(tl::types::InputPeerUser {
+                        user_id: u.id,
+                        access_hash: u.access_hash.unwrap_or(0),
+                    }))
+                }
+                _ => None,
+            }),
+            tl::enums::Peer::Chat(p) => {
+                Some(tl::enums::InputPeer::Chat(tl::types::InputPeerChat {
+                    chat_id: p.chat_id,
+                }))
+            }
+            tl::enums::Peer::Channel(p) => self.channel(p.channel_id).map(|c| {
+                tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+                    channel_id: c.id,
+                    access_hash: c.access_hash.unwrap_or(0),
+                })
+            }),
+        }
+    }
+}
+
+/// Checks if two peers are the same chat.
+fn same_peer(a: &tl::enums::Peer, b: &tl::enums::Peer) -> bool {
+    match (a, b) {
+        (tl::enums::Peer::User(a), tl::enums::Peer::User(b)) => a.user_id == b.user_id,
+        (tl::enums::Peer::Chat(a), tl::enums::Peer::Chat(b)) => a.chat_id == b.chat_id,
+        (tl::enums::Peer::Channel(a), tl::enums::Peer::Channel(b)) => a.channel_id == b.channel_id,
+        _ => false,
+    }
+}
+
+/// Checks if the account can post in a channel: as its creator, as an
+/// admin allowed to post, or in a group as an admin or a member who is
+/// not restricted from sending messages.
+fn can_post(channel: &tl::types::Channel) -> bool {
+    if channel.left {
+        return false;
+    }
+    if channel.creator {
+        return true;
+    }
+    let admin_rights = channel
+        .admin_rights
+        .as_ref()
+        .map(|tl::enums::ChatAdminRights::Rights(rights)| rights);
+    if channel.broadcast {
+        return admin_rights.is_some_and(|rights| rights.post_messages);
+    }
+    // Group admins are not bound by the default restrictions
+    admin_rights.is_some()
+        || !(cannot_send(channel.banned_rights.as_ref())
+            || cannot_send(channel.default_banned_rights.as_ref()))
+}
+
+/// Checks if restrictions forbid sending messages.
+fn cannot_send(rights: Option<&tl::enums::ChatBannedRights>) -> bool {
+    matches!(rights, Some(tl::enums::ChatBannedRights::Rights(rights)) if rights.send_messages)
+}
+
+/// Extracts text messages from a list of TL messages, only the account's
+/// own ones if `own_only` is set.
+fn extract_text_messages(messages: &[tl::enums::Message], own_only: bool) -> Vec<SavedMessage> {
     messages
         .iter()
         .filter_map(|msg| {
             if let tl::enums::Message::Message(m) = msg
                 && !m.message.is_empty()
+                && (m.out || !own_only)
             {
-                return Some((m.id, m.message.clone()));
+                return Some(SavedMessage {
+                    id: m.id,
+                    text: m.message.clone(),
+                    edit_date: m.edit_date,
+                });
             }
             None
         })
@@ -665,15 +1464,6 @@ fn mask_phone(phone: &str) -> String {
     }
 }
 
-/// Truncates a string for logging purposes.
-fn truncate_for_log(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_owned()
-    } else {
-        format!("{}...", s.chars().take(max_len).collect::<String>())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -685,10 +1475,41 @@ mod tests {
         assert_eq!(mask_phone("+7 (999) 123-45-67"), "***4567");
     }
 
+    fn app_config(entries: &[(&str, f64)]) -> tl::enums::JsonValue {
+        tl::enums::JsonValue::JsonObject(tl::types::JsonObject {
+            value: entries
+                .iter()
+                .map(|&(key, value)| {
+                    tl::enums::JsonObjectValue::Value(tl::types::JsonObjectValue {
+                        key: key.to_owned(),
+                        value: tl::enums::JsonValue::JsonNumber(tl::types::JsonNumber { value }),
+                    })
+                })
+                .collect(),
+        })
+    }
+
     #[test]
-    fn test_truncate_for_log() {
-        assert_eq!(truncate_for_log("Hello", 10), "Hello");
-        assert_eq!(truncate_for_log("Hello, World!", 5), "Hello...");
+    fn test_bio_limits_from_app_config() {
+        let config = app_config(&[
+            ("upload_max_fileparts_default", 4000.0),
+            ("about_length_limit_default", 80.0),
+            ("about_length_limit_premium", 160.0),
+        ]);
+        assert_eq!(bio_limits_from_app_config(&config), Some((80, 160)));
+
+        // Both are needed, as sane numbers
+        let partial = app_config(&[("about_length_limit_default", 80.0)]);
+        assert_eq!(bio_limits_from_app_config(&partial), None);
+        let odd = app_config(&[
+            ("about_length_limit_default", 80.5),
+            ("about_length_limit_premium", 160.0),
+        ]);
+        assert_eq!(bio_limits_from_app_config(&odd), None);
+        assert_eq!(
+            bio_limits_from_app_config(&tl::enums::JsonValue::JsonNull),
+            None
+        );
     }
 
     #[test]
@@ -700,4 +1521,78 @@ mod tests {
         );
         assert_eq!(extract_flood_wait_seconds("some other error"), None);
     }
+
+    #[test]
+    fn test_profile_update_request_omits_unset_fields() {
+        let request = profile_update_request(Some("Alex"), None, None);
+        assert_eq!(request.first_name.as_deref(), Some("Alex"));
+        assert!(request.last_name.is_none());
+        assert!(request.about.is_none());
+
+        let request = profile_update_request(None, None, Some("bio"));
+        assert!(request.first_name.is_none());
+        assert_eq!(request.about.as_deref(), Some("bio"));
+    }
+
+    #[test]
+    fn test_bot_accounts_are_refused() {
+        assert!(matches!(
+            ensure_user_account(true),
+            Err(TelegramError::BotAccount)
+        ));
+        assert!(ensure_user_account(false).is_ok());
+    }
+
+    #[test]
+    fn test_classify_invocation_error() {
+        assert!(matches!(
+            classify_invocation_error(
+                "rpc error 400: ABOUT_TOO_LONG caused by account.updateProfile".to_owned()
+            ),
+            TelegramError::BioTooLong
+        ));
+        assert!(matches!(
+            classify_invocation_error(
+                "rpc error 420: FLOOD_WAIT_30 caused by account.updateProfile".to_owned()
+            ),
+            TelegramError::FloodWait(30)
+        ));
+        assert!(matches!(
+            classify_invocation_error(
+                "rpc error 400: USERNAME_OCCUPIED caused by account.updateUsername".to_owned()
+            ),
+            TelegramError::UsernameOccupied
+        ));
+        assert!(matches!(
+            classify_invocation_error(
+                "rpc error 400: USERNAME_INVALID caused by account.updateUsername".to_owned()
+            ),
+            TelegramError::UsernameInvalid
+        ));
+        assert!(matches!(
+            classify_invocation_error("rpc error 500: INTERNAL".to_owned()),
+            TelegramError::Invocation(_)
+        ));
+    }
+
+    #[test]
+    fn test_classify_login_code_error() {
+        assert_eq!(
+            classify_login_code_error("rpc error 400: PHONE_CODE_EXPIRED caused by auth.signIn"),
+            Some(LoginCodeError::Expired)
+        );
+        assert_eq!(
+            classify_login_code_error("rpc error 400: PHONE_CODE_INVALID caused by auth.signIn"),
+            Some(LoginCodeError::Invalid)
+        );
+        assert_eq!(
+            classify_login_code_error("PHONE_CODE_EMPTY"),
+            Some(LoginCodeError::Invalid)
+        );
+        assert_eq!(
+            classify_login_code_error("invalid code"),
+            Some(LoginCodeError::Invalid)
+        );
+        assert_eq!(classify_login_code_error("FLOOD_WAIT_60"), None);
+    }
 }
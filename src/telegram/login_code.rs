@@ -0,0 +1,217 @@
+//! Where the login code and 2FA password come from during sign in.
+//!
+//! Unattended provisioning can't type the code, so it can be handed over
+//! in `TG_LOGIN_CODE` or written to a file (`--code-file`) once it
+//! arrives out-of-band; otherwise the code is asked for on the terminal.
+//! The 2FA password likewise comes from `TG_PASSWORD_FILE` or
+//! `TG_PASSWORD` before falling back to the terminal.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long to wait for the login code file by default, in seconds.
+pub const LOGIN_CODE_TIMEOUT_SECS: u64 = 300;
+
+/// Pause between two checks of the login code file, in milliseconds.
+pub const LOGIN_CODE_POLL_MS: u64 = 500;
+
+/// Source of the login code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginCodeSource {
+    /// Read from a file that appears once the code is delivered.
+    File(PathBuf),
+    /// Given up front in `TG_LOGIN_CODE`.
+    Env(String),
+    /// Asked for on the terminal.
+    Prompt,
+}
+
+impl LoginCodeSource {
+    /// Picks the source: the code file (`--code-file`) first, then the
+    /// code from the environment, else the terminal prompt.
+    #[must_use]
+    pub fn select(code_file: Option<PathBuf>, env_code: Option<&str>) -> Self {
+        if let Some(path) = code_file {
+            return Self::File(path);
+        }
+        match env_code.and_then(parse_code) {
+            Some(code) => Self::Env(code),
+            None => Self::Prompt,
+        }
+    }
+
+    /// Checks if a wrong code can be corrected, by typing it again or
+    /// rewriting the file; a code from the environment cannot.
+    #[must_use]
+    pub const fn can_retry(&self) -> bool {
+        !matches!(self, Self::Env(_))
+    }
+
+    /// Checks if the code has to be typed on the terminal.
+    #[must_use]
+    pub const fn needs_terminal(&self) -> bool {
+        matches!(self, Self::Prompt)
+    }
+}
+
+/// Source of the 2FA password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordSource {
+    /// Read from a file (`TG_PASSWORD_FILE`).
+    File(PathBuf),
+    /// Given in `TG_PASSWORD`.
+    Env(String),
+    /// Asked for on the terminal.
+    Prompt,
+}
+
+impl PasswordSource {
+    /// Picks the source: the password file first, then the password from
+    /// the environment, else the terminal prompt.
+    #[must_use]
+    pub fn select(password_file: Option<PathBuf>, env_password: Option<&str>) -> Self {
+        if let Some(path) = password_file {
+            return Self::File(path);
+        }
+        match env_password.and_then(parse_password) {
+            Some(password) => Self::Env(password),
+            None => Self::Prompt,
+        }
+    }
+
+    /// Checks if the password has to be typed on the terminal.
+    #[must_use]
+    pub const fn needs_terminal(&self) -> bool {
+        matches!(self, Self::Prompt)
+    }
+}
+
+/// Extracts a 2FA password from a file or variable: only the trailing
+/// line break is dropped, as spaces may be part of it.
+#[must_use]
+pub fn parse_password(text: &str) -> Option<String> {
+    let password = text.trim_end_matches(['\n', '\r']);
+    (!password.is_empty()).then(|| password.to_owned())
+}
+
+/// Extracts a login code from user input or a file: surrounding
+/// whitespace is dropped, `None` if nothing is left.
+#[must_use]
+pub fn parse_code(text: &str) -> Option<String> {
+    let code = text.trim();
+    (!code.is_empty()).then(|| code.to_owned())
+}
+
+/// Waits until the file at `path` holds a login code other than `used`
+/// (a code already rejected), and returns it.
+///
+/// A file last written before `requested_at`, when the code was requested,
+/// holds a code from an earlier sign in and is ignored. Returns `None` if
+/// no new code shows up within `timeout`.
+pub async fn wait_for_code_file(
+    path: &Path,
+    used: Option<&str>,
+    requested_at: SystemTime,
+    timeout: Duration,
+) -> Option<String> {
+    // File times can be as coarse as a second
+    let since = requested_at
+        .checked_sub(Duration::from_secs(1))
+        .unwrap_or(requested_at);
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if let Ok(meta) = tokio::fs::metadata(path).await
+            && meta.modified().is_ok_and(|modified| modified >= since)
+            && let Ok(content) = tokio::fs::read_to_string(path).await
+            && let Some(code) = parse_code(&content)
+            && used != Some(code.as_str())
+        {
+            return Some(code);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(Duration::from_millis(LOGIN_CODE_POLL_MS)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_code_source() {
+        let file = PathBuf::from("/run/secrets/login_code");
+        assert_eq!(
+            LoginCodeSource::select(Some(file.clone()), Some("12345")),
+            LoginCodeSource::File(file)
+        );
+        assert_eq!(
+            LoginCodeSource::select(None, Some(" 12345\n")),
+            LoginCodeSource::Env("12345".to_owned())
+        );
+        assert_eq!(
+            LoginCodeSource::select(None, Some("  ")),
+            LoginCodeSource::Prompt
+        );
+        assert_eq!(LoginCodeSource::select(None, None), LoginCodeSource::Prompt);
+
+        assert!(!LoginCodeSource::Env("12345".to_owned()).can_retry());
+        assert!(LoginCodeSource::Prompt.can_retry());
+        assert!(LoginCodeSource::Prompt.needs_terminal());
+    }
+
+    #[test]
+    fn test_select_password_source() {
+        let file = PathBuf::from("/run/secrets/tg_password");
+        assert_eq!(
+            PasswordSource::select(Some(file.clone()), Some("secret")),
+            PasswordSource::File(file)
+        );
+        assert_eq!(
+            PasswordSource::select(None, Some(" two words \n")),
+            PasswordSource::Env(" two words ".to_owned())
+        );
+        assert_eq!(
+            PasswordSource::select(None, Some("")),
+            PasswordSource::Prompt
+        );
+        assert!(PasswordSource::select(None, None).needs_terminal());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_code_file() {
+        let path = std::env::temp_dir().join(format!("login_code_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // The code arrives while waiting
+        let writer = {
+            let path = path.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                tokio::fs::write(&path, "54321\n").await.unwrap();
+            })
+        };
+        let requested_at = SystemTime::now();
+        let code = wait_for_code_file(&path, None, requested_at, Duration::from_secs(5)).await;
+        writer.await.unwrap();
+        assert_eq!(code.as_deref(), Some("54321"));
+
+        // A rejected code is not returned again
+        let code = wait_for_code_file(
+            &path,
+            Some("54321"),
+            requested_at,
+            Duration::from_millis(100),
+        )
+        .await;
+        assert_eq!(code, None);
+
+        // A file left over from before the code was requested is ignored
+        let later = SystemTime::now() + Duration::from_secs(90);
+        let code = wait_for_code_file(&path, None, later, Duration::from_millis(100)).await;
+        assert_eq!(code, None);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -3,12 +3,27 @@
 //! Provides high-level abstractions for interacting with Telegram,
 //! including authentication, profile updates, and rate limiting.
 
+mod bio_watch;
 mod client;
+mod login_code;
+mod qr;
 mod rate_limiter;
+mod retry;
+pub mod session_crypto;
 
+pub use bio_watch::{BioWatcher, ProfileChange};
 pub use client::{
-    PwdToken as PasswordToken, QrAuthResult, RawUpdatesReceiver, TelegramBot, TelegramError,
-    Token as LoginToken,
+    PwdToken as PasswordToken, QrAuthResult, RawUpdatesReceiver, SavedMessage, TelegramBot,
+    TelegramError, Token as LoginToken, ensure_user_account,
 };
 pub use grammers_client::update::Update;
-pub use rate_limiter::RateLimiter;
+pub use login_code::{
+    LOGIN_CODE_POLL_MS, LOGIN_CODE_TIMEOUT_SECS, LoginCodeSource, PasswordSource, parse_code,
+    parse_password, wait_for_code_file,
+};
+pub use qr::{
+    MAX_QR_MIGRATIONS, MigrationCounter, QR_CONFIRM_INTERVAL_MS, QR_POLL_INTERVAL_MS, QrAction,
+    jittered, qr_auth_step,
+};
+pub use rate_limiter::{DEFAULT_METHOD, RateLimiter};
+pub use retry::connect_with_retry;
@@ -0,0 +1,215 @@
+//! QR code login polling logic.
+
+use std::time::Duration;
+
+use super::{QrAuthResult, TelegramError};
+
+/// Pause between two login token polls, in milliseconds.
+pub const QR_POLL_INTERVAL_MS: u64 = 2_000;
+
+/// Pause while waiting for the user to confirm a 2FA login, in milliseconds.
+pub const QR_CONFIRM_INTERVAL_MS: u64 = 3_000;
+
+/// Upper bound of the random delay added to every pause, in milliseconds.
+pub const QR_JITTER_MS: u64 = 500;
+
+/// How many datacenter migrations a single login may go through.
+pub const MAX_QR_MIGRATIONS: u32 = 5;
+
+/// What the QR login loop should do after a poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QrAction {
+    /// A new token arrived: redraw the QR code.
+    ShowToken {
+        /// Raw token bytes to encode.
+        token: Vec<u8>,
+        /// Seconds until the token expires.
+        expires_in: i64,
+    },
+    /// Same token as before: only refresh the countdown.
+    UpdateCountdown {
+        /// Seconds until the token expires.
+        expires_in: i64,
+    },
+    /// The login must be completed on another datacenter.
+    Migrate {
+        /// Target datacenter ID.
+        dc_id: i32,
+        /// Token to import there.
+        token: Vec<u8>,
+    },
+    /// 2FA is enabled; the user must confirm on their phone.
+    WaitForConfirmation,
+    /// Login finished.
+    Done {
+        /// User ID of the authenticated user.
+        user_id: i64,
+        /// Username if available.
+        username: Option<String>,
+    },
+}
+
+/// Decides the next QR login action from a poll result.
+///
+/// `last_token` is the token currently on screen and `now_unix` the
+/// current Unix time, used for the expiry countdown.
+#[must_use]
+pub fn qr_auth_step(result: QrAuthResult, last_token: Option<&[u8]>, now_unix: i64) -> QrAction {
+    match result {
+        QrAuthResult::Token { token, expires } => {
+            let expires_in = (i64::from(expires) - now_unix).max(0);
+            if last_token == Some(token.as_slice()) {
+                QrAction::UpdateCountdown { expires_in }
+            } else {
+                QrAction::ShowToken { token, expires_in }
+            }
+        }
+        QrAuthResult::MigrateTo { dc_id, token } => QrAction::Migrate { dc_id, token },
+        QrAuthResult::PasswordRequired => QrAction::WaitForConfirmation,
+        QrAuthResult::Success { user_id, username } => QrAction::Done { user_id, username },
+    }
+}
+
+/// Returns `base_ms` plus a jitter of up to [`QR_JITTER_MS`] derived
+/// from `seed`, so parallel logins do not poll in lockstep.
+#[must_use]
+pub fn jittered(base_ms: u64, seed: u64) -> Duration {
+    Duration::from_millis(base_ms + seed % (QR_JITTER_MS + 1))
+}
+
+/// Counts datacenter migrations during one QR login.
+///
+/// Telegram may answer an imported token with yet another migration;
+/// the counter stops the login instead of bouncing between DCs forever.
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationCounter {
+    attempts: u32,
+    limit: u32,
+}
+
+impl MigrationCounter {
+    /// Creates a counter allowing `limit` migrations.
+    #[must_use]
+    pub const fn new(limit: u32) -> Self {
+        Self { attempts: 0, limit }
+    }
+
+    /// Records a migration and returns its 1-based number.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelegramError::SignInFailed`] once more than `limit`
+    /// migrations were requested.
+    pub fn record(&mut self) -> Result<u32, TelegramError> {
+        self.attempts = self.attempts.saturating_add(1);
+        if self.attempts > self.limit {
+            return Err(TelegramError::SignInFailed(format!(
+                "QR login was redirected to another datacenter more than {} times",
+                self.limit
+            )));
+        }
+        Ok(self.attempts)
+    }
+}
+
+impl Default for MigrationCounter {
+    fn default() -> Self {
+        Self::new(MAX_QR_MIGRATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_result(token: &[u8], expires: i32) -> QrAuthResult {
+        QrAuthResult::Token {
+            token: token.to_vec(),
+            expires,
+        }
+    }
+
+    #[test]
+    fn test_new_token_is_shown() {
+        let action = qr_auth_step(token_result(b"abc", 1_030), None, 1_000);
+        assert_eq!(
+            action,
+            QrAction::ShowToken {
+                token: b"abc".to_vec(),
+                expires_in: 30
+            }
+        );
+
+        // A refreshed token replaces the old one
+        let action = qr_auth_step(token_result(b"def", 1_030), Some(b"abc"), 1_000);
+        assert!(matches!(action, QrAction::ShowToken { .. }));
+    }
+
+    #[test]
+    fn test_same_token_updates_countdown() {
+        let action = qr_auth_step(token_result(b"abc", 1_030), Some(b"abc"), 1_010);
+        assert_eq!(action, QrAction::UpdateCountdown { expires_in: 20 });
+
+        // Never counts below zero
+        let action = qr_auth_step(token_result(b"abc", 1_030), Some(b"abc"), 1_100);
+        assert_eq!(action, QrAction::UpdateCountdown { expires_in: 0 });
+    }
+
+    #[test]
+    fn test_migrate_and_finish() {
+        let action = qr_auth_step(
+            QrAuthResult::MigrateTo {
+                dc_id: 4,
+                token: b"xyz".to_vec(),
+            },
+            Some(b"abc"),
+            0,
+        );
+        assert_eq!(
+            action,
+            QrAction::Migrate {
+                dc_id: 4,
+                token: b"xyz".to_vec()
+            }
+        );
+
+        assert_eq!(
+            qr_auth_step(QrAuthResult::PasswordRequired, None, 0),
+            QrAction::WaitForConfirmation
+        );
+
+        let action = qr_auth_step(
+            QrAuthResult::Success {
+                user_id: 42,
+                username: Some("alice".to_owned()),
+            },
+            Some(b"abc"),
+            0,
+        );
+        assert!(matches!(action, QrAction::Done { user_id: 42, .. }));
+    }
+
+    #[test]
+    fn test_migration_counter_trips_after_limit() {
+        let mut counter = MigrationCounter::new(MAX_QR_MIGRATIONS);
+        for expected in 1..=MAX_QR_MIGRATIONS {
+            assert_eq!(counter.record().unwrap(), expected);
+        }
+        assert!(matches!(
+            counter.record(),
+            Err(TelegramError::SignInFailed(_))
+        ));
+
+        // A zero limit refuses the first migration
+        assert!(MigrationCounter::new(0).record().is_err());
+    }
+
+    #[test]
+    fn test_jitter_is_bounded() {
+        for seed in [0, 1, QR_JITTER_MS, QR_JITTER_MS + 1, u64::MAX] {
+            let delay = jittered(QR_POLL_INTERVAL_MS, seed);
+            assert!(delay >= Duration::from_millis(QR_POLL_INTERVAL_MS));
+            assert!(delay <= Duration::from_millis(QR_POLL_INTERVAL_MS + QR_JITTER_MS));
+        }
+    }
+}
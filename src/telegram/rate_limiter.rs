@@ -1,21 +1,49 @@
 //! Rate limiter for Telegram API calls.
 //!
 //! Implements a simple rate limiter to avoid triggering Telegram's
-//! flood wait errors when updating the profile bio.
+//! flood wait errors when updating the profile bio, and keeps track of the
+//! flood waits Telegram imposes, separately for each API method.
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use tokio::sync::Mutex;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
+
+/// Flood wait key for calls that don't name their API method.
+pub const DEFAULT_METHOD: &str = "default";
+
+/// How many times longer the interval is during slow start.
+pub const SLOW_START_FACTOR: u32 = 2;
 
 /// Rate limiter that enforces minimum intervals between operations.
 #[derive(Debug)]
 pub struct RateLimiter {
-    /// Minimum duration between allowed operations.
-    min_interval: Duration,
+    /// Minimum duration between allowed operations (adjustable at runtime).
+    min_interval: Mutex<Duration>,
 
     /// Last time an operation was performed.
     last_operation: Mutex<Option<Instant>>,
+
+    /// Temporarily relaxed interval, if any.
+    interval_override: Mutex<Option<IntervalOverride>>,
+
+    /// End of the flood wait imposed on each API method, e.g.
+    /// `account.updateProfile`.
+    flood_waits: Mutex<HashMap<&'static str, Instant>>,
+
+    /// Operations left that use the longer slow start interval.
+    slow_start_remaining: Mutex<u32>,
+}
+
+/// A shorter minimum interval that applies until a deadline.
+#[derive(Debug, Clone, Copy)]
+struct IntervalOverride {
+    /// Minimum duration between operations while the override lasts.
+    interval: Duration,
+
+    /// When the normal interval applies again.
+    until: Instant,
 }
 
 impl RateLimiter {
@@ -23,8 +51,11 @@ impl RateLimiter {
     #[must_use]
     pub fn new(min_interval: Duration) -> Self {
         Self {
-            min_interval,
+            min_interval: Mutex::new(min_interval),
             last_operation: Mutex::new(None),
+            interval_override: Mutex::new(None),
+            flood_waits: Mutex::new(HashMap::new()),
+            slow_start_remaining: Mutex::new(0),
         }
     }
 
@@ -34,16 +65,103 @@ impl RateLimiter {
         Self::new(Duration::from_secs(secs))
     }
 
+    /// Uses `interval` instead of the normal minimum interval for the next
+    /// `window`, after which the normal interval applies again.
+    ///
+    /// Short intervals risk flood waits from Telegram.
+    pub async fn with_temporary_override(&self, window: Duration, interval: Duration) {
+        warn!(
+            "Rate limit relaxed to {:?} for {:?}; this risks flood waits",
+            interval, window
+        );
+        *self.interval_override.lock().await = Some(IntervalOverride {
+            interval,
+            until: Instant::now() + window,
+        });
+    }
+
+    /// Returns how long a temporary override still lasts, if one is active.
+    pub async fn override_remaining(&self) -> Option<Duration> {
+        let until = self.interval_override.lock().await.as_ref()?.until;
+        let remaining = until.saturating_duration_since(Instant::now());
+        (!remaining.is_zero()).then_some(remaining)
+    }
+
+    /// Returns the normal minimum interval (ignoring a temporary override).
+    pub async fn min_interval(&self) -> Duration {
+        *self.min_interval.lock().await
+    }
+
+    /// Changes the normal minimum interval; the next operation already
+    /// waits for the new value.
+    pub async fn set_min_interval(&self, interval: Duration) {
+        info!("Rate limit interval set to {:?}", interval);
+        *self.min_interval.lock().await = interval;
+    }
+
+    /// Makes the next `count` operations (counting from this one) use
+    /// [`SLOW_START_FACTOR`] times the normal minimum interval.
+    ///
+    /// Meant for right after startup, when the previous run may have ended
+    /// in a crash loop of rapid updates.
+    pub async fn set_slow_start(&self, count: u32) {
+        if count > 0 {
+            info!(
+                "Slow start: the first {} updates wait {}x the minimum interval",
+                count, SLOW_START_FACTOR
+            );
+        }
+        *self.slow_start_remaining.lock().await = count;
+    }
+
+    /// Returns how many operations still use the slow start interval.
+    pub async fn slow_start_remaining(&self) -> u32 {
+        *self.slow_start_remaining.lock().await
+    }
+
+    /// Returns the minimum interval currently in effect.
+    ///
+    /// A temporary override wins over slow start.
+    async fn interval(&self) -> Duration {
+        let mut current = self.interval_override.lock().await;
+        match *current {
+            Some(o) if Instant::now() < o.until => o.interval,
+            Some(_) => {
+                debug!("Rate limit override expired");
+                *current = None;
+                self.normal_interval().await
+            }
+            None => self.normal_interval().await,
+        }
+    }
+
+    /// Returns the minimum interval, lengthened during slow start.
+    async fn normal_interval(&self) -> Duration {
+        let interval = self.min_interval().await;
+        if *self.slow_start_remaining.lock().await > 0 {
+            interval.saturating_mul(SLOW_START_FACTOR)
+        } else {
+            interval
+        }
+    }
+
+    /// Counts an operation against the slow start.
+    async fn count_slow_start(&self) {
+        let mut remaining = self.slow_start_remaining.lock().await;
+        *remaining = remaining.saturating_sub(1);
+    }
+
     /// Waits until an operation is allowed, then marks the operation as performed.
     ///
     /// Returns the duration waited (0 if no wait was needed).
     pub async fn wait_and_acquire(&self) -> Duration {
+        let min_interval = self.interval().await;
         let mut last = self.last_operation.lock().await;
 
         let wait_duration = if let Some(last_time) = *last {
             let elapsed = last_time.elapsed();
-            if elapsed < self.min_interval {
-                self.min_interval - elapsed
+            if elapsed < min_interval {
+                min_interval - elapsed
             } else {
                 Duration::ZERO
             }
@@ -60,52 +178,70 @@ impl RateLimiter {
         }
 
         *last = Some(Instant::now());
+        drop(last);
+        self.count_slow_start().await;
         wait_duration
     }
 
     /// Checks if an operation is currently allowed without blocking.
     pub async fn is_allowed(&self) -> bool {
+        let min_interval = self.interval().await;
         let last = self.last_operation.lock().await;
         match *last {
-            Some(last_time) => last_time.elapsed() >= self.min_interval,
+            Some(last_time) => last_time.elapsed() >= min_interval,
             None => true,
         }
     }
 
     /// Marks an operation as just performed (non-blocking).
     pub async fn mark_used(&self) {
-        let mut last = self.last_operation.lock().await;
-        *last = Some(Instant::now());
+        *self.last_operation.lock().await = Some(Instant::now());
+        self.count_slow_start().await;
     }
 
     /// Returns the time remaining until the next operation is allowed.
     pub async fn time_until_allowed(&self) -> Duration {
+        let min_interval = self.interval().await;
         let last = self.last_operation.lock().await;
         match *last {
             Some(last_time) => {
                 let elapsed = last_time.elapsed();
-                if elapsed >= self.min_interval {
+                if elapsed >= min_interval {
                     Duration::ZERO
                 } else {
-                    self.min_interval - elapsed
+                    min_interval - elapsed
                 }
             }
             None => Duration::ZERO,
         }
     }
 
-    /// Handles a flood wait error from Telegram by updating the wait time.
-    pub async fn handle_flood_wait(&self, wait_seconds: u32) {
+    /// Handles a flood wait error from Telegram for `method`.
+    ///
+    /// Only `method` has to wait; other methods stay usable. Use
+    /// [`DEFAULT_METHOD`] for calls without a method of their own.
+    pub async fn handle_flood_wait(&self, method: &'static str, wait_seconds: u32) {
         warn!(
-            "Received flood wait from Telegram: {} seconds",
-            wait_seconds
+            "Received flood wait from Telegram for {}: {} seconds",
+            method, wait_seconds
         );
-        // We'll need to wait at least this long before the next operation
-        tokio::time::sleep(Duration::from_secs(u64::from(wait_seconds))).await;
+        let until = Instant::now() + Duration::from_secs(u64::from(wait_seconds));
+        self.flood_waits.lock().await.insert(method, until);
+    }
 
-        // Mark as just performed so the rate limiter knows to wait
-        let mut last = self.last_operation.lock().await;
-        *last = Some(Instant::now());
+    /// Returns how long `method` still has to wait after a flood wait
+    /// (0 if it may be called).
+    pub async fn flood_wait_remaining(&self, method: &'static str) -> Duration {
+        let mut waits = self.flood_waits.lock().await;
+        let Some(until) = waits.get(method) else {
+            return Duration::ZERO;
+        };
+        let remaining = until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            debug!("Flood wait for {} is over", method);
+            waits.remove(method);
+        }
+        remaining
     }
 
     /// Resets the rate limiter, allowing immediate operation.
@@ -153,4 +289,112 @@ mod tests {
         limiter.reset().await;
         assert!(limiter.is_allowed().await);
     }
+
+    #[tokio::test]
+    async fn test_temporary_override_allows_burst() {
+        let limiter = RateLimiter::new(Duration::from_secs(60));
+        limiter
+            .with_temporary_override(Duration::from_secs(60), Duration::ZERO)
+            .await;
+
+        for _ in 0..5 {
+            assert!(limiter.is_allowed().await);
+            assert_eq!(limiter.wait_and_acquire().await, Duration::ZERO);
+        }
+        assert!(limiter.override_remaining().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_temporary_override_expires() {
+        let limiter = RateLimiter::new(Duration::from_secs(60));
+        limiter
+            .with_temporary_override(Duration::from_millis(50), Duration::ZERO)
+            .await;
+        limiter.mark_used().await;
+        assert!(limiter.is_allowed().await);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        // Back to the normal interval
+        assert!(!limiter.is_allowed().await);
+        assert!(limiter.time_until_allowed().await > Duration::from_secs(50));
+        assert!(limiter.override_remaining().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_flood_wait_is_per_method() {
+        let limiter = RateLimiter::from_secs(0);
+        limiter.handle_flood_wait("account.updateProfile", 60).await;
+
+        let remaining = limiter.flood_wait_remaining("account.updateProfile").await;
+        assert!(remaining > Duration::from_secs(55));
+        assert_eq!(
+            limiter.flood_wait_remaining("users.getUsers").await,
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.flood_wait_remaining(DEFAULT_METHOD).await,
+            Duration::ZERO
+        );
+        // The interval between bio updates is a separate matter
+        assert!(limiter.is_allowed().await);
+    }
+
+    #[tokio::test]
+    async fn test_flood_wait_expires() {
+        let limiter = RateLimiter::from_secs(0);
+        limiter.handle_flood_wait(DEFAULT_METHOD, 0).await;
+
+        assert_eq!(
+            limiter.flood_wait_remaining(DEFAULT_METHOD).await,
+            Duration::ZERO
+        );
+        assert!(limiter.flood_waits.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slow_start_lengthens_first_operations() {
+        let limiter = RateLimiter::new(Duration::from_millis(40));
+        limiter.set_slow_start(3).await;
+
+        // The first acquire has nothing to wait for
+        assert_eq!(limiter.wait_and_acquire().await, Duration::ZERO);
+        for _ in 0..2 {
+            let waited = limiter.wait_and_acquire().await;
+            assert!(waited > Duration::from_millis(60), "{waited:?}");
+        }
+        assert_eq!(limiter.slow_start_remaining().await, 0);
+
+        // Back to the normal interval
+        let waited = limiter.wait_and_acquire().await;
+        assert!(waited > Duration::from_millis(20), "{waited:?}");
+        assert!(waited <= Duration::from_millis(40), "{waited:?}");
+    }
+
+    #[tokio::test]
+    async fn test_slow_start_counts_mark_used() {
+        let limiter = RateLimiter::from_secs(60);
+        limiter.set_slow_start(2).await;
+
+        limiter.mark_used().await;
+        assert!(limiter.time_until_allowed().await > Duration::from_secs(110));
+        limiter.mark_used().await;
+        assert!(limiter.time_until_allowed().await <= Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_min_interval_can_be_changed() {
+        let limiter = RateLimiter::from_secs(60);
+        assert_eq!(limiter.min_interval().await, Duration::from_secs(60));
+
+        limiter.wait_and_acquire().await;
+        assert!(limiter.time_until_allowed().await > Duration::from_secs(50));
+
+        // The next acquire waits for the new, shorter interval
+        limiter.set_min_interval(Duration::from_millis(50)).await;
+        assert_eq!(limiter.min_interval().await, Duration::from_millis(50));
+        let waited = limiter.wait_and_acquire().await;
+        assert!(waited <= Duration::from_millis(50));
+        assert!(!limiter.is_allowed().await);
+    }
 }
@@ -0,0 +1,124 @@
+//! Retrying the initial connection with exponential backoff.
+//!
+//! On servers the network is often not ready when the bot starts, so a
+//! failed first connection should not abort the whole run.
+
+use std::future::Future;
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::TelegramError;
+
+/// Longest pause between two connection attempts, in seconds.
+const MAX_BACKOFF_SECS: u64 = 300;
+
+/// Returns the pause after the given failed attempt (1-based):
+/// `base`, `2 * base`, `4 * base`, ..., capped at five minutes.
+#[must_use]
+pub fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    base.saturating_mul(factor)
+        .min(Duration::from_secs(MAX_BACKOFF_SECS))
+}
+
+/// Calls `connect` until it succeeds, retrying connection errors up to
+/// `retries` times with exponential backoff starting at `base`.
+///
+/// Other errors (e.g. an unreadable session file) are returned at once.
+///
+/// # Errors
+///
+/// Returns the last error once the retries are used up, or the first
+/// error that is not a connection error.
+pub async fn connect_with_retry<T, F, Fut>(
+    retries: u32,
+    base: Duration,
+    mut connect: F,
+) -> Result<T, TelegramError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, TelegramError>>,
+{
+    let attempts = retries.saturating_add(1);
+    let mut attempt = 1;
+
+    loop {
+        match connect().await {
+            Ok(connected) => return Ok(connected),
+            Err(e @ TelegramError::Connection(_)) if attempt < attempts => {
+                let delay = backoff_delay(base, attempt);
+                warn!(
+                    "Connection attempt {}/{} failed: {}. Retrying in {:?}",
+                    attempt, attempts, e, delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let base = Duration::from_secs(2);
+        assert_eq!(backoff_delay(base, 1), Duration::from_secs(2));
+        assert_eq!(backoff_delay(base, 2), Duration::from_secs(4));
+        assert_eq!(backoff_delay(base, 4), Duration::from_secs(16));
+        assert_eq!(
+            backoff_delay(base, 40),
+            Duration::from_secs(MAX_BACKOFF_SECS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_connected() {
+        let calls = AtomicU32::new(0);
+
+        let result = connect_with_retry(3, Duration::from_millis(1), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(TelegramError::Connection("network unreachable".to_owned())),
+                _ => Ok("connected"),
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Ok("connected")));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_retries() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = connect_with_retry(2, Duration::from_millis(1), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TelegramError::Connection("network unreachable".to_owned()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(TelegramError::Connection(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_not_retried() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = connect_with_retry(5, Duration::from_millis(1), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TelegramError::Session("database is locked".to_owned()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(TelegramError::Session(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,277 @@
+//! Passphrase encryption of the session file at rest.
+//!
+//! The encrypted session lives next to the plain one as `<session>.enc`:
+//! a magic header, an Argon2 salt, a nonce and the ChaCha20-Poly1305
+//! ciphertext. grammers needs a real database file, so the plain session is
+//! restored on startup ([`unseal`]) and encrypted again and removed on
+//! exit ([`seal`], through a [`SealGuard`] so an error exit seals too).
+//! While the bot runs (or after a crash) the plain file exists on disk.
+
+use std::path::{Path, PathBuf};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use tracing::{error, info};
+
+/// File header identifying an encrypted session.
+const MAGIC: &[u8; 8] = b"DUBSESS1";
+
+/// Length of the Argon2 salt in bytes.
+const SALT_LEN: usize = 16;
+
+/// Length of the ChaCha20-Poly1305 nonce in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Session encryption errors.
+#[derive(Debug, thiserror::Error)]
+pub enum SessionCryptoError {
+    #[error("Wrong session passphrase (or the encrypted session file is corrupted)")]
+    WrongPassphrase,
+
+    #[error("Not an encrypted session file")]
+    InvalidFormat,
+
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(String),
+
+    #[error("Session file I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Returns the path of the encrypted session (`<session>.enc`).
+#[must_use]
+pub fn encrypted_path(session_path: &Path) -> PathBuf {
+    let mut path = session_path.as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+/// Encrypts session bytes with a passphrase.
+///
+/// # Errors
+///
+/// Returns an error if the key cannot be derived from the passphrase.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, SessionCryptoError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = cipher(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| SessionCryptoError::InvalidFormat)?;
+
+    let mut data = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+/// Decrypts session bytes written by [`encrypt`].
+///
+/// # Errors
+///
+/// Returns [`SessionCryptoError::InvalidFormat`] for data not written by
+/// [`encrypt`] and [`SessionCryptoError::WrongPassphrase`] if it does not
+/// decrypt.
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, SessionCryptoError> {
+    let rest = data
+        .strip_prefix(MAGIC.as_slice())
+        .ok_or(SessionCryptoError::InvalidFormat)?;
+    if rest.len() < SALT_LEN + NONCE_LEN {
+        return Err(SessionCryptoError::InvalidFormat);
+    }
+
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    cipher(passphrase, salt)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| SessionCryptoError::WrongPassphrase)
+}
+
+/// Restores the plain session from `<session>.enc` before connecting.
+///
+/// A plain session that already exists is kept: it is newer than the
+/// encrypted copy (left behind by a crash or from before encryption was
+/// enabled). Returns `true` if the session was decrypted.
+///
+/// # Errors
+///
+/// Returns an error if the encrypted session cannot be read or decrypted,
+/// or the plain one cannot be written.
+pub fn unseal(session_path: &Path, passphrase: &str) -> Result<bool, SessionCryptoError> {
+    let encrypted = encrypted_path(session_path);
+    if session_path.exists() || !encrypted.exists() {
+        return Ok(false);
+    }
+
+    let plaintext = decrypt(&std::fs::read(&encrypted)?, passphrase)?;
+    write_private(session_path, &plaintext)?;
+    Ok(true)
+}
+
+/// Encrypts the plain session into `<session>.enc` and removes it.
+///
+/// Does nothing if there is no plain session.
+///
+/// # Errors
+///
+/// Returns an error if the plain session cannot be read or removed, or
+/// the encrypted copy cannot be written. The plain session is kept then.
+pub fn seal(session_path: &Path, passphrase: &str) -> Result<(), SessionCryptoError> {
+    if !session_path.exists() {
+        return Ok(());
+    }
+
+    let data = encrypt(&std::fs::read(session_path)?, passphrase)?;
+
+    // Write to a temporary file first so a crash never leaves a partial copy
+    let encrypted = encrypted_path(session_path);
+    let mut temp = encrypted.clone().into_os_string();
+    temp.push(".tmp");
+    write_private(Path::new(&temp), &data)?;
+    std::fs::rename(&temp, &encrypted)?;
+
+    std::fs::remove_file(session_path)?;
+    Ok(())
+}
+
+/// Seals the session when dropped, so every exit after [`unseal`] (an
+/// error return included) leaves it encrypted.
+///
+/// Drop it after disconnecting on a normal exit; the result is logged.
+#[derive(Debug)]
+pub struct SealGuard {
+    session_path: PathBuf,
+    passphrase: String,
+}
+
+impl SealGuard {
+    /// Creates a guard sealing `session_path` with `passphrase`.
+    #[must_use]
+    pub fn new(session_path: &Path, passphrase: &str) -> Self {
+        Self {
+            session_path: session_path.to_path_buf(),
+            passphrase: passphrase.to_owned(),
+        }
+    }
+}
+
+impl Drop for SealGuard {
+    fn drop(&mut self) {
+        match seal(&self.session_path, &self.passphrase) {
+            Ok(()) => info!("Session encrypted at rest"),
+            Err(e) => error!(
+                "Failed to encrypt the session, it stays unencrypted at {}: {}",
+                self.session_path.display(),
+                e
+            ),
+        }
+    }
+}
+
+/// Derives the cipher for a passphrase and salt.
+fn cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305, SessionCryptoError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| SessionCryptoError::KeyDerivation(e.to_string()))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+/// Writes a file readable only by the owner (on Unix).
+fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    std::io::Write::write_all(&mut options.open(path)?, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_requires_same_passphrase() {
+        let data = encrypt(b"auth key bytes", "correct horse").unwrap();
+        assert!(data.starts_with(MAGIC));
+        assert!(!data.windows(8).any(|w| w == b"auth key"));
+
+        assert_eq!(decrypt(&data, "correct horse").unwrap(), b"auth key bytes");
+        assert!(matches!(
+            decrypt(&data, "wrong horse"),
+            Err(SessionCryptoError::WrongPassphrase)
+        ));
+        assert!(matches!(
+            decrypt(b"SQLite format 3", "correct horse"),
+            Err(SessionCryptoError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_seal_and_unseal_files() {
+        let path = std::env::temp_dir().join(format!(
+            "description_bot_session_test_{}.db",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"session").unwrap();
+
+        seal(&path, "secret").unwrap();
+        assert!(!path.exists());
+        assert!(encrypted_path(&path).exists());
+
+        // A wrong passphrase leaves no plain session behind
+        assert!(matches!(
+            unseal(&path, "guess"),
+            Err(SessionCryptoError::WrongPassphrase)
+        ));
+        assert!(!path.exists());
+
+        assert!(unseal(&path, "secret").unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), b"session");
+
+        // An existing plain session is newer and is kept
+        std::fs::write(&path, b"newer").unwrap();
+        assert!(!unseal(&path, "secret").unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), b"newer");
+
+        let _ = std::fs::remove_file(encrypted_path(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_error_exit_seals_session() {
+        let path = std::env::temp_dir().join(format!(
+            "description_bot_session_guard_{}.db",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"session").unwrap();
+        seal(&path, "secret").unwrap();
+
+        // Fails after the session was restored, like a refused connection
+        let run = || -> Result<(), SessionCryptoError> {
+            let _seal = SealGuard::new(&path, "secret");
+            unseal(&path, "secret")?;
+            assert!(path.exists());
+            Err(SessionCryptoError::Io(std::io::ErrorKind::TimedOut.into()))
+        };
+        assert!(run().is_err());
+
+        assert!(!path.exists());
+        let data = std::fs::read(encrypted_path(&path)).unwrap();
+        assert_eq!(decrypt(&data, "secret").unwrap(), b"session");
+
+        let _ = std::fs::remove_file(encrypted_path(&path));
+    }
+}
@@ -0,0 +1,135 @@
+//! Small helpers shared by the bot, the command handler and the
+//! validator.
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use tracing::warn;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Pause before the second attempt of a failed write, doubled after each
+/// further failure.
+pub const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// Shortens `s` to at most `max_len` grapheme clusters for display,
+/// adding "..." if anything was cut.
+///
+/// Counting clusters rather than `char`s keeps emoji sequences such as
+/// flags and ZWJ families whole.
+#[must_use]
+pub fn truncate(s: &str, max_len: usize) -> String {
+    match s.grapheme_indices(true).nth(max_len) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_owned(),
+    }
+}
+
+/// Runs the write `op` up to `attempts` times (at least once), backing off
+/// between attempts while it fails with a transient error.
+///
+/// Meant for saves on networked filesystems, where a single write or
+/// rename can fail and succeed a moment later.
+///
+/// # Errors
+///
+/// Returns the last error once the attempts run out, or the first error
+/// that is not transient.
+pub fn retry_io<T>(
+    attempts: u32,
+    mut op: impl FnMut() -> std::io::Result<T>,
+) -> std::io::Result<T> {
+    let mut delay = RETRY_BASE_DELAY;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < attempts && is_transient(&e) => {
+                warn!("Write failed ({}), retrying in {:?}", e, delay);
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Checks if an I/O error may go away when the operation is retried.
+fn is_transient(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::TimedOut
+            | ErrorKind::WouldBlock
+            | ErrorKind::ResourceBusy
+            | ErrorKind::StaleNetworkFileHandle
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_io_persists_after_transient_failure() {
+        let path = std::env::temp_dir().join(format!("retry_{}.json", std::process::id()));
+        let mut calls = 0;
+        // Fails like a flaky network mount once, then writes for real
+        let mut writer = || {
+            calls += 1;
+            if calls == 1 {
+                return Err(std::io::Error::new(ErrorKind::TimedOut, "mount timed out"));
+            }
+            std::fs::write(&path, "{\"current_index\":2}")
+        };
+
+        retry_io(2, &mut writer).unwrap();
+        assert_eq!(calls, 2);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"current_index\":2}"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_retry_io_gives_up() {
+        let mut calls = 0;
+        let result: std::io::Result<()> = retry_io(3, || {
+            calls += 1;
+            Err(ErrorKind::ResourceBusy.into())
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        // Errors a retry cannot fix are returned at once
+        calls = 0;
+        let result: std::io::Result<()> = retry_io(3, || {
+            calls += 1;
+            Err(ErrorKind::PermissionDenied.into())
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("Hello", 10), "Hello");
+        assert_eq!(truncate("Hello, World!", 5), "Hello...");
+        assert_eq!(truncate("Hi", 2), "Hi");
+        assert_eq!(truncate("Привет, мир", 6), "Привет...");
+    }
+
+    #[test]
+    fn test_truncate_keeps_emoji_clusters() {
+        let family = "👨\u{200d}👩\u{200d}👧\u{200d}👦";
+        let flag = "🇺🇦";
+
+        let text = format!("{family}{flag}!");
+        assert_eq!(truncate(&text, 1), format!("{family}..."));
+        assert_eq!(truncate(&text, 2), format!("{family}{flag}..."));
+        assert_eq!(truncate(&text, 3), text);
+
+        // A flag is two regional indicators but one cluster
+        assert_eq!(truncate(&format!("a{flag}b"), 2), format!("a{flag}..."));
+    }
+}
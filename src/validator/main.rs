@@ -9,8 +9,10 @@ use clap::Parser;
 
 // Import from the main crate
 use description_user_bot::config::{
-    DescriptionConfig, MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM,
+    BotSettings, DescriptionConfig, MAX_BIO_LENGTH_FREE, MAX_BIO_LENGTH_PREMIUM, ValidationError,
+    normalize_bio, utf16_len,
 };
+use description_user_bot::util::truncate;
 
 /// Description configuration validator.
 #[derive(Parser, Debug)]
@@ -33,6 +35,10 @@ struct Args {
     /// Show detailed information for each description.
     #[arg(short, long)]
     verbose: bool,
+
+    /// Minimum update interval in seconds; shorter durations are reported.
+    #[arg(long, default_value_t = BotSettings::default().min_update_interval_secs)]
+    min_interval: u64,
 }
 
 fn main() -> ExitCode {
@@ -44,7 +50,7 @@ fn main() -> ExitCode {
     }
 
     // Validate the configuration file
-    validate_config(&args.file, args.premium, args.verbose)
+    validate_config(&args.file, args.premium, args.verbose, args.min_interval)
 }
 
 fn generate_example(output_path: &str) -> ExitCode {
@@ -67,7 +73,7 @@ fn generate_example(output_path: &str) -> ExitCode {
     }
 }
 
-fn validate_config(path: &str, premium: bool, verbose: bool) -> ExitCode {
+fn validate_config(path: &str, premium: bool, verbose: bool, min_interval: u64) -> ExitCode {
     println!("Validating: {path}");
     println!(
         "Account type: {}\n",
@@ -96,34 +102,43 @@ fn validate_config(path: &str, premium: bool, verbose: bool) -> ExitCode {
     let results = config.validate_all();
 
     let mut errors = 0;
+    let mut utf16_overflows = 0;
     let mut warnings = 0;
 
     for (i, result) in results.iter().enumerate() {
-        let desc = &config.descriptions[i];
+        let desc = &config.active_descriptions()[i];
         let char_count = desc.char_count();
+        let utf16_count = utf16_len(normalize_bio(&desc.text));
 
         if verbose {
+            let units = if utf16_count == char_count {
+                String::new()
+            } else {
+                format!(", {utf16_count} UTF-16 units")
+            };
             println!(
-                "[{}] \"{}\" ({} chars, {}s)",
+                "[{}] \"{}\" ({} chars{}, {}s)",
                 desc.id,
                 truncate(&desc.text, 40),
                 char_count,
+                units,
                 desc.duration_secs
             );
         }
 
         match result {
+            // Telegram counts UTF-16 code units: emoji outside the BMP take two
+            Err(ValidationError::TooLong { length, .. }) if char_count <= max_length => {
+                errors += 1;
+                utf16_overflows += 1;
+                println!(
+                    "  ✗ UTF-16 overflow: [{}] is {char_count} chars but {length} UTF-16 \
+                     units, over the {max_length} limit Telegram enforces",
+                    desc.id
+                );
+            }
             Ok(()) => {
-                // Check for warnings (close to limit)
-                let warn_threshold = max_length * 90 / 100; // 90% of max
-                if char_count > warn_threshold {
-                    warnings += 1;
-                    if verbose {
-                        println!(
-                            "  ⚠ Warning: {char_count} chars is close to the {max_length} char limit"
-                        );
-                    }
-                } else if verbose {
+                if verbose {
                     println!("  ✓ OK");
                 }
             }
@@ -134,6 +149,17 @@ fn validate_config(path: &str, premium: bool, verbose: bool) -> ExitCode {
         }
     }
 
+    for warning in config.warnings() {
+        warnings += 1;
+        println!("⚠ Warning: {warning}");
+    }
+
+    // Likely mistakes (near-limit texts, duplicates, odd durations, ...)
+    for warning in config.lint(min_interval) {
+        warnings += 1;
+        println!("⚠ Lint: {warning}");
+    }
+
     println!();
 
     // Summary
@@ -144,7 +170,7 @@ fn validate_config(path: &str, premium: bool, verbose: bool) -> ExitCode {
         println!("✓ All {total} descriptions are valid!");
 
         if warnings > 0 {
-            println!("  ({warnings} warning(s) - descriptions close to character limit)");
+            println!("  ({warnings} warning(s))");
         }
 
         // Show character limit info
@@ -159,18 +185,37 @@ fn validate_config(path: &str, premium: bool, verbose: bool) -> ExitCode {
         ExitCode::SUCCESS
     } else {
         println!("✗ Validation failed: {errors} error(s) in {total} descriptions");
+        if utf16_overflows > 0 {
+            println!("  ({utf16_overflows} fit by characters but overflow in UTF-16 units)");
+        }
         println!("  Valid: {valid}/{total}");
 
         ExitCode::FAILURE
     }
 }
 
-/// Truncates a string for display.
-fn truncate(s: &str, max_len: usize) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    if chars.len() <= max_len {
-        s.to_owned()
-    } else {
-        format!("{}...", chars[..max_len].iter().collect::<String>())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        format!(
+            "{}/src/validator/fixtures/{name}",
+            env!("CARGO_MANIFEST_DIR")
+        )
+    }
+
+    #[test]
+    fn test_utf16_overflow_fails_validation() {
+        let path = fixture("utf16_overflow.json");
+        let config = DescriptionConfig::load_from_file(&path).unwrap();
+        let moons = &config.active_descriptions()[1];
+
+        // Passes the scalar count, fails Telegram's UTF-16 limit
+        assert!(moons.char_count() <= MAX_BIO_LENGTH_FREE);
+        assert!(utf16_len(&moons.text) > MAX_BIO_LENGTH_FREE);
+
+        assert_eq!(validate_config(&path, false, true, 5), ExitCode::FAILURE);
+        assert_eq!(validate_config(&path, true, false, 5), ExitCode::SUCCESS);
     }
 }